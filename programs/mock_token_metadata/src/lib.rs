@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+
+declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Account key of a Metaplex `MetadataV1` account
+const METADATA_V1_KEY: u8 = 4;
+
+/// Stand-in for the Metaplex Token Metadata program, deployed at its address in
+/// local tests. It only writes the metadata fields read by collection token gates.
+#[program]
+pub mod mock_token_metadata {
+    use super::*;
+
+    /// Create the metadata account of a mint, optionally naming a collection
+    ///
+    /// Accounts:
+    /// 0. `[writable]` metadata: The metadata PDA of the mint to create
+    /// 1. `[]` mint: The mint the metadata describes
+    /// 2. `[writable, signer]` payer: Pays for the account and becomes its update authority
+    /// 3. `[]` system_program: System program for account creation
+    ///
+    /// Data:
+    /// - collection: [Option<Pubkey>] The collection key the NFT belongs to
+    /// - verified: [bool] Whether the collection membership is verified
+    pub fn create_metadata(
+        ctx: Context<CreateMetadata>,
+        collection: Option<Pubkey>,
+        verified: bool,
+    ) -> Result<()> {
+        let metadata = Metadata {
+            key: METADATA_V1_KEY,
+            update_authority: ctx.accounts.payer.key(),
+            mint: ctx.accounts.mint.key(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: collection.map(|key| Collection { verified, key }),
+        };
+        let data = metadata.try_to_vec()?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"metadata",
+            crate::ID.as_ref(),
+            mint_key.as_ref(),
+            &[ctx.bumps.metadata],
+        ]];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.metadata.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(data.len()),
+            data.len() as u64,
+            &crate::ID,
+        )?;
+
+        ctx.accounts.metadata.try_borrow_mut_data()?.copy_from_slice(&data);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateMetadata<'info> {
+    /// CHECK: Metadata PDA of the mint, created and written by this instruction
+    #[account(
+        mut,
+        seeds = [b"metadata", crate::ID.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Only its address is recorded
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Leading fields of a Metaplex metadata account, in its Borsh layout
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+}
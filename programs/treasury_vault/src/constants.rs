@@ -16,6 +16,40 @@ pub enum AuditAction {
     EpochDurationUpdated = 11,
     TokenDeposit = 12,
     TokenPayout = 13,
+    CreateProposal = 14,
+    ApproveProposal = 15,
+    RevokeApproval = 16,
+    ExecuteProposal = 17,
+    DeactivateUser = 18,
+    ReactivateUser = 19,
+    UpdateUserRole = 20,
+    ProposeAdmin = 21,
+    AcceptAdmin = 22,
+    CancelAdminTransfer = 23,
+    UpdateRecipient = 24,
+    RemoveRecipient = 25,
+    TokenSpendingLimitUpdated = 26,
+    CrankTipPaid = 27,
+    PayoutCompleted = 28,
+    CreateVestingStream = 29,
+    ClaimVesting = 30,
+    CancelVestingStream = 31,
+    ClaimPayout = 32,
+    AuditLogClosed = 33,
+    PayoutScheduleClosed = 34,
+    AuditLogRetentionUpdated = 35,
+    TreasuryConfigUpdated = 36,
+    WithdrawalQueued = 37,
+    WithdrawalVetoed = 38,
+    WithdrawalTimelockUpdated = 39,
+    UpdateUserPermissions = 40,
+    UserSpendingLimitUpdated = 41,
+    RecipientSpendingLimitUpdated = 42,
+    SpendingLimitModeUpdated = 43,
+    WithdrawalWhitelistUpdated = 44,
+    CreateDistribution = 45,
+    ClaimDistribution = 46,
+    ReclaimDistribution = 47,
 }
 
 pub const TREASURY_SEED: &[u8] = b"treasury";
@@ -25,6 +59,41 @@ pub const PAYOUT_SEED: &[u8] = b"payout";
 pub const AUDIT_SEED: &[u8] = b"audit";
 pub const TOKEN_BALANCE_SEED: &[u8] = b"token_balance";
 pub const TREASURY_TOKEN_ACCOUNT_SEED: &[u8] = b"treasury_token";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const VESTING_SEED: &[u8] = b"vesting";
+pub const PENDING_WITHDRAWAL_SEED: &[u8] = b"pending_withdrawal";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const DISTRIBUTION_SEED: &[u8] = b"distribution";
 
 // Minimum epoch duration in seconds (1 hour)
-pub const MIN_EPOCH_DURATION: u64 = 3600;
\ No newline at end of file
+pub const MIN_EPOCH_DURATION: u64 = 3600;
+
+// Maximum length of a whitelisted recipient name in bytes
+pub const MAX_RECIPIENT_NAME_LEN: usize = 32;
+
+// Maximum number of approvals a single proposal can record
+pub const MAX_PROPOSAL_APPROVERS: usize = 10;
+
+// Maximum lamport tip paid to a permissionless crank caller per payout (0.01 SOL)
+pub const MAX_CRANK_TIP: u64 = 10_000_000;
+
+// Maximum number of missed recurring periods settled by a single payout execution
+pub const MAX_CATCH_UP_PERIODS: u64 = 12;
+
+// Default age in seconds after which audit logs can be closed (90 days)
+pub const DEFAULT_AUDIT_LOG_RETENTION: u64 = 90 * 86400;
+
+// Maximum delay of a queued withdrawal in seconds (30 days)
+pub const MAX_WITHDRAWAL_TIMELOCK_DELAY: u64 = 30 * 86400;
+
+// Maximum number of per-mint spending limits on a single user or recipient
+pub const MAX_EPOCH_LIMITS: usize = 4;
+
+// Number of time buckets tracking spending over a rolling window
+pub const ROLLING_WINDOW_BUCKETS: usize = 24;
+
+// Maximum number of claims in a single merkle distribution, one bit each in its claimed bitmap
+pub const MAX_DISTRIBUTION_CLAIMS: usize = 2048;
+
+// Metaplex Token Metadata program, owner of the NFT metadata checked by collection gates
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
\ No newline at end of file
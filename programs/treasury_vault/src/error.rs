@@ -66,4 +66,128 @@ pub enum ErrorCode {
     TreasuryTokenAccountNotFound,
     #[msg("Invalid token account")]
     InvalidTokenAccount,
+    #[msg("Approval threshold must be between one and the maximum number of approvers")]
+    InvalidApprovalThreshold,
+    #[msg("This action requires an approved proposal")]
+    MultisigApprovalRequired,
+    #[msg("Invalid proposal action")]
+    InvalidProposalAction,
+    #[msg("Proposal expiry must be in the future")]
+    InvalidProposalExpiry,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Signer has already approved this proposal")]
+    ProposalAlreadyApproved,
+    #[msg("Signer has not approved this proposal")]
+    ProposalNotApproved,
+    #[msg("Proposal has reached the maximum number of approvals")]
+    MaxApprovalsReached,
+    #[msg("Proposal does not have enough approvals")]
+    InsufficientApprovals,
+    #[msg("Account does not match the proposal")]
+    ProposalAccountMismatch,
+    #[msg("The last active admin cannot be deactivated or demoted")]
+    LastActiveAdmin,
+    #[msg("Treasury user is already active")]
+    UserAlreadyActive,
+    #[msg("Treasury user is already inactive")]
+    UserAlreadyInactive,
+    #[msg("Treasury user already has this role")]
+    RoleUnchanged,
+    #[msg("Only the current treasury admin can manage an admin transfer")]
+    UnauthorizedAdminTransfer,
+    #[msg("New admin must differ from the current admin")]
+    InvalidPendingAdmin,
+    #[msg("No admin transfer is pending for this signer")]
+    NoPendingAdminTransfer,
+    #[msg("Recipient name must be at most 32 bytes")]
+    RecipientNameTooLong,
+    #[msg("All active payout schedules of the recipient must be cancelled before removal")]
+    ActivePayoutsRemaining,
+    #[msg("Payout schedule does not belong to this recipient")]
+    PayoutRecipientMismatch,
+    #[msg("Crank tip exceeds the maximum allowed tip")]
+    CrankTipTooLarge,
+    #[msg("End time and execution cap are only valid for recurring payouts")]
+    InvalidPayoutBounds,
+    #[msg("End time must not be before the schedule time")]
+    InvalidEndTime,
+    #[msg("Maximum executions must be greater than zero")]
+    InvalidMaxExecutions,
+    #[msg("Invalid catch-up policy")]
+    InvalidCatchUpPolicy,
+    #[msg("Vesting must end after it starts and the cliff must fall within the vesting period")]
+    InvalidVestingSchedule,
+    #[msg("Nothing has vested that is not already claimed")]
+    NothingToClaim,
+    #[msg("Vesting stream has already fully vested or been cancelled")]
+    VestingEnded,
+    #[msg("Only inactive payout schedules can be closed")]
+    PayoutStillActive,
+    #[msg("Audit log is still within the retention period")]
+    AuditLogRetentionNotElapsed,
+    #[msg("Rent must be returned to the account's original payer")]
+    RentReceiverMismatch,
+    #[msg("At least one audit log must be supplied")]
+    EmptyAuditRange,
+    #[msg("Audit log does not belong to this treasury")]
+    AuditLogTreasuryMismatch,
+    #[msg("Audit log hash chain is broken")]
+    AuditChainBroken,
+    #[msg("Withdrawals above the timelock threshold must be queued")]
+    WithdrawalTimelockRequired,
+    #[msg("The withdrawal delay has not elapsed yet")]
+    WithdrawalTimelockActive,
+    #[msg("Withdrawal delay exceeds the maximum allowed delay")]
+    InvalidWithdrawalDelay,
+    #[msg("Recipient does not match the pending withdrawal")]
+    WithdrawalRecipientMismatch,
+    #[msg("Pause duration must be greater than zero")]
+    InvalidPauseDuration,
+    #[msg("Unknown permission bits")]
+    InvalidPermissions,
+    #[msg("Cannot grant permissions the caller does not hold")]
+    PermissionEscalation,
+    #[msg("Admins always hold every permission")]
+    AdminPermissionsFixed,
+    #[msg("Withdrawal would exceed the user's spending limit for the current epoch")]
+    UserSpendingLimitExceeded,
+    #[msg("Payment would exceed the recipient's spending limit for the current epoch")]
+    RecipientSpendingLimitExceeded,
+    #[msg("Too many per-mint spending limits on this account")]
+    TooManyEpochLimits,
+    #[msg("Invalid spending limit mode")]
+    InvalidSpendingLimitMode,
+    #[msg("Token gate minimum balance must be greater than zero")]
+    InvalidGateMinBalance,
+    #[msg("Invalid token gate mode")]
+    InvalidTokenGateMode,
+    #[msg("Invalid distribution parameters")]
+    InvalidDistribution,
+    #[msg("Distribution has expired")]
+    DistributionExpired,
+    #[msg("Distribution has not expired yet")]
+    DistributionNotExpired,
+    #[msg("Claim index is outside the distribution")]
+    InvalidDistributionIndex,
+    #[msg("Distribution share has already been claimed")]
+    DistributionAlreadyClaimed,
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Claims would exceed the distribution total")]
+    DistributionExhausted,
+    #[msg("Approval threshold cannot exceed the number of active users who can approve")]
+    ApprovalThresholdUnreachable,
+    #[msg("Audit log retention must be greater than zero")]
+    InvalidAuditLogRetention,
+    #[msg("Approver accounts must be the treasury user accounts of the proposal's approvers, in order")]
+    ApproverAccountMismatch,
+    #[msg("Tip audit log must be passed exactly when a crank tip is paid")]
+    InvalidTipAuditLog,
+    #[msg("All vesting streams of the recipient must be cancelled and settled before removal")]
+    ActiveVestingStreamsRemaining,
+    #[msg("Only an admin can change the role or status of another admin")]
+    AdminTargetRequiresAdmin,
 }
\ No newline at end of file
@@ -3,6 +3,8 @@ use anchor_lang::prelude::*;
 #[event]
 pub struct TreasuryInitializedEvent {
     pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub treasury_id: u64,
     pub epoch_duration: u64,
     pub spending_limit: u64,
 }
@@ -46,6 +48,7 @@ pub struct TreasuryEvent {
 pub struct TreasuryPausedEvent {
     pub admin: Pubkey,
     pub treasury: Pubkey,
+    pub expires_at: Option<i64>,
     pub timestamp: i64,
 }
 
@@ -69,6 +72,8 @@ pub struct TokenGateSetEvent {
     pub admin: Pubkey,
     pub treasury: Pubkey,
     pub token_mint: Option<Pubkey>,
+    pub min_balance: u64,
+    pub mode: u8,
     pub timestamp: i64,
 }
 
@@ -104,5 +109,314 @@ pub struct TokenPayoutEvent {
     pub recipient: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
+    pub periods_settled: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutEvent {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub periods_settled: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreatedEvent {
+    pub proposer: Pubkey,
+    pub treasury: Pubkey,
+    pub proposal: Pubkey,
+    pub action: u8,
+    pub target: Option<Pubkey>,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApprovedEvent {
+    pub approver: Pubkey,
+    pub treasury: Pubkey,
+    pub proposal: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApprovalRevokedEvent {
+    pub approver: Pubkey,
+    pub treasury: Pubkey,
+    pub proposal: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecutedEvent {
+    pub executor: Pubkey,
+    pub treasury: Pubkey,
+    pub proposal: Pubkey,
+    pub action: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryUserUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub user: Pubkey,
+    pub role: u8,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferProposedEvent {
+    pub treasury: Pubkey,
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferAcceptedEvent {
+    pub treasury: Pubkey,
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferCancelledEvent {
+    pub treasury: Pubkey,
+    pub admin: Pubkey,
+    pub cancelled_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecipientUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub name: String,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecipientRemovedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub cancelled_payouts: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenSpendingLimitUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_limit: u64,
+    pub new_limit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrankTipPaidEvent {
+    pub treasury: Pubkey,
+    pub cranker: Pubkey,
+    pub payout_schedule: Pubkey,
+    pub tip: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutCompletedEvent {
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub payout_schedule: Pubkey,
+    pub execution_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingStreamCreatedEvent {
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub vesting_stream: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct VestingClaimedEvent {
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub vesting_stream: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingStreamCancelledEvent {
+    pub treasury: Pubkey,
+    pub vesting_stream: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub vested_amount: u64,
+    pub returned_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuditLogClosedEvent {
+    pub treasury: Pubkey,
+    pub audit_log: Pubkey,
+    pub commitment: [u8; 32],
+    pub audit_digest: [u8; 32],
+    pub closed_audit_log_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutScheduleClosedEvent {
+    pub treasury: Pubkey,
+    pub payout_schedule: Pubkey,
+    pub rent_receiver: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuditRangeVerifiedEvent {
+    pub treasury: Pubkey,
+    pub first_sequence: u64,
+    pub last_sequence: u64,
+    pub last_entry_hash: [u8; 32],
+    pub reaches_chain_head: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalTimelockUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub threshold: u64,
+    pub delay: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalQueuedEvent {
+    pub treasury: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub requested_by: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PendingWithdrawalExecutedEvent {
+    pub treasury: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub executor: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalVetoedEvent {
+    pub treasury: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserPermissionsUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub user: Pubkey,
+    pub old_permissions: u16,
+    pub new_permissions: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserSpendingLimitUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub user: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub old_limit: Option<u64>,
+    pub new_limit: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecipientSpendingLimitUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub recipient: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub old_limit: Option<u64>,
+    pub new_limit: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalWhitelistUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub required: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionCreatedEvent {
+    pub treasury: Pubkey,
+    pub distribution: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub claim_count: u32,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionClaimedEvent {
+    pub treasury: Pubkey,
+    pub distribution: Pubkey,
+    pub claimant: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub index: u32,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionReclaimedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub distribution: Pubkey,
+    pub token_mint: Option<Pubkey>,
+    pub amount: u64,
     pub timestamp: i64,
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut)]
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = treasury.pending_admin == Some(new_admin.key()) @ ErrorCode::NoPendingAdminTransfer
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, treasury.admin.as_ref(), treasury.key().as_ref()],
+        bump = previous_admin_user.bump
+    )]
+    pub previous_admin_user: Account<'info, TreasuryUser>,
+
+    #[account(
+        init_if_needed,
+        payer = new_admin,
+        space = 8 + TreasuryUser::INIT_SPACE,
+        seeds = [USER_SEED, new_admin.key().as_ref(), treasury.key().as_ref()],
+        bump
+    )]
+    pub new_admin_user: Account<'info, TreasuryUser>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accept a pending admin handover. The new admin's user record is created
+/// or promoted to an active admin, and the previous admin's record is deactivated.
+///
+/// Accounts:
+/// 0. `[writable, signer]` new_admin: The pending admin accepting the handover
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` previous_admin_user: The treasury user account of the current admin
+/// 3. `[writable]` new_admin_user: The treasury user account of the new admin
+/// 4. `[]` system_program: System program for account creation
+pub fn handler(
+    ctx: Context<AcceptAdmin>,
+) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    let treasury_key = treasury.key();
+    let previous_admin = treasury.admin;
+    let new_admin = ctx.accounts.new_admin.key();
+
+    // Promote the new admin first so the active admin count never drops to zero
+    let new_admin_user = &mut ctx.accounts.new_admin_user;
+    let was_approver = new_admin_user.can_approve();
+    if new_admin_user.treasury == Pubkey::default() {
+        new_admin_user.user = new_admin;
+        new_admin_user.treasury = treasury_key;
+        new_admin_user.bump = ctx.bumps.new_admin_user;
+        new_admin_user.role = Role::Admin as u8;
+        new_admin_user.permissions = ALL_PERMISSIONS;
+        new_admin_user.is_active = true;
+        treasury.increment_admin_count()?;
+    } else if !new_admin_user.is_admin() {
+        new_admin_user.role = Role::Admin as u8;
+        new_admin_user.permissions = ALL_PERMISSIONS;
+        new_admin_user.is_active = true;
+        treasury.increment_admin_count()?;
+    }
+    treasury.update_approver_count(was_approver, true)?;
+
+    // Retire the previous admin key
+    let previous_admin_user = &mut ctx.accounts.previous_admin_user;
+    if previous_admin_user.is_admin() {
+        treasury.decrement_admin_count()?;
+    }
+    let was_approver = previous_admin_user.can_approve();
+    previous_admin_user.is_active = false;
+    treasury.update_approver_count(was_approver, false)?;
+
+    treasury.admin = new_admin;
+    treasury.pending_admin = None;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Emit events
+    emit!(AdminTransferAcceptedEvent {
+        treasury: treasury_key,
+        previous_admin,
+        new_admin,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::AcceptAdmin as u8,
+        treasury: treasury_key,
+        initiator: new_admin,
+        target: Some(previous_admin),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+
+    Ok(())
+}
\ No newline at end of file
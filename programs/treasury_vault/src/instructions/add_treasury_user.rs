@@ -5,6 +5,7 @@ use crate::{
     error::ErrorCode,
     events::*,
     state::*,
+    utils::execute_user_proposal,
 };
 
 #[derive(Accounts)]
@@ -14,12 +15,19 @@ pub struct AddTreasuryUser<'info> {
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
-        bump = treasury.bump,
-        constraint = treasury.admin == admin.key() @ ErrorCode::UnauthorizedUser
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
     
+    #[account(
+        seeds = [USER_SEED, admin.key().as_ref(), treasury.key().as_ref()],
+        bump = admin_user.bump,
+        constraint = admin_user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = admin_user.has_permission(Permission::ManageUsers) @ ErrorCode::UnauthorizedUser
+    )]
+    pub admin_user: Account<'info, TreasuryUser>,
+    
     #[account(
         init,
         payer = admin,
@@ -32,6 +40,27 @@ pub struct AddTreasuryUser<'info> {
     /// CHECK: This is just a pubkey that will be stored
     pub user: UncheckedAccount<'info>,
     
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Approved proposal for this change, required while the treasury requires multisig
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -40,21 +69,45 @@ pub fn handler(
     role: u8,
 ) -> Result<()> {
     // Validate role
+    let permissions = Role::from_u8(role)
+        .ok_or(ErrorCode::InvalidRole)?
+        .default_permissions();
     require!(
-        role == Role::Admin as u8 || role == Role::Treasurer as u8,
-        ErrorCode::InvalidRole
+        permissions & !ctx.accounts.admin_user.permissions == 0,
+        ErrorCode::PermissionEscalation
     );
     
+    // Under multisig the change needs an approved proposal
+    execute_user_proposal(
+        &ctx.accounts.treasury,
+        ctx.accounts.proposal.as_mut(),
+        ctx.remaining_accounts,
+        ctx.accounts.admin.key(),
+        ProposalAction::AddUser,
+        ctx.accounts.user.key(),
+        Some(role),
+        None,
+    )?;
+    
     let user_account = &mut ctx.accounts.user_account;
-    let treasury = &ctx.accounts.treasury;
+    let treasury = &mut ctx.accounts.treasury;
+    
+    // Track active admins so the last one can never be removed
+    if role == Role::Admin as u8 {
+        treasury.increment_admin_count()?;
+    }
     
     // Initialize user account
     user_account.user = ctx.accounts.user.key();
     user_account.role = role;
+    user_account.permissions = permissions;
     user_account.is_active = true;
     user_account.treasury = treasury.key();
     user_account.bump = ctx.bumps.user_account;
     
+    // Users holding Withdraw can approve proposals
+    treasury.update_approver_count(false, user_account.can_approve())?;
+    
     // Create audit log
     emit!(TreasuryEvent {
         action: AuditAction::AddUser as u8,
@@ -66,5 +119,26 @@ pub fn handler(
         token_mint: None, // Not token related
     });
     
+    // Create audit log entry
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::AddUser as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.admin.key();
+    audit_log.target = Some(ctx.accounts.user.key());
+    audit_log.amount = 0;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = None;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
     Ok(())
 }
\ No newline at end of file
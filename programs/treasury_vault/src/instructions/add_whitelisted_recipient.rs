@@ -14,7 +14,7 @@ pub struct AddWhitelistedRecipient<'info> {
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -23,7 +23,7 @@ pub struct AddWhitelistedRecipient<'info> {
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Admin) @ ErrorCode::UnauthorizedUser
+        constraint = user.has_permission(Permission::ManageRecipients) @ ErrorCode::UnauthorizedUser
     )]
     pub user: Account<'info, TreasuryUser>,
     
@@ -39,6 +39,19 @@ pub struct AddWhitelistedRecipient<'info> {
     /// CHECK: This is just a pubkey that will be stored
     pub recipient: UncheckedAccount<'info>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -57,6 +70,8 @@ pub fn handler(
     recipient_account.name = name;
     recipient_account.is_active = true;
     recipient_account.treasury = treasury.key();
+    recipient_account.active_payout_count = 0;
+    recipient_account.active_vesting_count = 0;
     recipient_account.bump = ctx.bumps.recipient_account;
     
     // Create audit log
@@ -70,5 +85,26 @@ pub fn handler(
         token_mint: None, // Not token related
     });
     
+    // Create audit log entry
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::AddRecipient as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(ctx.accounts.recipient.key());
+    audit_log.amount = 0;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = None;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Withdraw) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Record the signer's approval on a pending proposal
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The approving user
+/// 1. `[readable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` proposal: The proposal being approved
+pub fn handler(
+    ctx: Context<ApproveProposal>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let approver = ctx.accounts.authority.key();
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(!proposal.is_expired(current_time), ErrorCode::ProposalExpired);
+    require!(!proposal.has_approved(&approver), ErrorCode::ProposalAlreadyApproved);
+    require!(
+        proposal.approvals.len() < MAX_PROPOSAL_APPROVERS,
+        ErrorCode::MaxApprovalsReached
+    );
+
+    proposal.approvals.push(approver);
+
+    // Emit events
+    emit!(ProposalApprovedEvent {
+        approver,
+        treasury: ctx.accounts.treasury.key(),
+        proposal: proposal.key(),
+        approvals: proposal.approval_count(),
+        threshold: ctx.accounts.treasury.approval_threshold,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::ApproveProposal as u8,
+        treasury: ctx.accounts.treasury.key(),
+        initiator: approver,
+        target: Some(proposal.key()),
+        amount: proposal.amount,
+        timestamp: current_time,
+        token_mint: proposal.token_mint,
+    });
+
+    Ok(())
+}
\ No newline at end of file
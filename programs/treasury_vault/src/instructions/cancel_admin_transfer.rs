@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = treasury.admin == authority.key() @ ErrorCode::UnauthorizedAdminTransfer
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+/// Cancel a pending admin handover
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The current treasury admin who proposed the handover
+/// 1. `[writable]` treasury: The treasury account
+pub fn handler(
+    ctx: Context<CancelAdminTransfer>,
+) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+
+    let cancelled_admin = treasury.pending_admin.ok_or(ErrorCode::NoPendingAdminTransfer)?;
+    treasury.pending_admin = None;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Emit events
+    emit!(AdminTransferCancelledEvent {
+        treasury: treasury.key(),
+        admin: ctx.accounts.authority.key(),
+        cancelled_admin,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::CancelAdminTransfer as u8,
+        treasury: treasury.key(),
+        initiator: ctx.accounts.authority.key(),
+        target: Some(cancelled_admin),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -14,7 +14,7 @@ pub struct CancelPayout<'info> {
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -23,11 +23,12 @@ pub struct CancelPayout<'info> {
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Treasurer) @ ErrorCode::UnauthorizedUser
+        constraint = user.has_permission(Permission::Cancel) @ ErrorCode::UnauthorizedUser
     )]
     pub user: Account<'info, TreasuryUser>,
     
     #[account(
+        mut,
         seeds = [RECIPIENT_SEED, recipient.recipient.as_ref(), treasury.key().as_ref()],
         bump = recipient.bump
     )]
@@ -46,6 +47,19 @@ pub struct CancelPayout<'info> {
     )]
     pub payout_schedule: Account<'info, PayoutSchedule>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -57,6 +71,7 @@ pub fn handler(
     
     // Deactivate the payout schedule
     payout_schedule.is_active = false;
+    ctx.accounts.recipient.track_payout_closed()?;
     
     // Create audit log
     emit!(TreasuryEvent {
@@ -69,5 +84,26 @@ pub fn handler(
         token_mint: payout_schedule.token_mint, // Use the token mint from the payout schedule
     });
     
+    // Create audit log entry
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::CancelPayout as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(ctx.accounts.recipient.recipient);
+    audit_log.amount = ctx.accounts.payout_schedule.amount;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = ctx.accounts.payout_schedule.token_mint;
+    audit_log.payout_schedule = Some(ctx.accounts.payout_schedule.key());
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
     Ok(())
 }
\ No newline at end of file
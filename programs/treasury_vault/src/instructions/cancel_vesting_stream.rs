@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct CancelVestingStream<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Cancel) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, recipient.recipient.as_ref(), treasury.key().as_ref()],
+        bump = recipient.bump
+    )]
+    pub recipient: Account<'info, WhitelistedRecipient>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            recipient.recipient.as_ref(),
+            treasury.key().as_ref(),
+            &vesting_stream.index.to_le_bytes()
+        ],
+        bump = vesting_stream.bump
+    )]
+    pub vesting_stream: Account<'info, VestingStream>,
+    
+    /// Token balance of the stream's mint, required for SPL token streams
+    #[account(mut)]
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Stop a vesting stream and return its unvested remainder to the treasury.
+/// Whatever vested before cancellation stays claimable by the recipient.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: Treasurer cancelling the stream
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` recipient: The whitelisted recipient of the stream
+/// 4. `[writable]` vesting_stream: The vesting stream to cancel
+/// 5. `[writable, optional]` token_balance: The token balance for SPL token streams
+/// 6. `[]` system_program: System program
+pub fn handler(
+    ctx: Context<CancelVestingStream>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    
+    let vesting_stream = &mut ctx.accounts.vesting_stream;
+    require!(current_time < vesting_stream.end_time, ErrorCode::VestingEnded);
+    
+    let token_mint = vesting_stream.token_mint;
+    let returned_amount = vesting_stream.cancel(current_time)?;
+    
+    // Make the unvested remainder spendable again
+    if let Some(token_mint) = token_mint {
+        let token_balance = ctx.accounts.token_balance.as_mut()
+            .ok_or(ErrorCode::InvalidTokenMint)?;
+        require!(
+            token_balance.treasury == treasury_key && token_balance.token_mint == token_mint,
+            ErrorCode::InvalidTokenMint
+        );
+        
+        token_balance.release(returned_amount)?;
+    } else {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_funds = treasury.total_funds.checked_add(returned_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+    
+    // Close the stream right away when nothing vested is left to claim
+    if vesting_stream.is_settled(current_time) {
+        vesting_stream.is_active = false;
+        ctx.accounts.recipient.track_vesting_closed()?;
+    }
+    
+    emit!(VestingStreamCancelledEvent {
+        treasury: treasury_key,
+        vesting_stream: vesting_stream.key(),
+        token_mint,
+        vested_amount: vesting_stream.total_amount,
+        returned_amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::CancelVestingStream as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(vesting_stream.recipient),
+        amount: returned_amount,
+        timestamp: current_time,
+        token_mint,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::{charge_recipient_limit, check_token_gate, require_whitelisted_recipient, transfer_tokens_checked},
+};
+
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    /// Claimant named by the proven leaf, receives SOL distributions
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_SEED, treasury.key().as_ref(), &distribution.index.to_le_bytes()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    
+    /// CHECK: Whitelist entry of the claimant, which may not exist. Its address
+    /// is pinned by the seeds so the whitelist policy and recipient limits always apply
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump
+    )]
+    pub whitelisted_recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Token account of the claimant holding the gate token, only required if token gate is enabled
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata account of the gate NFT, only required in collection gate mode.
+    /// Its address, owner and contents are validated by check_token_gate
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+    
+    /// Token balance of the distribution's mint, recorded in the audit log of SPL token claims
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+    
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: The claimant's associated token account for the distribution's mint, created if needed.
+    /// The associated token program validates its address.
+    #[account(mut)]
+    pub recipient_token_account: Option<UncheckedAccount<'info>>,
+    
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim a share of a merkle distribution by proving its leaf
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The claimant, receives SOL distributions
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` distribution: The distribution to claim from
+/// 3. `[writable]` whitelisted_recipient: The claimant's whitelist entry PDA, which may not exist
+/// 4. `[writable]` audit_log: The audit log account to create
+/// 5. `[optional]` gate_token_account: The claimant's gate token account if token gate is enabled
+/// 6. `[optional]` gate_metadata: Metadata of the gate NFT if the token gate is in collection mode
+/// 7. `[optional]` token_balance: The token balance for SPL token distributions
+/// 8. `[writable, optional]` treasury_token_account: The treasury's token account
+/// 9. `[writable, optional]` recipient_token_account: The claimant's associated token account
+/// 10. `[optional]` token_mint: The token mint for SPL token distributions
+/// 11. `[optional]` token_program: SPL Token or Token-2022 program for SPL token distributions
+/// 12. `[optional]` associated_token_program: Associated token program to create the claimant's token account
+/// 13. `[]` system_program: System program for account creation
+/// 14. `[]` remaining_accounts: Extra accounts required by a Token-2022 transfer hook
+///
+/// Data:
+/// - index: [u32] Index of the claimant's leaf
+/// - amount: [u64] Amount named by the leaf
+/// - proof: [Vec<[u8; 32]>] Sibling hashes from the leaf up to the merkle root
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimDistribution<'info>>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    timestamp: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Validate timestamp
+    require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let claimant_key = ctx.accounts.authority.key();
+    require!(
+        !ctx.accounts.distribution.is_expired(current_time),
+        ErrorCode::DistributionExpired
+    );
+    
+    // Check if token gate is enabled and validate the claimant's gate tokens
+    check_token_gate(
+        &ctx.accounts.treasury,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_deref(),
+        claimant_key,
+    )?;
+    
+    // When the treasury requires it, the claimant must be an active whitelisted recipient
+    require_whitelisted_recipient(&ctx.accounts.treasury, &ctx.accounts.whitelisted_recipient)?;
+    
+    // Prove the leaf and mark it claimed
+    let distribution = &mut ctx.accounts.distribution;
+    let token_mint = distribution.token_mint;
+    let leaf = Distribution::leaf_hash(index, &claimant_key, token_mint, amount);
+    require!(distribution.verify_proof(&proof, leaf), ErrorCode::InvalidMerkleProof);
+    distribution.record_claim(index, amount)?;
+    let distribution_key = distribution.key();
+    let claimed_amount = distribution.claimed_amount;
+    
+    // The treasury limit was charged when the distribution was created, the claimant's own limit applies now
+    charge_recipient_limit(
+        &ctx.accounts.whitelisted_recipient,
+        token_mint,
+        amount,
+        current_time,
+        ctx.accounts.treasury.epoch_duration,
+    )?;
+    
+    match token_mint {
+        Some(token_mint_key) => claim_token_share(&ctx, token_mint_key, amount)?,
+        None => claim_sol_share(&ctx, amount)?,
+    }
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    // Funds were reserved when the distribution was created, so the claim leaves the balance unchanged
+    let balance = match token_mint {
+        Some(_) => ctx.accounts.token_balance.as_ref().map_or(0, |token_balance| token_balance.balance),
+        None => ctx.accounts.treasury.total_funds,
+    };
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ClaimDistribution as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = claimant_key;
+    audit_log.target = Some(claimant_key);
+    audit_log.amount = amount;
+    audit_log.balance_before = balance;
+    audit_log.balance_after = balance;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = token_mint;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    emit!(DistributionClaimedEvent {
+        treasury: treasury_key,
+        distribution: distribution_key,
+        claimant: claimant_key,
+        token_mint,
+        index,
+        amount,
+        claimed_amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::ClaimDistribution as u8,
+        treasury: treasury_key,
+        initiator: claimant_key,
+        target: Some(claimant_key),
+        amount,
+        timestamp: current_time,
+        token_mint,
+    });
+    
+    Ok(())
+}
+
+fn claim_sol_share(ctx: &Context<ClaimDistribution>, amount: u64) -> Result<()> {
+    // Transfer lamports (native SOL) from treasury to claimant
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let recipient_info = ctx.accounts.authority.to_account_info();
+    let treasury_lamports = treasury_info.lamports();
+    let recipient_lamports = recipient_info.lamports();
+    
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports.checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports.checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    Ok(())
+}
+
+fn claim_token_share<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ClaimDistribution<'info>>,
+    token_mint_key: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let treasury_key = ctx.accounts.treasury.key();
+    let treasury_bump = ctx.accounts.treasury.bump;
+    let treasury_creator = ctx.accounts.treasury.creator;
+    let treasury_id_bytes = ctx.accounts.treasury.treasury_id.to_le_bytes();
+    
+    let token_mint = ctx.accounts.token_mint.as_ref().ok_or(ErrorCode::InvalidTokenMint)?;
+    require!(token_mint.key() == token_mint_key, ErrorCode::InvalidTokenMint);
+    let token_program = ctx.accounts.token_program.as_ref().ok_or(ErrorCode::TokenProgramRequired)?;
+    let associated_token_program = ctx.accounts.associated_token_program.as_ref()
+        .ok_or(ErrorCode::InvalidTokenAccount)?;
+    let treasury_token_account = ctx.accounts.treasury_token_account.as_ref()
+        .ok_or(ErrorCode::TreasuryTokenAccountNotFound)?;
+    let recipient_token_account = ctx.accounts.recipient_token_account.as_ref()
+        .ok_or(ErrorCode::InvalidTokenAccount)?;
+    
+    // The token balance only feeds the audit log, but must still be the mint's own
+    if let Some(token_balance) = &ctx.accounts.token_balance {
+        require!(
+            token_balance.treasury == treasury_key && token_balance.token_mint == token_mint_key,
+            ErrorCode::InvalidTokenMint
+        );
+    }
+    
+    // Check treasury token account
+    require!(
+        treasury_token_account.owner == treasury_key,
+        ErrorCode::InvalidTokenAccountOwner
+    );
+    
+    require!(
+        treasury_token_account.mint == token_mint_key,
+        ErrorCode::InvalidTokenMint
+    );
+    
+    // Create the claimant's associated token account if it does not exist yet
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.authority.to_account_info(),
+            associated_token: recipient_token_account.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            mint: token_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))?;
+    
+    // Transfer tokens from treasury to claimant
+    let treasury_seeds = &[
+        TREASURY_SEED,
+        treasury_creator.as_ref(),
+        &treasury_id_bytes,
+        &[treasury_bump],
+    ];
+    let treasury_signer = &[&treasury_seeds[..]];
+    
+    transfer_tokens_checked(
+        token_program,
+        treasury_token_account.to_account_info(),
+        token_mint,
+        recipient_token_account.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        treasury_signer,
+    )?;
+    
+    Ok(())
+}
\ No newline at end of file
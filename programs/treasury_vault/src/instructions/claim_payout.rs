@@ -0,0 +1,387 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::{audited_balance, check_token_gate, transfer_tokens_checked},
+};
+
+#[derive(Accounts)]
+pub struct ClaimPayout<'info> {
+    /// Whitelisted recipient claiming its own due payout
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = recipient.bump,
+        constraint = recipient.is_active @ ErrorCode::RecipientNotActive
+    )]
+    pub recipient: Account<'info, WhitelistedRecipient>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PAYOUT_SEED,
+            authority.key().as_ref(),
+            treasury.key().as_ref(),
+            &payout_schedule.index.to_le_bytes()
+        ],
+        bump = payout_schedule.bump,
+        constraint = payout_schedule.is_active @ ErrorCode::PayoutNotActive
+    )]
+    pub payout_schedule: Account<'info, PayoutSchedule>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Token account of the recipient holding the gate token, only required if token gate is enabled
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata account of the gate NFT, only required in collection gate mode.
+    /// Its address, owner and contents are validated by check_token_gate
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+    
+    /// Token balance of the payout mint, required for SPL token payouts
+    #[account(mut)]
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+    
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: The recipient's associated token account for the payout mint, created if needed.
+    /// The associated token program validates its address.
+    #[account(mut)]
+    pub recipient_token_account: Option<UncheckedAccount<'info>>,
+    
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Let a whitelisted recipient claim a due payout without a treasurer
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The recipient wallet, receives SOL payouts
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` recipient: The whitelisted recipient account of the authority
+/// 3. `[writable]` payout_schedule: The due payout schedule of the recipient
+/// 4. `[writable]` audit_log: The audit log account to create
+/// 5. `[optional]` gate_token_account: The recipient's gate token account if token gate is enabled
+/// 6. `[optional]` gate_metadata: Metadata of the gate NFT if the token gate is in collection mode
+/// 7. `[writable, optional]` token_balance: The token balance for SPL token payouts
+/// 8. `[writable, optional]` treasury_token_account: The treasury's token account
+/// 9. `[writable, optional]` recipient_token_account: The recipient's associated token account
+/// 10. `[optional]` token_mint: The token mint for SPL token payouts
+/// 11. `[optional]` token_program: SPL Token or Token-2022 program for SPL token payouts
+/// 12. `[optional]` associated_token_program: Associated token program to create the recipient's token account
+/// 13. `[]` system_program: System program for account creation
+/// 14. `[]` remaining_accounts: Extra accounts required by a Token-2022 transfer hook
+///
+/// Data:
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
+pub fn handler<'info>(
+    mut ctx: Context<'_, '_, 'info, 'info, ClaimPayout<'info>>,
+    timestamp: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Validate timestamp
+    require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
+    
+    // Payouts bypass proposals, so treasuries with an approval threshold can't run them
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let recipient_key = ctx.accounts.authority.key();
+    let payout_schedule = &ctx.accounts.payout_schedule;
+    
+    // Check if payout is due
+    require!(payout_schedule.is_due(current_time), ErrorCode::PayoutNotDue);
+    
+    // Settle every period due under the schedule's catch-up policy
+    let periods_settled = payout_schedule.periods_to_settle(current_time);
+    let payout_amount = payout_schedule
+        .amount
+        .checked_mul(periods_settled as u64)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let token_mint = payout_schedule.token_mint;
+    
+    // Check if token gate is enabled and validate the recipient's gate tokens
+    check_token_gate(
+        &ctx.accounts.treasury,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_deref(),
+        recipient_key,
+    )?;
+    
+    let balance_before = audited_balance(&ctx.accounts.treasury, token_mint.and(ctx.accounts.token_balance.as_ref()));
+    match token_mint {
+        Some(token_mint_key) => claim_token_payout(&mut ctx, token_mint_key, payout_amount, current_time)?,
+        None => claim_sol_payout(&mut ctx, payout_amount, current_time)?,
+    }
+    
+    // Update payout schedule, deactivating it after its final payment
+    let payout_schedule = &mut ctx.accounts.payout_schedule;
+    if payout_schedule.record_execution(current_time, periods_settled)? {
+        ctx.accounts.recipient.track_payout_closed()?;
+        
+        emit!(PayoutCompletedEvent {
+            treasury: treasury_key,
+            recipient: recipient_key,
+            payout_schedule: payout_schedule.key(),
+            execution_count: payout_schedule.execution_count,
+            timestamp: current_time,
+        });
+        
+        emit!(TreasuryEvent {
+            action: AuditAction::PayoutCompleted as u8,
+            treasury: treasury_key,
+            initiator: recipient_key,
+            target: Some(recipient_key),
+            amount: 0,
+            timestamp: current_time,
+            token_mint,
+        });
+    }
+    
+    // Create audit log
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = audited_balance(&ctx.accounts.treasury, token_mint.and(ctx.accounts.token_balance.as_ref()));
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ClaimPayout as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = recipient_key;
+    audit_log.target = Some(recipient_key);
+    audit_log.amount = payout_amount;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = token_mint;
+    audit_log.payout_schedule = Some(ctx.accounts.payout_schedule.key());
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    // Emit events
+    match token_mint {
+        Some(token_mint_key) => emit!(TokenPayoutEvent {
+            authority: recipient_key,
+            treasury: treasury_key,
+            recipient: recipient_key,
+            token_mint: token_mint_key,
+            amount: payout_amount,
+            periods_settled,
+            timestamp: current_time,
+        }),
+        None => emit!(PayoutEvent {
+            authority: recipient_key,
+            treasury: treasury_key,
+            recipient: recipient_key,
+            amount: payout_amount,
+            periods_settled,
+            timestamp: current_time,
+        }),
+    }
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::ClaimPayout as u8,
+        treasury: treasury_key,
+        initiator: recipient_key,
+        target: Some(recipient_key),
+        amount: payout_amount,
+        timestamp: current_time,
+        token_mint,
+    });
+    
+    Ok(())
+}
+
+fn claim_sol_payout(ctx: &mut Context<ClaimPayout>, amount: u64, current_time: i64) -> Result<()> {
+    let treasury_key = ctx.accounts.treasury.key();
+    let treasury = &mut ctx.accounts.treasury;
+    
+    // Check if treasury has enough funds
+    require!(treasury.total_funds >= amount, ErrorCode::InsufficientFunds);
+    
+    // Check if we need to reset the epoch
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL payout
+        });
+        
+        // Also emit the treasury event for better tracking
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitReset as u8,
+            treasury: treasury_key,
+            initiator: ctx.accounts.authority.key(),
+            target: None,
+            amount: previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL payout
+        });
+    }
+    
+    // Charge the payout against the spending limit
+    treasury.record_spending(amount, current_time)?;
+    
+    // Charge the recipient's own limit on top of the treasury's
+    let epoch_duration = treasury.epoch_duration;
+    ctx.accounts.recipient.charge_spending(None, amount, current_time, epoch_duration)?;
+    
+    // Update treasury state
+    treasury.total_funds = treasury.total_funds.checked_sub(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    // Transfer lamports (native SOL) from treasury to recipient
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let recipient_info = ctx.accounts.authority.to_account_info();
+    let treasury_lamports = treasury_info.lamports();
+    let recipient_lamports = recipient_info.lamports();
+    
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports.checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports.checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    Ok(())
+}
+
+fn claim_token_payout<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, ClaimPayout<'info>>,
+    token_mint_key: Pubkey,
+    amount: u64,
+    current_time: i64,
+) -> Result<()> {
+    let treasury_key = ctx.accounts.treasury.key();
+    let treasury_bump = ctx.accounts.treasury.bump;
+    let treasury_creator = ctx.accounts.treasury.creator;
+    let treasury_id_bytes = ctx.accounts.treasury.treasury_id.to_le_bytes();
+    
+    let token_mint = ctx.accounts.token_mint.as_ref().ok_or(ErrorCode::InvalidTokenMint)?;
+    require!(token_mint.key() == token_mint_key, ErrorCode::InvalidTokenMint);
+    let token_program = ctx.accounts.token_program.as_ref().ok_or(ErrorCode::TokenProgramRequired)?;
+    let associated_token_program = ctx.accounts.associated_token_program.as_ref()
+        .ok_or(ErrorCode::InvalidTokenAccount)?;
+    let treasury_token_account = ctx.accounts.treasury_token_account.as_ref()
+        .ok_or(ErrorCode::TreasuryTokenAccountNotFound)?;
+    let recipient_token_account = ctx.accounts.recipient_token_account.as_ref()
+        .ok_or(ErrorCode::InvalidTokenAccount)?;
+    let token_balance = ctx.accounts.token_balance.as_mut().ok_or(ErrorCode::TokenBalanceNotFound)?;
+    
+    // Validate token balance
+    require!(token_balance.treasury == treasury_key, ErrorCode::TokenBalanceNotFound);
+    require!(token_balance.token_mint == token_mint_key, ErrorCode::InvalidTokenMint);
+    
+    // Check treasury token account
+    require!(
+        treasury_token_account.owner == treasury_key,
+        ErrorCode::InvalidTokenAccountOwner
+    );
+    
+    require!(
+        treasury_token_account.mint == token_mint_key,
+        ErrorCode::InvalidTokenMint
+    );
+    
+    // Start a new epoch for this mint if the current one has elapsed
+    let epoch_duration = ctx.accounts.treasury.epoch_duration;
+    let rolling = ctx.accounts.treasury.uses_rolling_window();
+    if let Some(previous_epoch_spending) = token_balance.roll_epoch(current_time, epoch_duration) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: Some(token_mint_key),
+        });
+        
+        // Also emit the treasury event for better tracking
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitReset as u8,
+            treasury: treasury_key,
+            initiator: ctx.accounts.authority.key(),
+            target: None,
+            amount: previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: Some(token_mint_key),
+        });
+    }
+    
+    // Charge the payout against the mint's balance and spending limit
+    token_balance.record_outflow(amount, current_time, epoch_duration, rolling)?;
+    
+    // Charge the recipient's own limit on top of the mint's
+    ctx.accounts.recipient.charge_spending(Some(token_mint_key), amount, current_time, epoch_duration)?;
+    
+    // Create the recipient's associated token account if it does not exist yet
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.authority.to_account_info(),
+            associated_token: recipient_token_account.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            mint: token_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))?;
+    
+    // Transfer tokens from treasury to recipient
+    let treasury_seeds = &[
+        TREASURY_SEED,
+        treasury_creator.as_ref(),
+        &treasury_id_bytes,
+        &[treasury_bump],
+    ];
+    let treasury_signer = &[&treasury_seeds[..]];
+    
+    transfer_tokens_checked(
+        token_program,
+        treasury_token_account.to_account_info(),
+        token_mint,
+        recipient_token_account.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        treasury_signer,
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::{check_token_gate, transfer_tokens_checked},
+};
+
+#[derive(Accounts)]
+pub struct ClaimTokenVesting<'info> {
+    /// Recipient of the stream claiming its vested tokens
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = recipient.bump,
+        constraint = recipient.is_active @ ErrorCode::RecipientNotActive
+    )]
+    pub recipient: Account<'info, WhitelistedRecipient>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            authority.key().as_ref(),
+            treasury.key().as_ref(),
+            &vesting_stream.index.to_le_bytes()
+        ],
+        bump = vesting_stream.bump,
+        constraint = vesting_stream.token_mint == Some(token_mint.key()) @ ErrorCode::InvalidTokenMint
+    )]
+    pub vesting_stream: Account<'info, VestingStream>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_BALANCE_SEED, treasury.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+    
+    #[account(mut)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Token account of the recipient holding the gate token, only required if token gate is enabled
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata account of the gate NFT, only required in collection gate mode.
+    /// Its address, owner and contents are validated by check_token_gate
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim everything vested so far from an SPL token vesting stream
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The stream recipient
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` recipient: The whitelisted recipient account of the authority
+/// 3. `[writable]` vesting_stream: The vesting stream to claim from
+/// 4. `[writable]` token_balance: The token balance of the stream's mint
+/// 5. `[writable]` treasury_token_account: The treasury's token account
+/// 6. `[writable]` recipient_token_account: The authority's token account
+/// 7. `[]` token_mint: The stream's token mint
+/// 8. `[optional]` gate_token_account: The recipient's gate token account if token gate is enabled
+/// 9. `[optional]` gate_metadata: Metadata of the gate NFT if the token gate is in collection mode
+/// 10. `[writable]` audit_log: The audit log account to create
+/// 11. `[]` token_program: SPL Token or Token-2022 program
+/// 12. `[]` system_program: System program for account creation
+/// 13. `[]` remaining_accounts: Extra accounts required by a Token-2022 transfer hook
+///
+/// Data:
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimTokenVesting<'info>>,
+    timestamp: i64,
+) -> Result<()> {
+    // Validate timestamp is current or in the past
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
+    
+    // Streams bypass proposals, so treasuries with an approval threshold can't pay them out
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    // Get treasury and token data
+    let treasury_key = ctx.accounts.treasury.key();
+    let treasury_bump = ctx.accounts.treasury.bump;
+    let treasury_creator = ctx.accounts.treasury.creator;
+    let treasury_id_bytes = ctx.accounts.treasury.treasury_id.to_le_bytes();
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let recipient_key = ctx.accounts.authority.key();
+    
+    // Check if token gate is enabled and validate the recipient's gate tokens
+    check_token_gate(
+        &ctx.accounts.treasury,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_deref(),
+        recipient_key,
+    )?;
+    
+    let vesting_stream = &mut ctx.accounts.vesting_stream;
+    let amount = vesting_stream.claimable_amount(current_time);
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    
+    // Validate token accounts
+    require!(
+        ctx.accounts.treasury_token_account.owner == treasury_key,
+        ErrorCode::InvalidTokenAccountOwner
+    );
+    
+    require!(
+        ctx.accounts.treasury_token_account.mint == token_mint_key,
+        ErrorCode::InvalidTokenMint
+    );
+    
+    require!(
+        ctx.accounts.recipient_token_account.owner == recipient_key,
+        ErrorCode::InvalidTokenAccountOwner
+    );
+    
+    require!(
+        ctx.accounts.recipient_token_account.mint == token_mint_key,
+        ErrorCode::InvalidTokenMint
+    );
+    
+    // Start a new epoch for this mint if the current one has elapsed
+    let token_balance = &mut ctx.accounts.token_balance;
+    let epoch_duration = ctx.accounts.treasury.epoch_duration;
+    let rolling = ctx.accounts.treasury.uses_rolling_window();
+    if let Some(previous_epoch_spending) = token_balance.roll_epoch(current_time, epoch_duration) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: Some(token_mint_key),
+        });
+        
+        // Also emit the treasury event for better tracking
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitReset as u8,
+            treasury: treasury_key,
+            initiator: recipient_key,
+            target: None,
+            amount: previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: Some(token_mint_key),
+        });
+    }
+    
+    // Charge the claim against the mint's spending limit, the balance
+    // was already reserved when the stream was created
+    token_balance.record_spending(amount, current_time, epoch_duration, rolling)?;
+    vesting_stream.record_claim(amount)?;
+    
+    // Charge the recipient's own limit on top of the mint's
+    ctx.accounts.recipient.charge_spending(Some(token_mint_key), amount, current_time, epoch_duration)?;
+    
+    // Transfer tokens from treasury to recipient
+    let treasury_seeds = &[
+        TREASURY_SEED,
+        treasury_creator.as_ref(),
+        &treasury_id_bytes,
+        &[treasury_bump],
+    ];
+    let treasury_signer = &[&treasury_seeds[..]];
+    
+    transfer_tokens_checked(
+        &ctx.accounts.token_program,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        treasury_signer,
+    )?;
+    
+    // Close the stream once everything has vested and been claimed
+    let vesting_stream = &mut ctx.accounts.vesting_stream;
+    if vesting_stream.is_active && vesting_stream.is_settled(current_time) {
+        vesting_stream.is_active = false;
+        ctx.accounts.recipient.track_vesting_closed()?;
+    }
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    // Funds were reserved when the stream was created, so the claim leaves the balance unchanged
+    let balance = ctx.accounts.token_balance.balance;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ClaimVesting as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = recipient_key;
+    audit_log.target = Some(recipient_key);
+    audit_log.amount = amount;
+    audit_log.balance_before = balance;
+    audit_log.balance_after = balance;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = Some(token_mint_key);
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    emit!(VestingClaimedEvent {
+        treasury: treasury_key,
+        recipient: recipient_key,
+        vesting_stream: vesting_stream.key(),
+        token_mint: Some(token_mint_key),
+        amount,
+        claimed_amount: vesting_stream.claimed_amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::ClaimVesting as u8,
+        treasury: treasury_key,
+        initiator: recipient_key,
+        target: Some(recipient_key),
+        amount,
+        timestamp: current_time,
+        token_mint: Some(token_mint_key),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::check_token_gate,
+};
+
+#[derive(Accounts)]
+pub struct ClaimVesting<'info> {
+    /// Recipient of the stream claiming its vested SOL
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = recipient.bump,
+        constraint = recipient.is_active @ ErrorCode::RecipientNotActive
+    )]
+    pub recipient: Account<'info, WhitelistedRecipient>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            authority.key().as_ref(),
+            treasury.key().as_ref(),
+            &vesting_stream.index.to_le_bytes()
+        ],
+        bump = vesting_stream.bump,
+        constraint = vesting_stream.token_mint.is_none() @ ErrorCode::InvalidTokenMint // Ensure this is a SOL stream
+    )]
+    pub vesting_stream: Account<'info, VestingStream>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Token account of the recipient holding the gate token, only required if token gate is enabled
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata account of the gate NFT, only required in collection gate mode.
+    /// Its address, owner and contents are validated by check_token_gate
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim everything vested so far from a SOL vesting stream
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The stream recipient, receives the claimed SOL
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` recipient: The whitelisted recipient account of the authority
+/// 3. `[writable]` vesting_stream: The vesting stream to claim from
+/// 4. `[writable]` audit_log: The audit log account to create
+/// 5. `[optional]` gate_token_account: The recipient's gate token account if token gate is enabled
+/// 6. `[optional]` gate_metadata: Metadata of the gate NFT if the token gate is in collection mode
+/// 7. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
+pub fn handler(
+    ctx: Context<ClaimVesting>,
+    timestamp: i64,
+) -> Result<()> {
+    // Validate timestamp is current or in the past
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
+    
+    // Streams bypass proposals, so treasuries with an approval threshold can't pay them out
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let recipient_key = ctx.accounts.authority.key();
+    
+    // Check if token gate is enabled and validate the recipient's gate tokens
+    check_token_gate(
+        &ctx.accounts.treasury,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_deref(),
+        recipient_key,
+    )?;
+    
+    let vesting_stream = &mut ctx.accounts.vesting_stream;
+    let amount = vesting_stream.claimable_amount(current_time);
+    require!(amount > 0, ErrorCode::NothingToClaim);
+    
+    // Check if we need to reset the epoch
+    let treasury = &mut ctx.accounts.treasury;
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL stream
+        });
+        
+        // Also emit the treasury event for better tracking
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitReset as u8,
+            treasury: treasury_key,
+            initiator: recipient_key,
+            target: None,
+            amount: previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL stream
+        });
+    }
+    
+    // Charge the claim against the spending limit.
+    // The claimed amount was already taken out of total_funds when the stream was created.
+    treasury.record_spending(amount, current_time)?;
+    vesting_stream.record_claim(amount)?;
+    
+    // Charge the recipient's own limit on top of the treasury's
+    let epoch_duration = treasury.epoch_duration;
+    ctx.accounts.recipient.charge_spending(None, amount, current_time, epoch_duration)?;
+    
+    // Transfer lamports (native SOL) from treasury to recipient
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let recipient_info = ctx.accounts.authority.to_account_info();
+    let treasury_lamports = treasury_info.lamports();
+    let recipient_lamports = recipient_info.lamports();
+    
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports.checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports.checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    // Close the stream once everything has vested and been claimed
+    if vesting_stream.is_active && vesting_stream.is_settled(current_time) {
+        vesting_stream.is_active = false;
+        ctx.accounts.recipient.track_vesting_closed()?;
+    }
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    // Funds were reserved when the stream was created, so the claim leaves the balance unchanged
+    let balance = ctx.accounts.treasury.total_funds;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ClaimVesting as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = recipient_key;
+    audit_log.target = Some(recipient_key);
+    audit_log.amount = amount;
+    audit_log.balance_before = balance;
+    audit_log.balance_after = balance;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = None; // SOL stream
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    emit!(VestingClaimedEvent {
+        treasury: treasury_key,
+        recipient: recipient_key,
+        vesting_stream: vesting_stream.key(),
+        token_mint: None, // SOL stream
+        amount,
+        claimed_amount: vesting_stream.claimed_amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::ClaimVesting as u8,
+        treasury: treasury_key,
+        initiator: recipient_key,
+        target: Some(recipient_key),
+        amount,
+        timestamp: current_time,
+        token_mint: None, // SOL stream
+    });
+    
+    Ok(())
+}
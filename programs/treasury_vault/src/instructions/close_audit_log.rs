@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct CloseAuditLog<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &audit_log.sequence.to_le_bytes()
+        ],
+        bump = audit_log.bump,
+        close = rent_receiver
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Initiator of the logged action, who paid for the audit log and receives its rent
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == audit_log.initiator @ ErrorCode::RentReceiverMismatch
+    )]
+    pub rent_receiver: SystemAccount<'info>,
+}
+
+/// Allow admin users to close an audit log older than the treasury's retention
+/// period. A commitment of the log is folded into the treasury's audit digest first.
+///
+/// Accounts:
+/// 0. `[signer]` authority: The admin closing the audit log
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` audit_log: The audit log to close
+/// 4. `[writable]` rent_receiver: The account that paid for the audit log
+pub fn handler(
+    ctx: Context<CloseAuditLog>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let audit_log = &ctx.accounts.audit_log;
+    let treasury = &mut ctx.accounts.treasury;
+    
+    // Only logs past the retention period can be closed
+    let retention = i64::try_from(treasury.audit_log_retention)
+        .map_err(|_| ErrorCode::ArithmeticOverflow)?;
+    let closable_at = audit_log.timestamp
+        .checked_add(retention)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(current_time >= closable_at, ErrorCode::AuditLogRetentionNotElapsed);
+    
+    // Keep the history verifiable once the account is gone
+    let commitment = audit_log.commitment(&audit_log.key());
+    treasury.fold_audit_commitment(&commitment)?;
+    
+    emit!(AuditLogClosedEvent {
+        treasury: treasury.key(),
+        audit_log: audit_log.key(),
+        commitment,
+        audit_digest: treasury.audit_digest,
+        closed_audit_log_count: treasury.closed_audit_log_count,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::AuditLogClosed as u8,
+        treasury: treasury.key(),
+        initiator: ctx.accounts.authority.key(),
+        target: Some(audit_log.initiator),
+        amount: audit_log.amount,
+        timestamp: current_time,
+        token_mint: audit_log.token_mint,
+    });
+    
+    Ok(())
+}
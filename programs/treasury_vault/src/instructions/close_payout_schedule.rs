@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ClosePayoutSchedule<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PAYOUT_SEED,
+            payout_schedule.recipient.as_ref(),
+            treasury.key().as_ref(),
+            &payout_schedule.index.to_le_bytes()
+        ],
+        bump = payout_schedule.bump,
+        constraint = !payout_schedule.is_active @ ErrorCode::PayoutStillActive,
+        close = rent_receiver
+    )]
+    pub payout_schedule: Account<'info, PayoutSchedule>,
+    
+    /// Original payer of the payout schedule, receives its rent
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == payout_schedule.created_by @ ErrorCode::RentReceiverMismatch
+    )]
+    pub rent_receiver: SystemAccount<'info>,
+}
+
+/// Allow admin users to close a completed or cancelled payout schedule
+///
+/// Accounts:
+/// 0. `[signer]` authority: The admin closing the payout schedule
+/// 1. `[]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` payout_schedule: The inactive payout schedule to close
+/// 4. `[writable]` rent_receiver: The account that paid for the payout schedule
+pub fn handler(
+    ctx: Context<ClosePayoutSchedule>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    
+    emit!(PayoutScheduleClosedEvent {
+        treasury: treasury_key,
+        payout_schedule: ctx.accounts.payout_schedule.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::PayoutScheduleClosed as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(ctx.accounts.payout_schedule.recipient),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: ctx.accounts.payout_schedule.token_mint,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], total_amount: u64, claim_count: u32, expires_at: i64, index: u64)]
+pub struct CreateDistribution<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Schedule) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Distribution::INIT_SPACE,
+        seeds = [DISTRIBUTION_SEED, treasury.key().as_ref(), &index.to_le_bytes()],
+        bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    
+    /// Token balance of the distribution's mint, required for SPL token distributions
+    #[account(mut)]
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+    
+    /// Optional token mint for SPL token distributions
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Reserve treasury funds for a merkle distribution that claimants draw from
+/// by proving their leaf. The full amount is charged against the spending limit
+/// and the creator's own limit up front; each claim is checked against the
+/// withdrawal whitelist and the claimant's limit.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: Treasurer creating the distribution
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` user: The treasury user account of the authority
+/// 3. `[writable]` distribution: The distribution account to create
+/// 4. `[writable, optional]` token_balance: The token balance for SPL token distributions
+/// 5. `[optional]` token_mint: The token mint for SPL token distributions
+/// 6. `[writable]` audit_log: The audit log account to create
+/// 7. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - merkle_root: [[u8; 32]] Root of the `(index, recipient, mint, amount)` claim leaves
+/// - total_amount: [u64] The amount reserved for all claims
+/// - claim_count: [u32] Number of leaves in the tree
+/// - expires_at: [i64] When claims close and the rest can be reclaimed
+/// - index: [u64] Index of the distribution for this treasury
+pub fn handler(
+    ctx: Context<CreateDistribution>,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    claim_count: u32,
+    expires_at: i64,
+    index: u64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Validate inputs
+    require!(
+        total_amount > 0
+            && claim_count > 0
+            && claim_count as usize <= MAX_DISTRIBUTION_CLAIMS
+            && expires_at > current_time,
+        ErrorCode::InvalidDistribution
+    );
+    
+    // Treasuries with an approval threshold must withdraw through proposals
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let authority_key = ctx.accounts.authority.key();
+    
+    // Charge the full amount against the spending limit and reserve it, so
+    // every claim stays funded until the distribution expires
+    let (token_mint_pubkey, balance_before, balance_after) = if let Some(token_mint) = &ctx.accounts.token_mint {
+        let token_balance = ctx.accounts.token_balance.as_mut()
+            .ok_or(ErrorCode::TokenBalanceNotFound)?;
+        require!(
+            token_balance.treasury == treasury_key && token_balance.token_mint == token_mint.key(),
+            ErrorCode::InvalidTokenMint
+        );
+        
+        // Start a new epoch for this mint if the current one has elapsed
+        let epoch_duration = ctx.accounts.treasury.epoch_duration;
+        let rolling = ctx.accounts.treasury.uses_rolling_window();
+        if let Some(previous_epoch_spending) = token_balance.roll_epoch(current_time, epoch_duration) {
+            emit!(SpendingLimitResetEvent {
+                treasury: treasury_key,
+                previous_epoch_spending,
+                timestamp: current_time,
+                token_mint: Some(token_mint.key()),
+            });
+            
+            emit!(TreasuryEvent {
+                action: AuditAction::SpendingLimitReset as u8,
+                treasury: treasury_key,
+                initiator: authority_key,
+                target: None,
+                amount: previous_epoch_spending,
+                timestamp: current_time,
+                token_mint: Some(token_mint.key()),
+            });
+        }
+        
+        let balance_before = token_balance.balance;
+        token_balance.record_outflow(total_amount, current_time, epoch_duration, rolling)?;
+        (Some(token_mint.key()), balance_before, token_balance.balance)
+    } else {
+        let treasury = &mut ctx.accounts.treasury;
+        require!(treasury.total_funds >= total_amount, ErrorCode::InsufficientFunds);
+        
+        // Check if we need to reset the epoch
+        if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
+            emit!(SpendingLimitResetEvent {
+                treasury: treasury_key,
+                previous_epoch_spending,
+                timestamp: current_time,
+                token_mint: None,
+            });
+            
+            emit!(TreasuryEvent {
+                action: AuditAction::SpendingLimitReset as u8,
+                treasury: treasury_key,
+                initiator: authority_key,
+                target: None,
+                amount: previous_epoch_spending,
+                timestamp: current_time,
+                token_mint: None,
+            });
+        }
+        
+        let balance_before = treasury.total_funds;
+        treasury.record_spending(total_amount, current_time)?;
+        treasury.total_funds = treasury.total_funds.checked_sub(total_amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        (None, balance_before, treasury.total_funds)
+    };
+    
+    // Charge the creator's own limit for the mint
+    let epoch_duration = ctx.accounts.treasury.epoch_duration;
+    ctx.accounts.user.charge_spending(token_mint_pubkey, total_amount, current_time, epoch_duration)?;
+    
+    // Initialize distribution
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.treasury = treasury_key;
+    distribution.token_mint = token_mint_pubkey;
+    distribution.merkle_root = merkle_root;
+    distribution.total_amount = total_amount;
+    distribution.claimed_amount = 0;
+    distribution.claim_count = claim_count;
+    distribution.expires_at = expires_at;
+    distribution.created_by = authority_key;
+    distribution.index = index;
+    distribution.claimed_bitmap = [0; MAX_DISTRIBUTION_CLAIMS / 8];
+    distribution.bump = ctx.bumps.distribution;
+    let distribution_key = distribution.key();
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::CreateDistribution as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = authority_key;
+    audit_log.target = Some(distribution_key);
+    audit_log.amount = total_amount;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = token_mint_pubkey;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    emit!(DistributionCreatedEvent {
+        treasury: treasury_key,
+        distribution: distribution_key,
+        token_mint: token_mint_pubkey,
+        merkle_root,
+        total_amount,
+        claim_count,
+        expires_at,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::CreateDistribution as u8,
+        treasury: treasury_key,
+        initiator: authority_key,
+        target: Some(distribution_key),
+        amount: total_amount,
+        timestamp: current_time,
+        token_mint: token_mint_pubkey,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Withdraw) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [
+            PROPOSAL_SEED,
+            treasury.key().as_ref(),
+            &treasury.next_proposal_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a proposal for an action that requires multisig approval.
+/// The proposer's approval is recorded automatically.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The user creating the proposal
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` proposal: The proposal account to create
+/// 4. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - action: [u8] The ProposalAction to perform
+/// - params: [ProposalParams] The payload of the proposal
+///   - amount: [u64] The amount to withdraw, or the new per-user or per-recipient limit with 0 removing it
+///   - target: [Option<Pubkey>] The recipient wallet for withdrawals, or the user or recipient to manage
+///   - token_mint: [Option<Pubkey>] The token mint for token withdrawals and per-mint limits
+///   - config: [TreasuryConfigUpdate] New settings for config changes; its spending_limit also sets per-mint limits
+///   - role: [Option<u8>] The role for added users and role changes
+///   - permissions: [Option<u16>] The permission bitmask for permission changes
+/// - expires_at: [i64] Time after which the proposal can no longer be approved or executed
+pub fn handler(
+    ctx: Context<CreateProposal>,
+    action: u8,
+    params: ProposalParams,
+    expires_at: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(expires_at > current_time, ErrorCode::InvalidProposalExpiry);
+
+    let ProposalParams { amount, target, token_mint, config, role, permissions } = params;
+
+    // Validate the payload for the requested action
+    match ProposalAction::from_u8(action) {
+        Some(ProposalAction::SolWithdraw) => {
+            require!(amount > 0, ErrorCode::InvalidWithdrawAmount);
+            require!(target.is_some(), ErrorCode::InvalidProposalAction);
+            require!(token_mint.is_none(), ErrorCode::InvalidProposalAction);
+        }
+        Some(ProposalAction::TokenWithdraw) => {
+            require!(amount > 0, ErrorCode::InvalidWithdrawAmount);
+            require!(target.is_some(), ErrorCode::InvalidProposalAction);
+            require!(token_mint.is_some(), ErrorCode::InvalidProposalAction);
+        }
+        Some(ProposalAction::ConfigChange) => {
+            require!(!config.is_empty(), ErrorCode::InvalidProposalAction);
+            ctx.accounts.treasury.validate_config(&config)?;
+        }
+        Some(ProposalAction::TokenSpendingLimit) => {
+            require!(token_mint.is_some(), ErrorCode::InvalidProposalAction);
+            let limit = config.spending_limit.ok_or(ErrorCode::InvalidProposalAction)?;
+            require!(limit > 0, ErrorCode::InvalidSpendingLimit);
+        }
+        Some(ProposalAction::AddUser) | Some(ProposalAction::UpdateUserRole) => {
+            require!(target.is_some(), ErrorCode::InvalidProposalAction);
+            require!(permissions.is_none(), ErrorCode::InvalidProposalAction);
+            let role = role.ok_or(ErrorCode::InvalidProposalAction)?;
+            require!(Role::from_u8(role).is_some(), ErrorCode::InvalidRole);
+        }
+        Some(ProposalAction::SetUserPermissions) => {
+            require!(target.is_some(), ErrorCode::InvalidProposalAction);
+            require!(role.is_none(), ErrorCode::InvalidProposalAction);
+            let permissions = permissions.ok_or(ErrorCode::InvalidProposalAction)?;
+            require!(permissions & !ALL_PERMISSIONS == 0, ErrorCode::InvalidPermissions);
+        }
+        Some(ProposalAction::DeactivateUser) | Some(ProposalAction::ReactivateUser) => {
+            require!(target.is_some(), ErrorCode::InvalidProposalAction);
+            require!(role.is_none() && permissions.is_none(), ErrorCode::InvalidProposalAction);
+        }
+        Some(ProposalAction::SetUserSpendingLimit) | Some(ProposalAction::SetRecipientSpendingLimit) => {
+            require!(target.is_some(), ErrorCode::InvalidProposalAction);
+            require!(role.is_none() && permissions.is_none(), ErrorCode::InvalidProposalAction);
+        }
+        None => return Err(ErrorCode::InvalidProposalAction.into()),
+    }
+
+    let treasury = &mut ctx.accounts.treasury;
+    let treasury_key = treasury.key();
+    let index = treasury.get_next_proposal_index();
+
+    // Initialize proposal
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.treasury = treasury_key;
+    proposal.proposer = ctx.accounts.authority.key();
+    proposal.index = index;
+    proposal.action = action;
+    proposal.target = target;
+    proposal.token_mint = token_mint;
+    proposal.amount = amount;
+    proposal.config = config;
+    proposal.role = role;
+    proposal.permissions = permissions;
+    proposal.approvals = vec![ctx.accounts.authority.key()];
+    proposal.created_at = current_time;
+    proposal.expires_at = expires_at;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    // Emit events
+    emit!(ProposalCreatedEvent {
+        proposer: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        proposal: proposal.key(),
+        action,
+        target,
+        token_mint,
+        amount,
+        expires_at,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::CreateProposal as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target,
+        amount,
+        timestamp: current_time,
+        token_mint,
+    });
+
+    Ok(())
+}
\ No newline at end of file
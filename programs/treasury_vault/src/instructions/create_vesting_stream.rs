@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+#[instruction(total_amount: u64, start_time: i64, cliff_time: Option<i64>, end_time: i64, index: u64)]
+pub struct CreateVestingStream<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Schedule) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, recipient.recipient.as_ref(), treasury.key().as_ref()],
+        bump = recipient.bump,
+        constraint = recipient.is_active @ ErrorCode::RecipientNotActive
+    )]
+    pub recipient: Account<'info, WhitelistedRecipient>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VestingStream::INIT_SPACE,
+        seeds = [
+            VESTING_SEED,
+            recipient.recipient.as_ref(),
+            treasury.key().as_ref(),
+            &index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub vesting_stream: Account<'info, VestingStream>,
+    
+    /// Token balance of the stream's mint, required for SPL token streams
+    #[account(mut)]
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+    
+    /// Optional token mint for SPL token streams
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Reserve treasury funds for a recipient and release them linearly over time
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: Treasurer creating the stream
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` recipient: The whitelisted recipient of the stream
+/// 4. `[writable]` vesting_stream: The vesting stream account to create
+/// 5. `[writable, optional]` token_balance: The token balance for SPL token streams
+/// 6. `[optional]` token_mint: The token mint for SPL token streams
+/// 7. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - total_amount: [u64] The amount that vests over the stream
+/// - start_time: [i64] When vesting starts
+/// - cliff_time: [Option<i64>] Nothing can be claimed before this time, defaults to the start time
+/// - end_time: [i64] When the full amount has vested
+/// - index: [u64] Index of the stream for this recipient
+pub fn handler(
+    ctx: Context<CreateVestingStream>,
+    total_amount: u64,
+    start_time: i64,
+    cliff_time: Option<i64>,
+    end_time: i64,
+    index: u64,
+) -> Result<()> {
+    // Streams bypass proposals, so treasuries with an approval threshold can't create them
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    // Validate inputs
+    require!(total_amount > 0, ErrorCode::InvalidWithdrawAmount);
+    require!(end_time > start_time, ErrorCode::InvalidVestingSchedule);
+    
+    let cliff_time = cliff_time.unwrap_or(start_time);
+    require!(
+        cliff_time >= start_time && cliff_time <= end_time,
+        ErrorCode::InvalidVestingSchedule
+    );
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    
+    // Reserve the full amount so the stream stays funded until it is cancelled
+    let token_mint_pubkey = if let Some(token_mint) = &ctx.accounts.token_mint {
+        let token_balance = ctx.accounts.token_balance.as_mut()
+            .ok_or(ErrorCode::InvalidTokenMint)?;
+        require!(
+            token_balance.treasury == treasury_key && token_balance.token_mint == token_mint.key(),
+            ErrorCode::InvalidTokenMint
+        );
+        
+        token_balance.reserve(total_amount)?;
+        Some(token_mint.key())
+    } else {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_funds = treasury.total_funds.checked_sub(total_amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+        None
+    };
+    
+    let recipient = &mut ctx.accounts.recipient;
+    let vesting_stream = &mut ctx.accounts.vesting_stream;
+    
+    // Initialize vesting stream
+    vesting_stream.treasury = treasury_key;
+    vesting_stream.recipient = recipient.recipient;
+    vesting_stream.token_mint = token_mint_pubkey;
+    vesting_stream.total_amount = total_amount;
+    vesting_stream.claimed_amount = 0;
+    vesting_stream.start_time = start_time;
+    vesting_stream.cliff_time = cliff_time;
+    vesting_stream.end_time = end_time;
+    vesting_stream.created_by = ctx.accounts.authority.key();
+    vesting_stream.index = index;
+    vesting_stream.is_active = true;
+    vesting_stream.bump = ctx.bumps.vesting_stream;
+    
+    // Track the active stream on the recipient
+    recipient.track_vesting_created()?;
+    
+    emit!(VestingStreamCreatedEvent {
+        treasury: treasury_key,
+        recipient: recipient.recipient,
+        vesting_stream: vesting_stream.key(),
+        token_mint: token_mint_pubkey,
+        total_amount,
+        start_time,
+        cliff_time,
+        end_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::CreateVestingStream as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(recipient.recipient),
+        amount: total_amount,
+        timestamp: current_time,
+        token_mint: token_mint_pubkey,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::execute_user_proposal,
+};
+
+#[derive(Accounts)]
+pub struct DeactivateTreasuryUser<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::ManageUsers) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_account.user.as_ref(), treasury.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.role != Role::Admin as u8 || user.is_admin() @ ErrorCode::AdminTargetRequiresAdmin
+    )]
+    pub user_account: Account<'info, TreasuryUser>,
+    
+    /// Approved proposal for this change, required while the treasury requires multisig
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+}
+
+/// Allow admin users to deactivate a treasury user, revoking all of its permissions. Only an admin
+/// can change another admin.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The admin initiating the change
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` user_account: The treasury user account to deactivate
+/// 4. `[writable, optional]` proposal: The approved proposal, required while the treasury requires multisig
+/// 5. `[]` remaining_accounts: The treasury user accounts of the proposal's approvers, in approval order
+pub fn handler(
+    ctx: Context<DeactivateTreasuryUser>,
+) -> Result<()> {
+    // Under multisig the change needs an approved proposal
+    execute_user_proposal(
+        &ctx.accounts.treasury,
+        ctx.accounts.proposal.as_mut(),
+        ctx.remaining_accounts,
+        ctx.accounts.authority.key(),
+        ProposalAction::DeactivateUser,
+        ctx.accounts.user_account.user,
+        None,
+        None,
+    )?;
+    
+    let treasury = &mut ctx.accounts.treasury;
+    let user_account = &mut ctx.accounts.user_account;
+    
+    require!(user_account.is_active, ErrorCode::UserAlreadyInactive);
+    
+    // Never allow the last active admin to be removed
+    if user_account.role == Role::Admin as u8 {
+        treasury.decrement_admin_count()?;
+    }
+    
+    let was_approver = user_account.can_approve();
+    user_account.is_active = false;
+    treasury.update_approver_count(was_approver, false)?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Emit events
+    emit!(TreasuryUserUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury.key(),
+        user: user_account.user,
+        role: user_account.role,
+        is_active: false,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::DeactivateUser as u8,
+        treasury: treasury.key(),
+        initiator: ctx.accounts.authority.key(),
+        target: Some(user_account.user),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+    
+    Ok(())
+}
\ No newline at end of file
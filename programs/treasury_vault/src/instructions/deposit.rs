@@ -6,11 +6,10 @@ use anchor_lang::{
 use crate::error::ErrorCode;
 
 #[derive(Accounts)]
-#[instruction(amount: u64, timestamp: i64)]
 pub struct Deposit<'info> {
     #[account(
         mut,
-        seeds = [b"treasury"],
+        seeds = [b"treasury", treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -22,8 +21,7 @@ pub struct Deposit<'info> {
         seeds = [
             b"audit",
             treasury.key().as_ref(),
-            &timestamp.to_le_bytes(),
-            depositor.key().as_ref()
+            &treasury.audit_sequence.to_le_bytes()
         ],
         bump
     )]
@@ -45,7 +43,7 @@ pub struct Deposit<'info> {
 ///
 /// Data:
 /// - amount: [u64] The amount of SOL to deposit in lamports
-/// - timestamp: [i64] The current timestamp
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
 pub fn handler(
     ctx: Context<Deposit>,
     amount: u64,
@@ -71,20 +69,31 @@ pub fn handler(
     );
     system_program::transfer(cpi_context, amount)?;
 
+    let balance_before = ctx.accounts.treasury.total_funds;
     // Update treasury total funds
     let treasury = &mut ctx.accounts.treasury;
     treasury.total_funds = treasury.total_funds.checked_add(amount)
         .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
 
     // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = ctx.accounts.treasury.total_funds;
     let audit_log = &mut ctx.accounts.audit_log;
     audit_log.action = AuditAction::Deposit as u8;
     audit_log.treasury = treasury_key;
     audit_log.initiator = ctx.accounts.depositor.key();
+    audit_log.target = None;
     audit_log.amount = amount;
-    audit_log.timestamp = timestamp;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
     audit_log.token_mint = None; // Native SOL deposit
+    audit_log.payout_schedule = None;
     audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
 
     // Emit event
     emit!(DepositEvent {
@@ -1,21 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::*,
     error::ErrorCode,
     events::*,
     state::*,
+    utils::transfer_tokens_checked,
 };
 
 #[derive(Accounts)]
-#[instruction(amount: u64, timestamp: i64)]
 pub struct DepositToken<'info> {
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump,
-        constraint = !treasury.is_paused @ ErrorCode::TreasuryPaused
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
     )]
     pub treasury: Account<'info, Treasury>,
     
@@ -28,26 +28,22 @@ pub struct DepositToken<'info> {
     )]
     pub token_balance: Account<'info, TokenBalance>,
     
-    /// CHECK: This is validated in the handler
     #[account(mut)]
-    pub treasury_token_account: AccountInfo<'info>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This is validated in the handler
     #[account(mut)]
-    pub depositor_token_account: AccountInfo<'info>,
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This is validated in the handler
-    pub token_mint: AccountInfo<'info>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
         payer = depositor,
         space = 8 + AuditLog::INIT_SPACE,
         seeds = [
-            AUDIT_SEED, 
-            treasury.key().as_ref(), 
-            &timestamp.to_le_bytes(), 
-            depositor.key().as_ref()
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
         ],
         bump
     )]
@@ -56,13 +52,13 @@ pub struct DepositToken<'info> {
     #[account(mut)]
     pub depositor: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
-    ctx: Context<DepositToken>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositToken<'info>>,
     amount: u64,
     timestamp: i64,
 ) -> Result<()> {
@@ -85,44 +81,43 @@ pub fn handler(
     // Validate token accounts
     // We need to check that the depositor token account belongs to the depositor and has the correct mint
     let depositor_token_account = &ctx.accounts.depositor_token_account;
-    let depositor_token_account_data = token::TokenAccount::try_deserialize(&mut &depositor_token_account.data.borrow()[..])?;
     
     require!(
-        depositor_token_account_data.owner == depositor.key(),
+        depositor_token_account.owner == depositor.key(),
         ErrorCode::InvalidTokenAccountOwner
     );
     
     require!(
-        depositor_token_account_data.mint == token_mint_key,
+        depositor_token_account.mint == token_mint_key,
         ErrorCode::InvalidTokenMint
     );
     
     require!(
-        depositor_token_account_data.amount >= amount,
+        depositor_token_account.amount >= amount,
         ErrorCode::InsufficientTokenBalance
     );
     
     // Validate treasury token account
     let treasury_token_account = &ctx.accounts.treasury_token_account;
-    let treasury_token_account_data = token::TokenAccount::try_deserialize(&mut &treasury_token_account.data.borrow()[..])?;
     
     require!(
-        treasury_token_account_data.owner == treasury_key,
+        treasury_token_account.owner == treasury_key,
         ErrorCode::InvalidTokenAccountOwner
     );
     
     require!(
-        treasury_token_account_data.mint == token_mint_key,
+        treasury_token_account.mint == token_mint_key,
         ErrorCode::InvalidTokenMint
     );
     
     // If token balance account is new, initialize it
     if token_balance.treasury == Pubkey::default() {
-        token_balance.treasury = treasury_key;
-        token_balance.token_mint = token_mint_key;
-        token_balance.balance = 0;
-        token_balance.epoch_spending = 0;
-        token_balance.bump = ctx.bumps.token_balance;
+        **token_balance = TokenBalance::new(
+            treasury_key,
+            token_mint_key,
+            current_time,
+            ctx.bumps.token_balance,
+        );
         
         // Emit token balance created event
         emit!(TokenBalanceCreatedEvent {
@@ -133,38 +128,55 @@ pub fn handler(
     }
     
     // Transfer tokens from depositor to treasury token account
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.depositor_token_account.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.depositor.to_account_info(),
-            },
-        ),
+    let treasury_amount_before = treasury_token_account.amount;
+    transfer_tokens_checked(
+        &ctx.accounts.token_program,
+        ctx.accounts.depositor_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        ctx.accounts.depositor.to_account_info(),
+        ctx.remaining_accounts,
         amount,
+        &[],
     )?;
     
+    // Credit what actually arrived, which is less than amount for transfer-fee mints
+    ctx.accounts.treasury_token_account.reload()?;
+    let received = ctx.accounts.treasury_token_account.amount
+        .checked_sub(treasury_amount_before)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    let balance_before = token_balance.balance;
     // Update token balance
-    token_balance.balance = token_balance.balance.checked_add(amount)
+    token_balance.balance = token_balance.balance.checked_add(received)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
     
     // Create audit log
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = ctx.accounts.token_balance.balance;
     let audit_log = &mut ctx.accounts.audit_log;
     audit_log.action = AuditAction::TokenDeposit as u8;
     audit_log.treasury = treasury_key;
     audit_log.initiator = depositor.key();
-    audit_log.amount = amount;
-    audit_log.timestamp = timestamp;
+    audit_log.target = None;
+    audit_log.amount = received;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
     audit_log.token_mint = Some(token_mint_key);
+    audit_log.payout_schedule = None;
     audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
     
     // Emit events
     emit!(TokenDepositEvent {
         depositor: depositor.key(),
         treasury: treasury_key,
         token_mint: token_mint_key,
-        amount,
+        amount: received,
         timestamp,
     });
     
@@ -173,7 +185,7 @@ pub fn handler(
         treasury: treasury_key,
         initiator: depositor.key(),
         target: Some(token_mint_key),
-        amount,
+        amount: received,
         timestamp,
         token_mint: Some(token_mint_key),
     });
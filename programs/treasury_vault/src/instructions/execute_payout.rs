@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::TokenAccount;
 
 use crate::{
     constants::*,
     error::ErrorCode,
     events::*,
     state::*,
+    utils::{check_token_gate, pay_crank_tip},
 };
 
 #[derive(Accounts)]
@@ -16,21 +17,25 @@ pub struct ExecutePayout<'info> {
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump,
-        constraint = !treasury.is_paused @ ErrorCode::TreasuryPaused
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
     )]
     pub treasury: Account<'info, Treasury>,
     
+    /// Treasurer executing the payout. Omit to run as a permissionless crank
+    /// once the payout is due, which pays the treasury's crank tip to the authority
     #[account(
+        mut,
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Treasurer) @ ErrorCode::UnauthorizedUser
+        constraint = user.has_permission(Permission::Execute) @ ErrorCode::UnauthorizedUser
     )]
-    pub user: Account<'info, TreasuryUser>,
+    pub user: Option<Account<'info, TreasuryUser>>,
     
     #[account(
+        mut,
         seeds = [RECIPIENT_SEED, recipient.recipient.as_ref(), treasury.key().as_ref()],
         bump = recipient.bump,
         constraint = recipient.is_active @ ErrorCode::RecipientNotActive
@@ -58,12 +63,40 @@ pub struct ExecutePayout<'info> {
     )]
     pub recipient_wallet: UncheckedAccount<'info>,
     
-    /// Token account owned by the recipient, only required if token gate is enabled
-    /// CHECK: We manually verify this account in the handler
-    pub recipient_token_account: UncheckedAccount<'info>,
+    /// Token account of the recipient holding the gate token, only required if token gate is enabled
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Token program, only required if token gate is enabled
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Metadata account of the gate NFT, only required in collection gate mode.
+    /// Its address, owner and contents are validated by check_token_gate
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Audit log of the crank tip, written right after the payout's entry.
+    /// Only passed when the payout is cranked and the treasury pays a tip
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &(treasury.audit_sequence + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub tip_audit_log: Option<Account<'info, AuditLog>>,
     
     pub system_program: Program<'info, System>,
 }
@@ -77,6 +110,12 @@ pub fn handler(
     // Validate timestamp
     require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
     
+    // Payouts bypass proposals, so treasuries with an approval threshold can't run them
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
     let payout_schedule = &mut ctx.accounts.payout_schedule;
     
     // Check if payout is due
@@ -89,32 +128,21 @@ pub fn handler(
     
     // Get treasury data before mutable borrow
     let treasury_key = ctx.accounts.treasury.key();
-    let treasury_bump = ctx.accounts.treasury.bump;
-    let payout_amount = payout_schedule.amount;
     
-    // Check if token gate is enabled and validate token ownership
-    if let Some(gate_token_mint) = ctx.accounts.treasury.gate_token_mint {
-        // Check if the recipient token account is valid
-        let recipient_token_account_info = ctx.accounts.recipient_token_account.to_account_info();
-        
-        // Skip validation if the account is empty (zero data)
-        if !recipient_token_account_info.data_is_empty() {
-            // Deserialize the token account
-            let recipient_token_account = TokenAccount::try_deserialize(
-                &mut &recipient_token_account_info.data.borrow()[..]
-            )?;
-            
-            // Verify the recipient owns the token account and it's for the correct mint
-            require!(
-                recipient_token_account.owner == ctx.accounts.recipient_wallet.key() &&
-                recipient_token_account.mint == gate_token_mint &&
-                recipient_token_account.amount > 0,
-                ErrorCode::TokenGateCheckFailed
-            );
-        } else {
-            return Err(ErrorCode::TokenGateCheckFailed.into());
-        }
-    }
+    // Settle every period due under the schedule's catch-up policy
+    let periods_settled = payout_schedule.periods_to_settle(current_time);
+    let payout_amount = payout_schedule
+        .amount
+        .checked_mul(periods_settled as u64)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    // Check if token gate is enabled and validate the recipient's gate tokens
+    check_token_gate(
+        &ctx.accounts.treasury,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_deref(),
+        ctx.accounts.recipient.recipient,
+    )?;
     
     // Check if treasury has enough funds
     require!(
@@ -122,13 +150,9 @@ pub fn handler(
         ErrorCode::InsufficientFunds
     );
     
-    // Check if this would exceed the spending limit for the current epoch
+    // Check if we need to reset the epoch
     let treasury = &mut ctx.accounts.treasury;
-    let previous_epoch_spending = treasury.epoch_spending;
-    let current_epoch_start = if current_time >= treasury.last_epoch_start + treasury.epoch_duration as i64 {
-        // We're in a new epoch, reset the epoch_spending
-        treasury.epoch_spending = 0;
-        
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
         // Emit spending limit reset event
         emit!(SpendingLimitResetEvent {
             treasury: treasury_key,
@@ -147,62 +171,108 @@ pub fn handler(
             timestamp: current_time,
             token_mint: None, // SOL payout
         });
-        
-        current_time
-    } else {
-        treasury.last_epoch_start
-    };
+    }
     
-    // Update treasury's epoch data
-    treasury.last_epoch_start = current_epoch_start;
+    // Charge the payout against the spending limit
+    treasury.record_spending(payout_amount, current_time)?;
     
-    // Check if this withdrawal would exceed the spending limit
-    let new_epoch_spending = treasury
-        .epoch_spending
-        .checked_add(payout_amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
-        
-    require!(
-        new_epoch_spending <= treasury.spending_limit,
-        ErrorCode::SpendingLimitExceeded
-    );
+    // Charge the per-recipient limit, and the executing user's unless cranked
+    let epoch_duration = treasury.epoch_duration;
+    if let Some(user) = ctx.accounts.user.as_mut() {
+        user.charge_spending(None, payout_amount, current_time, epoch_duration)?;
+    }
+    ctx.accounts.recipient.charge_spending(None, payout_amount, current_time, epoch_duration)?;
     
     // Update treasury state
-    treasury.epoch_spending = new_epoch_spending;
+    let treasury = &mut ctx.accounts.treasury;
+    let balance_before = treasury.total_funds;
     treasury.total_funds = treasury
         .total_funds
         .checked_sub(payout_amount)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let balance_after = treasury.total_funds;
     
-    // Transfer funds to recipient
-    let treasury_seeds = &[
-        TREASURY_SEED,
-        &[treasury_bump],
-    ];
-    let treasury_signer = &[&treasury_seeds[..]];
-    
-    // Transfer lamports from treasury to recipient
-    anchor_lang::system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.treasury.to_account_info(),
-                to: ctx.accounts.recipient_wallet.to_account_info(),
-            },
-            treasury_signer,
-        ),
-        payout_amount,
-    )?;
+    // Move lamports directly, the treasury is owned by this program
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let recipient_info = ctx.accounts.recipient_wallet.to_account_info();
+    let treasury_lamports = treasury_info.lamports();
+    let recipient_lamports = recipient_info.lamports();
     
-    // Update payout schedule
-    payout_schedule.last_executed = current_time;
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports
+        .checked_sub(payout_amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports
+        .checked_add(payout_amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
     
-    // If it's a one-time payout, deactivate it
-    if !payout_schedule.recurring {
-        payout_schedule.is_active = false;
+    // Update payout schedule, deactivating it after its final payment
+    if payout_schedule.record_execution(current_time, periods_settled)? {
+        ctx.accounts.recipient.track_payout_closed()?;
+        
+        emit!(PayoutCompletedEvent {
+            treasury: treasury_key,
+            recipient: payout_schedule.recipient,
+            payout_schedule: payout_schedule.key(),
+            execution_count: payout_schedule.execution_count,
+            timestamp: current_time,
+        });
+        
+        emit!(TreasuryEvent {
+            action: AuditAction::PayoutCompleted as u8,
+            treasury: treasury_key,
+            initiator: ctx.accounts.authority.key(),
+            target: Some(payout_schedule.recipient),
+            amount: 0,
+            timestamp: current_time,
+            token_mint: None, // SOL payout
+        });
+    }
+    
+    // Create audit log
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ExecutePayout as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(ctx.accounts.recipient.recipient);
+    audit_log.amount = payout_amount;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = None; // SOL payout
+    audit_log.payout_schedule = Some(ctx.accounts.payout_schedule.key());
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    // Tip the caller when the payout was cranked without a treasurer
+    if ctx.accounts.user.is_none() {
+        let payout_schedule_key = ctx.accounts.payout_schedule.key();
+        pay_crank_tip(
+            &mut ctx.accounts.treasury,
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.tip_audit_log.as_mut(),
+            ctx.bumps.tip_audit_log,
+            payout_schedule_key,
+            current_time,
+            timestamp,
+        )?;
+    } else {
+        require!(ctx.accounts.tip_audit_log.is_none(), ErrorCode::InvalidTipAuditLog);
     }
     
     // Emit events
+    emit!(PayoutEvent {
+        authority: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        recipient: ctx.accounts.recipient.recipient,
+        amount: payout_amount,
+        periods_settled,
+        timestamp: current_time,
+    });
+    
     emit!(WithdrawEvent {
         admin: ctx.accounts.authority.key(),
         recipient: ctx.accounts.recipient.recipient,
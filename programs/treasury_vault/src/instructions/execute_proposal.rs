@@ -0,0 +1,508 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::{
+        audited_balance,
+        charge_recipient_limit,
+        count_active_approvals,
+        require_whitelisted_recipient,
+        transfer_tokens_checked,
+    },
+};
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Execute) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    /// Recipient wallet, required for SOL and token withdrawals
+    #[account(mut)]
+    pub recipient: Option<SystemAccount<'info>>,
+
+    /// CHECK: Whitelist entry of the withdrawal recipient, which may not exist. Required for
+    /// withdrawals, its address is checked against the proposal's target
+    #[account(mut)]
+    pub whitelisted_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Token balance for the proposal mint, required for token withdrawals
+    #[account(mut)]
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute a proposal once it has collected enough approvals
+/// from current approvers. SOL withdrawals above the timelock threshold also
+/// wait out the timelock delay, counted from the proposal's creation.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The user executing the proposal
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` user: The treasury user account of the authority
+/// 3. `[writable]` proposal: The proposal to execute
+/// 4. `[writable]` audit_log: The audit log account to create
+/// 5. `[writable, optional]` recipient: The recipient wallet for withdrawals
+/// 6. `[writable, optional]` whitelisted_recipient: The recipient's whitelist entry PDA for withdrawals, which may not exist
+/// 7. `[writable, optional]` token_balance: The token balance for token withdrawals and per-mint limits
+/// 8. `[writable, optional]` treasury_token_account: The treasury's token account
+/// 9. `[writable, optional]` recipient_token_account: The recipient's token account
+/// 10. `[optional]` token_mint: The token mint for token withdrawals
+/// 11. `[optional]` token_program: SPL Token or Token-2022 program for token withdrawals
+/// 12. `[]` system_program: System program for account creation
+/// 13. `[]` remaining_accounts: The treasury user accounts of the proposal's approvers, in
+///     approval order, followed by extra accounts required by a Token-2022 transfer hook
+///
+/// Data:
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
+pub fn handler<'info>(
+    mut ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    timestamp: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
+
+    let proposal = &ctx.accounts.proposal;
+    require!(!proposal.is_expired(current_time), ErrorCode::ProposalExpired);
+    // Only approvers who are still active and hold Withdraw count
+    let approvals = count_active_approvals(proposal, ctx.accounts.treasury.key(), ctx.remaining_accounts)?;
+    require!(
+        approvals >= ctx.accounts.treasury.approval_threshold,
+        ErrorCode::InsufficientApprovals
+    );
+
+    let action = proposal.action;
+    let amount = proposal.amount;
+    let target = proposal.target;
+    let token_mint = proposal.token_mint;
+
+    let balance_before = audited_balance(&ctx.accounts.treasury, token_mint.and(ctx.accounts.token_balance.as_ref()));
+    match ProposalAction::from_u8(action) {
+        Some(ProposalAction::SolWithdraw) => execute_sol_withdraw(&mut ctx, current_time)?,
+        Some(ProposalAction::TokenWithdraw) => execute_token_withdraw(&mut ctx, current_time)?,
+        Some(ProposalAction::ConfigChange) => execute_config_change(&mut ctx, current_time)?,
+        Some(ProposalAction::TokenSpendingLimit) => execute_token_spending_limit(&mut ctx, current_time)?,
+        // User management and per-user or per-recipient limit proposals are executed
+        // by the instructions that make those changes
+        _ => return Err(ErrorCode::InvalidProposalAction.into()),
+    }
+
+    ctx.accounts.proposal.executed = true;
+
+    let treasury_key = ctx.accounts.treasury.key();
+
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = audited_balance(&ctx.accounts.treasury, token_mint.and(ctx.accounts.token_balance.as_ref()));
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ExecuteProposal as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = target;
+    audit_log.amount = amount;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = token_mint;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+
+    // Emit events
+    emit!(ProposalExecutedEvent {
+        executor: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        proposal: ctx.accounts.proposal.key(),
+        action,
+        amount,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::ExecuteProposal as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target,
+        amount,
+        timestamp: current_time,
+        token_mint,
+    });
+
+    Ok(())
+}
+
+fn execute_sol_withdraw(ctx: &mut Context<ExecuteProposal>, current_time: i64) -> Result<()> {
+    let amount = ctx.accounts.proposal.amount;
+    let treasury_key = ctx.accounts.treasury.key();
+
+    let recipient = ctx.accounts.recipient.as_ref().ok_or(ErrorCode::ProposalAccountMismatch)?;
+    require!(
+        Some(recipient.key()) == ctx.accounts.proposal.target,
+        ErrorCode::ProposalAccountMismatch
+    );
+    check_whitelisted_recipient(ctx, recipient.key())?;
+
+    // Large withdrawals still wait out the timelock, counted from the proposal's creation
+    if ctx.accounts.treasury.requires_timelock(amount) {
+        let executable_at = ctx.accounts.proposal.created_at
+            .checked_add(ctx.accounts.treasury.withdrawal_timelock_delay as i64)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(current_time >= executable_at, ErrorCode::WithdrawalTimelockActive);
+    }
+
+    // Check if treasury has enough funds
+    require!(
+        ctx.accounts.treasury.total_funds >= amount,
+        ErrorCode::InsufficientFunds
+    );
+
+    // Check if we need to reset the epoch
+    let treasury = &mut ctx.accounts.treasury;
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL withdrawal
+        });
+    }
+
+    // Charge the withdrawal against the spending limit
+    treasury.record_spending(amount, current_time)?;
+
+    // Charge the executing user's and the recipient's limits on top of the treasury's
+    let epoch_duration = treasury.epoch_duration;
+    charge_user_and_recipient(
+        &mut ctx.accounts.user,
+        ctx.accounts.whitelisted_recipient.as_deref(),
+        None,
+        amount,
+        current_time,
+        epoch_duration,
+    )?;
+
+    // Update treasury state
+    treasury.total_funds = treasury.total_funds.checked_sub(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Transfer lamports (native SOL) from treasury to recipient
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let recipient_info = recipient.to_account_info();
+
+    let treasury_lamports = treasury_info.lamports();
+    let recipient_lamports = recipient_info.lamports();
+
+    require!(
+        treasury_lamports >= amount,
+        ErrorCode::InsufficientFunds
+    );
+
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports.checked_sub(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports.checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(WithdrawEvent {
+        admin: ctx.accounts.authority.key(),
+        recipient: recipient.key(),
+        amount,
+        timestamp: current_time,
+        token_mint: None, // SOL withdrawal
+    });
+
+    Ok(())
+}
+
+fn execute_token_withdraw<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    current_time: i64,
+) -> Result<()> {
+    let amount = ctx.accounts.proposal.amount;
+    let treasury_key = ctx.accounts.treasury.key();
+    let treasury_bump = ctx.accounts.treasury.bump;
+    let treasury_creator = ctx.accounts.treasury.creator;
+    let treasury_id_bytes = ctx.accounts.treasury.treasury_id.to_le_bytes();
+    let recipient_key = ctx.accounts.proposal.target.ok_or(ErrorCode::InvalidProposalAction)?;
+    let hook_accounts = &ctx.remaining_accounts[ctx.accounts.proposal.approvals.len()..];
+    check_whitelisted_recipient(ctx, recipient_key)?;
+    let token_mint_key = ctx.accounts.proposal.token_mint.ok_or(ErrorCode::InvalidProposalAction)?;
+
+    let token_mint = ctx.accounts.token_mint.as_ref().ok_or(ErrorCode::ProposalAccountMismatch)?;
+    require!(token_mint.key() == token_mint_key, ErrorCode::InvalidTokenMint);
+    let token_program = ctx.accounts.token_program.as_ref().ok_or(ErrorCode::TokenProgramRequired)?;
+    let treasury_token_account = ctx.accounts.treasury_token_account.as_ref()
+        .ok_or(ErrorCode::TreasuryTokenAccountNotFound)?;
+    let recipient_token_account = ctx.accounts.recipient_token_account.as_ref()
+        .ok_or(ErrorCode::InvalidTokenAccount)?;
+    let token_balance = ctx.accounts.token_balance.as_mut().ok_or(ErrorCode::TokenBalanceNotFound)?;
+
+    // Validate token balance
+    require!(token_balance.treasury == treasury_key, ErrorCode::TokenBalanceNotFound);
+    require!(token_balance.token_mint == token_mint_key, ErrorCode::InvalidTokenMint);
+    require!(token_balance.balance >= amount, ErrorCode::InsufficientTokenBalance);
+
+    // Check treasury token account
+
+    require!(
+        treasury_token_account.owner == treasury_key,
+        ErrorCode::InvalidTokenAccountOwner
+    );
+
+    require!(
+        treasury_token_account.mint == token_mint_key,
+        ErrorCode::InvalidTokenMint
+    );
+
+    // Check recipient token account
+
+    require!(
+        recipient_token_account.owner == recipient_key,
+        ErrorCode::InvalidTokenAccountOwner
+    );
+
+    require!(
+        recipient_token_account.mint == token_mint_key,
+        ErrorCode::InvalidTokenMint
+    );
+
+    // Start a new epoch for this mint if the current one has elapsed
+    let epoch_duration = ctx.accounts.treasury.epoch_duration;
+    let rolling = ctx.accounts.treasury.uses_rolling_window();
+    if let Some(previous_epoch_spending) = token_balance.roll_epoch(current_time, epoch_duration) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: Some(token_mint_key),
+        });
+    }
+
+    // Charge the withdrawal against the mint's balance and spending limit
+    token_balance.record_outflow(amount, current_time, epoch_duration, rolling)?;
+
+    // Charge the executing user's and the recipient's limits on top of the mint's
+    charge_user_and_recipient(
+        &mut ctx.accounts.user,
+        ctx.accounts.whitelisted_recipient.as_deref(),
+        Some(token_mint_key),
+        amount,
+        current_time,
+        epoch_duration,
+    )?;
+
+    // Transfer tokens from treasury to recipient
+    let treasury_seeds = &[
+        TREASURY_SEED,
+        treasury_creator.as_ref(),
+        &treasury_id_bytes,
+        &[treasury_bump],
+    ];
+    let treasury_signer = &[&treasury_seeds[..]];
+
+    transfer_tokens_checked(
+        token_program,
+        treasury_token_account.to_account_info(),
+        token_mint,
+        recipient_token_account.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        hook_accounts,
+        amount,
+        treasury_signer,
+    )?;
+
+    emit!(WithdrawEvent {
+        admin: ctx.accounts.authority.key(),
+        recipient: recipient_key,
+        amount,
+        timestamp: current_time,
+        token_mint: Some(token_mint_key),
+    });
+
+    Ok(())
+}
+
+fn execute_config_change(ctx: &mut Context<ExecuteProposal>, current_time: i64) -> Result<()> {
+    let config = &ctx.accounts.proposal.config;
+    let treasury = &mut ctx.accounts.treasury;
+
+    // The approvers may have changed since the proposal was created
+    treasury.validate_config(config)?;
+
+    if let Some(duration) = config.epoch_duration {
+        let old_duration = treasury.epoch_duration;
+        treasury.epoch_duration = duration;
+
+        emit!(EpochDurationUpdatedEvent {
+            admin: ctx.accounts.authority.key(),
+            treasury: treasury.key(),
+            old_duration,
+            new_duration: duration,
+            timestamp: current_time,
+        });
+    }
+
+    if let Some(limit) = config.spending_limit {
+        treasury.spending_limit = limit;
+    }
+
+    if let Some(threshold) = config.approval_threshold {
+        treasury.approval_threshold = threshold;
+    }
+
+    if let Some(tip) = config.crank_tip {
+        treasury.crank_tip = tip;
+    }
+
+    if let Some(retention) = config.audit_log_retention {
+        treasury.audit_log_retention = retention;
+    }
+
+    if let Some(mode) = config.spending_limit_mode {
+        treasury.spending_limit_mode = mode;
+    }
+
+    if config.withdrawal_timelock_threshold.is_some() || config.withdrawal_timelock_delay.is_some() {
+        if let Some(threshold) = config.withdrawal_timelock_threshold {
+            treasury.withdrawal_timelock_threshold = threshold;
+        }
+        if let Some(delay) = config.withdrawal_timelock_delay {
+            treasury.withdrawal_timelock_delay = delay;
+        }
+
+        emit!(WithdrawalTimelockUpdatedEvent {
+            admin: ctx.accounts.authority.key(),
+            treasury: treasury.key(),
+            threshold: treasury.withdrawal_timelock_threshold,
+            delay: treasury.withdrawal_timelock_delay,
+            timestamp: current_time,
+        });
+    }
+
+    if let Some(required) = config.withdrawals_require_whitelist {
+        treasury.withdrawals_require_whitelist = required;
+
+        emit!(WithdrawalWhitelistUpdatedEvent {
+            admin: ctx.accounts.authority.key(),
+            treasury: treasury.key(),
+            required,
+            timestamp: current_time,
+        });
+    }
+
+    emit!(TreasuryConfigUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        epoch_duration: treasury.epoch_duration,
+        spending_limit: treasury.spending_limit,
+    });
+
+    Ok(())
+}
+
+fn execute_token_spending_limit(ctx: &mut Context<ExecuteProposal>, current_time: i64) -> Result<()> {
+    let treasury_key = ctx.accounts.treasury.key();
+    let token_mint_key = ctx.accounts.proposal.token_mint.ok_or(ErrorCode::InvalidProposalAction)?;
+    let spending_limit = ctx.accounts.proposal.config.spending_limit.ok_or(ErrorCode::InvalidProposalAction)?;
+
+    let token_balance = ctx.accounts.token_balance.as_mut().ok_or(ErrorCode::TokenBalanceNotFound)?;
+    require!(token_balance.treasury == treasury_key, ErrorCode::TokenBalanceNotFound);
+    require!(token_balance.token_mint == token_mint_key, ErrorCode::InvalidTokenMint);
+
+    let old_limit = token_balance.spending_limit;
+    token_balance.spending_limit = spending_limit;
+
+    emit!(TokenSpendingLimitUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        token_mint: token_mint_key,
+        old_limit,
+        new_limit: spending_limit,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Enforce the treasury's withdrawal whitelist policy on the recipient of a withdrawal
+fn check_whitelisted_recipient(ctx: &Context<ExecuteProposal>, recipient: Pubkey) -> Result<()> {
+    let whitelisted_recipient = ctx.accounts.whitelisted_recipient.as_ref()
+        .ok_or(ErrorCode::ProposalAccountMismatch)?;
+    require_keys_eq!(
+        whitelisted_recipient.key(),
+        WhitelistedRecipient::address(&recipient, &ctx.accounts.treasury.key()),
+        ErrorCode::ProposalAccountMismatch
+    );
+    require_whitelisted_recipient(&ctx.accounts.treasury, whitelisted_recipient)
+}
+
+/// Charge a withdrawal against the executing user's and the recipient's per-mint limits
+fn charge_user_and_recipient(
+    user: &mut TreasuryUser,
+    whitelisted_recipient: Option<&AccountInfo>,
+    token_mint: Option<Pubkey>,
+    amount: u64,
+    current_time: i64,
+    epoch_duration: u64,
+) -> Result<()> {
+    user.charge_spending(token_mint, amount, current_time, epoch_duration)?;
+    if let Some(whitelisted_recipient) = whitelisted_recipient {
+        charge_recipient_limit(whitelisted_recipient, token_mint, amount, current_time, epoch_duration)?;
+    }
+    Ok(())
+}
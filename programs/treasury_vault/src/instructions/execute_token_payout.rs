@@ -1,36 +1,40 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::*,
     error::ErrorCode,
     events::*,
     state::*,
+    utils::{check_token_gate, pay_crank_tip, transfer_tokens_checked},
 };
 
 #[derive(Accounts)]
-#[instruction(timestamp: i64)]
 pub struct ExecuteTokenPayout<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump,
-        constraint = !treasury.is_paused @ ErrorCode::TreasuryPaused
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
     )]
     pub treasury: Account<'info, Treasury>,
     
+    /// Treasurer executing the payout. Omit to run as a permissionless crank
+    /// once the payout is due, which pays the treasury's crank tip to the authority
     #[account(
+        mut,
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Treasurer) @ ErrorCode::UnauthorizedUser
+        constraint = user.has_permission(Permission::Execute) @ ErrorCode::UnauthorizedUser
     )]
-    pub user: Account<'info, TreasuryUser>,
+    pub user: Option<Account<'info, TreasuryUser>>,
     
     #[account(
+        mut,
         seeds = [RECIPIENT_SEED, recipient.recipient.as_ref(), treasury.key().as_ref()],
         bump = recipient.bump,
         constraint = recipient.is_active @ ErrorCode::RecipientNotActive
@@ -57,38 +61,56 @@ pub struct ExecuteTokenPayout<'info> {
     )]
     pub token_balance: Account<'info, TokenBalance>,
     
-    /// CHECK: This is validated in the handler
     #[account(mut)]
-    pub treasury_token_account: AccountInfo<'info>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This is validated in the handler
     #[account(mut)]
-    pub recipient_token_account: AccountInfo<'info>,
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    /// CHECK: This is validated in the handler
-    pub token_mint: AccountInfo<'info>,
+    /// Token account of the recipient holding the gate token, only required if token gate is enabled
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata account of the gate NFT, only required in collection gate mode.
+    /// Its address, owner and contents are validated by check_token_gate
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
     
     #[account(
         init,
         payer = authority,
         space = 8 + AuditLog::INIT_SPACE,
         seeds = [
-            AUDIT_SEED, 
-            treasury.key().as_ref(), 
-            &timestamp.to_le_bytes(), 
-            authority.key().as_ref()
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
         ],
         bump
     )]
     pub audit_log: Account<'info, AuditLog>,
     
-    pub token_program: Program<'info, Token>,
+    /// Audit log of the crank tip, written right after the payout's entry.
+    /// Only passed when the payout is cranked and the treasury pays a tip
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &(treasury.audit_sequence + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub tip_audit_log: Option<Account<'info, AuditLog>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
-    ctx: Context<ExecuteTokenPayout>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteTokenPayout<'info>>,
     timestamp: i64,
 ) -> Result<()> {
     let current_time = Clock::get()?.unix_timestamp;
@@ -96,6 +118,12 @@ pub fn handler(
     // Validate timestamp
     require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
     
+    // Payouts bypass proposals, so treasuries with an approval threshold can't run them
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
     let payout_schedule = &mut ctx.accounts.payout_schedule;
     
     // Validate payout schedule has token mint
@@ -115,7 +143,15 @@ pub fn handler(
     // Get treasury data before mutable borrow
     let treasury_key = ctx.accounts.treasury.key();
     let treasury_bump = ctx.accounts.treasury.bump;
-    let payout_amount = payout_schedule.amount;
+    let treasury_creator = ctx.accounts.treasury.creator;
+    let treasury_id_bytes = ctx.accounts.treasury.treasury_id.to_le_bytes();
+    
+    // Settle every period due under the schedule's catch-up policy
+    let periods_settled = payout_schedule.periods_to_settle(current_time);
+    let payout_amount = payout_schedule
+        .amount
+        .checked_mul(periods_settled as u64)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
     
     // Validate token balance
     let token_balance = &mut ctx.accounts.token_balance;
@@ -125,48 +161,42 @@ pub fn handler(
     // Validate token accounts
     // Check treasury token account
     let treasury_token_account = &ctx.accounts.treasury_token_account;
-    let treasury_token_account_data = token::TokenAccount::try_deserialize(&mut &treasury_token_account.data.borrow()[..])?;
     
     require!(
-        treasury_token_account_data.owner == treasury_key,
+        treasury_token_account.owner == treasury_key,
         ErrorCode::InvalidTokenAccountOwner
     );
     
     require!(
-        treasury_token_account_data.mint == token_mint_key,
+        treasury_token_account.mint == token_mint_key,
         ErrorCode::InvalidTokenMint
     );
     
     // Check recipient token account
     let recipient_token_account = &ctx.accounts.recipient_token_account;
-    let recipient_token_account_data = token::TokenAccount::try_deserialize(&mut &recipient_token_account.data.borrow()[..])?;
     
     require!(
-        recipient_token_account_data.owner == ctx.accounts.recipient.recipient,
+        recipient_token_account.owner == ctx.accounts.recipient.recipient,
         ErrorCode::InvalidTokenAccountOwner
     );
     
     require!(
-        recipient_token_account_data.mint == token_mint_key,
+        recipient_token_account.mint == token_mint_key,
         ErrorCode::InvalidTokenMint
     );
     
-    // Check if token gate is enabled and validate token ownership
-    if let Some(gate_token_mint) = ctx.accounts.treasury.gate_token_mint {
-        // Verify the recipient owns the token account and it's for the correct mint
-        require!(
-            recipient_token_account_data.mint == gate_token_mint &&
-            recipient_token_account_data.amount > 0,
-            ErrorCode::TokenGateCheckFailed
-        );
-    }
+    // Check if token gate is enabled and validate the recipient's gate tokens
+    check_token_gate(
+        &ctx.accounts.treasury,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_deref(),
+        ctx.accounts.recipient.recipient,
+    )?;
     
-    // Check if this would exceed the spending limit for the current epoch
-    let previous_epoch_spending = token_balance.epoch_spending;
-    let current_epoch_start = if current_time >= ctx.accounts.treasury.last_epoch_start + ctx.accounts.treasury.epoch_duration as i64 {
-        // We're in a new epoch, reset the epoch_spending
-        token_balance.epoch_spending = 0;
-        
+    // Start a new epoch for this mint if the current one has elapsed
+    let epoch_duration = ctx.accounts.treasury.epoch_duration;
+    let rolling = ctx.accounts.treasury.uses_rolling_window();
+    if let Some(previous_epoch_spending) = token_balance.roll_epoch(current_time, epoch_duration) {
         // Emit spending limit reset event
         emit!(SpendingLimitResetEvent {
             treasury: treasury_key,
@@ -185,70 +215,101 @@ pub fn handler(
             timestamp: current_time,
             token_mint: Some(token_mint_key),
         });
-        
-        current_time
-    } else {
-        ctx.accounts.treasury.last_epoch_start
-    };
-    
-    // Update treasury's epoch data
-    ctx.accounts.treasury.last_epoch_start = current_epoch_start;
+    }
     
-    // Check if this withdrawal would exceed the spending limit
-    let new_epoch_spending = token_balance
-        .epoch_spending
-        .checked_add(payout_amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
-        
-    require!(
-        new_epoch_spending <= ctx.accounts.treasury.spending_limit,
-        ErrorCode::SpendingLimitExceeded
-    );
+    // Charge the payout against the mint's balance and spending limit
+    let balance_before = token_balance.balance;
+    token_balance.record_outflow(payout_amount, current_time, epoch_duration, rolling)?;
     
-    // Update token balance state
-    token_balance.epoch_spending = new_epoch_spending;
-    token_balance.balance = token_balance
-        .balance
-        .checked_sub(payout_amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    // Charge the per-recipient limit, and the executing user's unless cranked
+    if let Some(user) = ctx.accounts.user.as_mut() {
+        user.charge_spending(Some(token_mint_key), payout_amount, current_time, epoch_duration)?;
+    }
+    ctx.accounts.recipient.charge_spending(
+        Some(token_mint_key),
+        payout_amount,
+        current_time,
+        epoch_duration,
+    )?;
     
     // Transfer tokens from treasury to recipient
     let treasury_seeds = &[
         TREASURY_SEED,
+        treasury_creator.as_ref(),
+        &treasury_id_bytes,
         &[treasury_bump],
     ];
     let treasury_signer = &[&treasury_seeds[..]];
     
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.treasury_token_account.to_account_info(),
-                to: ctx.accounts.recipient_token_account.to_account_info(),
-                authority: ctx.accounts.treasury.to_account_info(),
-            },
-            treasury_signer,
-        ),
+    transfer_tokens_checked(
+        &ctx.accounts.token_program,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        ctx.remaining_accounts,
         payout_amount,
+        treasury_signer,
     )?;
     
-    // Update payout schedule
-    payout_schedule.last_executed = current_time;
-    
-    // If it's a one-time payout, deactivate it
-    if !payout_schedule.recurring {
-        payout_schedule.is_active = false;
+    // Update payout schedule, deactivating it after its final payment
+    if payout_schedule.record_execution(current_time, periods_settled)? {
+        ctx.accounts.recipient.track_payout_closed()?;
+        
+        emit!(PayoutCompletedEvent {
+            treasury: treasury_key,
+            recipient: payout_schedule.recipient,
+            payout_schedule: payout_schedule.key(),
+            execution_count: payout_schedule.execution_count,
+            timestamp: current_time,
+        });
+        
+        emit!(TreasuryEvent {
+            action: AuditAction::PayoutCompleted as u8,
+            treasury: treasury_key,
+            initiator: ctx.accounts.authority.key(),
+            target: Some(payout_schedule.recipient),
+            amount: 0,
+            timestamp: current_time,
+            token_mint: Some(token_mint_key),
+        });
     }
     
     // Create audit log
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = ctx.accounts.token_balance.balance;
     let audit_log = &mut ctx.accounts.audit_log;
     audit_log.action = AuditAction::TokenPayout as u8;
     audit_log.treasury = treasury_key;
     audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(ctx.accounts.recipient.recipient);
     audit_log.amount = payout_amount;
-    audit_log.timestamp = timestamp;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
     audit_log.token_mint = Some(token_mint_key);
+    audit_log.payout_schedule = Some(ctx.accounts.payout_schedule.key());
     audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    // Tip the caller when the payout was cranked without a treasurer
+    if ctx.accounts.user.is_none() {
+        let payout_schedule_key = ctx.accounts.payout_schedule.key();
+        pay_crank_tip(
+            &mut ctx.accounts.treasury,
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.tip_audit_log.as_mut(),
+            ctx.bumps.tip_audit_log,
+            payout_schedule_key,
+            current_time,
+            timestamp,
+        )?;
+    } else {
+        require!(ctx.accounts.tip_audit_log.is_none(), ErrorCode::InvalidTipAuditLog);
+    }
     
     // Emit events
     emit!(TokenPayoutEvent {
@@ -257,6 +318,7 @@ pub fn handler(
         recipient: ctx.accounts.recipient.recipient,
         token_mint: token_mint_key,
         amount: payout_amount,
+        periods_settled,
         timestamp: current_time,
     });
     
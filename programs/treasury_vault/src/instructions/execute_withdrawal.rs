@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::{charge_recipient_limit, require_whitelisted_recipient},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Withdraw) @ ErrorCode::UnauthorizedWithdrawal
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PENDING_WITHDRAWAL_SEED,
+            treasury.key().as_ref(),
+            &pending_withdrawal.index.to_le_bytes()
+        ],
+        bump = pending_withdrawal.bump,
+        close = rent_receiver
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    #[account(
+        mut,
+        constraint = recipient.key() == pending_withdrawal.recipient @ ErrorCode::WithdrawalRecipientMismatch
+    )]
+    pub recipient: SystemAccount<'info>,
+    
+    /// CHECK: Whitelist entry of the recipient, which may not exist. Its address
+    /// is pinned by the seeds so the whitelist policy and recipient limits always apply
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, recipient.key().as_ref(), treasury.key().as_ref()],
+        bump
+    )]
+    pub whitelisted_recipient: UncheckedAccount<'info>,
+    
+    /// Treasurer who queued the withdrawal and receives its rent
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == pending_withdrawal.requested_by @ ErrorCode::RentReceiverMismatch
+    )]
+    pub rent_receiver: SystemAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute a queued withdrawal once its delay has elapsed. The withdrawal is
+/// charged against the spending limits of the epoch in which it executes: the
+/// treasury's, the executing user's and the recipient's.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The treasurer executing the withdrawal
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[writable]` user: The treasury user account of the authority
+/// 3. `[writable]` pending_withdrawal: The pending withdrawal to execute and close
+/// 4. `[writable]` recipient: The account receiving the withdrawn SOL
+/// 5. `[writable]` whitelisted_recipient: The recipient's whitelist entry PDA, which may not exist
+/// 6. `[writable]` rent_receiver: The treasurer who queued the withdrawal
+/// 7. `[writable]` audit_log: The audit log account to create
+/// 8. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
+pub fn handler(
+    ctx: Context<ExecuteWithdrawal>,
+    timestamp: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
+    
+    // Treasuries with an approval threshold must withdraw through proposals
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    require!(
+        ctx.accounts.pending_withdrawal.is_executable(current_time),
+        ErrorCode::WithdrawalTimelockActive
+    );
+    
+    // The recipient may have been removed from the whitelist during the delay
+    require_whitelisted_recipient(&ctx.accounts.treasury, &ctx.accounts.whitelisted_recipient)?;
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let recipient_key = ctx.accounts.recipient.key();
+    let amount = ctx.accounts.pending_withdrawal.amount;
+    
+    // Check if treasury has enough funds
+    require!(
+        ctx.accounts.treasury.total_funds >= amount,
+        ErrorCode::InsufficientFunds
+    );
+    
+    // Check if we need to reset the epoch
+    let treasury = &mut ctx.accounts.treasury;
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
+        // Emit spending limit reset event
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL withdrawal
+        });
+        
+        // Also emit the treasury event for better tracking
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitReset as u8,
+            treasury: treasury_key,
+            initiator: ctx.accounts.authority.key(),
+            target: None,
+            amount: previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // SOL withdrawal
+        });
+    }
+    
+    // Charge the withdrawal against the spending limit
+    treasury.record_spending(amount, current_time)?;
+    
+    // Charge the per-user and per-recipient limits on top of the treasury's
+    let epoch_duration = treasury.epoch_duration;
+    ctx.accounts.user.charge_spending(None, amount, current_time, epoch_duration)?;
+    charge_recipient_limit(
+        &ctx.accounts.whitelisted_recipient,
+        None,
+        amount,
+        current_time,
+        epoch_duration,
+    )?;
+    
+    let balance_before = treasury.total_funds;
+    // Update treasury state
+    treasury.total_funds = treasury.total_funds.checked_sub(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    // Transfer lamports (native SOL) from treasury to recipient
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    let treasury_lamports = treasury_info.lamports();
+    let recipient_lamports = recipient_info.lamports();
+    
+    require!(
+        treasury_lamports >= amount,
+        ErrorCode::InsufficientFunds
+    );
+    
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports.checked_sub(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports.checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = ctx.accounts.treasury.total_funds;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::Withdraw as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(recipient_key);
+    audit_log.amount = amount;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
+    audit_log.token_mint = None; // SOL withdrawal
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    // Emit events
+    emit!(PendingWithdrawalExecutedEvent {
+        treasury: treasury_key,
+        pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+        executor: ctx.accounts.authority.key(),
+        recipient: recipient_key,
+        amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::Withdraw as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(recipient_key),
+        amount,
+        timestamp: current_time,
+        token_mint: None, // SOL withdrawal
+    });
+    
+    Ok(())
+}
\ No newline at end of file
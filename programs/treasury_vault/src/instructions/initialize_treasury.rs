@@ -8,12 +8,13 @@ use crate::{
 };
 
 #[derive(Accounts)]
+#[instruction(treasury_id: u64)]
 pub struct InitializeTreasury<'info> {
     #[account(
         init,
         payer = admin,
         space = 8 + Treasury::INIT_SPACE,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, admin.key().as_ref(), &treasury_id.to_le_bytes()],
         bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -42,10 +43,12 @@ pub struct InitializeTreasury<'info> {
 /// 3. `[]` system_program: System program for account creation
 ///
 /// Data:
+/// - treasury_id: [u64] Identifier distinguishing this treasury from others created by the same admin
 /// - epoch_duration: [u64] The duration of an epoch in seconds
 /// - spending_limit: [u64] The spending limit per epoch in lamports
 pub fn handler(
     ctx: Context<InitializeTreasury>,
+    treasury_id: u64,
     epoch_duration: u64,
     spending_limit: u64,
 ) -> Result<()> {
@@ -56,6 +59,8 @@ pub fn handler(
 
     let treasury = &mut ctx.accounts.treasury;
     treasury.admin = ctx.accounts.admin.key();
+    treasury.creator = ctx.accounts.admin.key();
+    treasury.treasury_id = treasury_id;
     treasury.epoch_duration = epoch_duration;
     treasury.spending_limit = spending_limit;
     treasury.total_funds = 0;
@@ -64,12 +69,28 @@ pub fn handler(
     treasury.next_payout_index = 0;
     treasury.is_paused = false; // Initialize as unpaused
     treasury.gate_token_mint = None; // Initialize with no token gate
+    treasury.approval_threshold = 1; // Single-signer until a threshold is configured
+    treasury.next_proposal_index = 0;
+    treasury.active_admin_count = 1; // The initializing admin
+    treasury.active_approver_count = 1; // The initializing admin holds Withdraw
+    treasury.pending_admin = None;
+    treasury.crank_tip = 0; // No tip until an admin configures one
+    treasury.audit_log_retention = DEFAULT_AUDIT_LOG_RETENTION;
+    treasury.audit_digest = [0; 32];
+    treasury.closed_audit_log_count = 0;
+    treasury.audit_sequence = 0;
+    treasury.audit_chain_head = [0; 32];
+    treasury.withdrawal_timelock_threshold = 0; // Timelock disabled by default
+    treasury.withdrawal_timelock_delay = 0;
+    treasury.next_pending_withdrawal_index = 0;
+    treasury.pause_expires_at = None;
     treasury.bump = ctx.bumps.treasury;
     
     // Initialize admin user
     let admin_user = &mut ctx.accounts.admin_user;
     admin_user.user = ctx.accounts.admin.key();
     admin_user.role = Role::Admin as u8;
+    admin_user.permissions = ALL_PERMISSIONS;
     admin_user.is_active = true;
     admin_user.treasury = treasury.key();
     admin_user.bump = ctx.bumps.admin_user;
@@ -77,6 +98,8 @@ pub fn handler(
     // Emit event
     emit!(TreasuryInitializedEvent {
         admin: treasury.admin,
+        treasury: treasury.key(),
+        treasury_id,
         epoch_duration,
         spending_limit,
     });
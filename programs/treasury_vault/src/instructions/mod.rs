@@ -13,6 +13,38 @@ pub mod cancel_payout;
 pub mod pause_treasury;
 pub mod unpause_treasury;
 pub mod set_token_gate;
+pub mod create_proposal;
+pub mod approve_proposal;
+pub mod revoke_approval;
+pub mod execute_proposal;
+pub mod deactivate_treasury_user;
+pub mod reactivate_treasury_user;
+pub mod update_user_role;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod update_whitelisted_recipient;
+pub mod remove_whitelisted_recipient;
+pub mod set_token_spending_limit;
+pub mod create_vesting_stream;
+pub mod claim_vesting;
+pub mod claim_token_vesting;
+pub mod cancel_vesting_stream;
+pub mod claim_payout;
+pub mod close_payout_schedule;
+pub mod close_audit_log;
+pub mod verify_audit_range;
+pub mod set_withdrawal_timelock;
+pub mod queue_withdrawal;
+pub mod execute_withdrawal;
+pub mod veto_withdrawal;
+pub mod set_user_permissions;
+pub mod set_user_spending_limit;
+pub mod set_recipient_spending_limit;
+pub mod set_withdrawal_whitelist;
+pub mod create_distribution;
+pub mod claim_distribution;
+pub mod reclaim_distribution;
 
 pub use initialize_treasury::*;
 pub use deposit::*;
@@ -28,4 +60,36 @@ pub use execute_token_payout::*;
 pub use cancel_payout::*;
 pub use pause_treasury::*;
 pub use unpause_treasury::*;
-pub use set_token_gate::*;
\ No newline at end of file
+pub use set_token_gate::*;
+pub use create_proposal::*;
+pub use approve_proposal::*;
+pub use revoke_approval::*;
+pub use execute_proposal::*;
+pub use deactivate_treasury_user::*;
+pub use reactivate_treasury_user::*;
+pub use update_user_role::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use cancel_admin_transfer::*;
+pub use update_whitelisted_recipient::*;
+pub use remove_whitelisted_recipient::*;
+pub use set_token_spending_limit::*;
+pub use create_vesting_stream::*;
+pub use claim_vesting::*;
+pub use claim_token_vesting::*;
+pub use cancel_vesting_stream::*;
+pub use claim_payout::*;
+pub use close_payout_schedule::*;
+pub use close_audit_log::*;
+pub use verify_audit_range::*;
+pub use set_withdrawal_timelock::*;
+pub use queue_withdrawal::*;
+pub use execute_withdrawal::*;
+pub use veto_withdrawal::*;
+pub use set_user_permissions::*;
+pub use set_user_spending_limit::*;
+pub use set_recipient_spending_limit::*;
+pub use set_withdrawal_whitelist::*;
+pub use create_distribution::*;
+pub use claim_distribution::*;
+pub use reclaim_distribution::*;
\ No newline at end of file
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = treasury.admin == authority.key() @ ErrorCode::UnauthorizedAdminTransfer
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::ManageUsers) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+
+    /// CHECK: This is just a pubkey that will be stored as the pending admin
+    pub new_admin: UncheckedAccount<'info>,
+}
+
+/// Propose a new treasury admin. The handover completes only when the
+/// proposed key signs accept_admin, so a mistyped key can't lock the vault.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The current treasury admin
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[]` new_admin: The proposed admin
+pub fn handler(
+    ctx: Context<ProposeAdmin>,
+) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    let new_admin = ctx.accounts.new_admin.key();
+
+    require!(new_admin != treasury.admin, ErrorCode::InvalidPendingAdmin);
+
+    // Replaces any earlier pending proposal
+    treasury.pending_admin = Some(new_admin);
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Emit events
+    emit!(AdminTransferProposedEvent {
+        treasury: treasury.key(),
+        current_admin: treasury.admin,
+        pending_admin: new_admin,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::ProposeAdmin as u8,
+        treasury: treasury.key(),
+        initiator: ctx.accounts.authority.key(),
+        target: Some(new_admin),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::require_whitelisted_recipient,
+};
+
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Withdraw) @ ErrorCode::UnauthorizedWithdrawal
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingWithdrawal::INIT_SPACE,
+        seeds = [
+            PENDING_WITHDRAWAL_SEED,
+            treasury.key().as_ref(),
+            &treasury.next_pending_withdrawal_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    pub recipient: SystemAccount<'info>,
+    
+    /// CHECK: Whitelist entry of the recipient, which may not exist. Its address
+    /// is pinned by the seeds so the whitelist policy can't be bypassed
+    #[account(
+        seeds = [RECIPIENT_SEED, recipient.key().as_ref(), treasury.key().as_ref()],
+        bump
+    )]
+    pub whitelisted_recipient: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a SOL withdrawal behind the treasury's timelock. It can be executed
+/// once the delay has elapsed and any admin can veto it until then.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The treasurer queueing the withdrawal
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` pending_withdrawal: The pending withdrawal account to create
+/// 4. `[readable]` recipient: The account that will receive the SOL
+/// 5. `[readable]` whitelisted_recipient: The recipient's whitelist entry PDA, which may not exist
+/// 6. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - amount: [u64] The amount of SOL to withdraw in lamports
+pub fn handler(
+    ctx: Context<QueueWithdrawal>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidWithdrawAmount);
+    
+    // Treasuries with an approval threshold must withdraw through proposals
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    require!(
+        ctx.accounts.treasury.total_funds >= amount,
+        ErrorCode::InsufficientFunds
+    );
+    require_whitelisted_recipient(&ctx.accounts.treasury, &ctx.accounts.whitelisted_recipient)?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let treasury_key = ctx.accounts.treasury.key();
+    let treasury = &mut ctx.accounts.treasury;
+    let executable_at = current_time
+        .checked_add(treasury.withdrawal_timelock_delay as i64)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    
+    let pending_withdrawal = &mut ctx.accounts.pending_withdrawal;
+    pending_withdrawal.treasury = treasury_key;
+    pending_withdrawal.requested_by = ctx.accounts.authority.key();
+    pending_withdrawal.recipient = ctx.accounts.recipient.key();
+    pending_withdrawal.amount = amount;
+    pending_withdrawal.index = treasury.get_next_pending_withdrawal_index();
+    pending_withdrawal.queued_at = current_time;
+    pending_withdrawal.executable_at = executable_at;
+    pending_withdrawal.bump = ctx.bumps.pending_withdrawal;
+    
+    // Emit events
+    emit!(WithdrawalQueuedEvent {
+        treasury: treasury_key,
+        pending_withdrawal: pending_withdrawal.key(),
+        requested_by: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        executable_at,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::WithdrawalQueued as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(ctx.accounts.recipient.key()),
+        amount,
+        timestamp: current_time,
+        token_mint: None, // SOL withdrawal
+    });
+    
+    Ok(())
+}
\ No newline at end of file
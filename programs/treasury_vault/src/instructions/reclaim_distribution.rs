@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ReclaimDistribution<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_SEED, treasury.key().as_ref(), &distribution.index.to_le_bytes()],
+        bump = distribution.bump,
+        close = rent_receiver
+    )]
+    pub distribution: Account<'info, Distribution>,
+    
+    /// Token balance of the distribution's mint, required for SPL token distributions
+    #[account(mut)]
+    pub token_balance: Option<Account<'info, TokenBalance>>,
+    
+    /// Original payer of the distribution, receives its rent
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == distribution.created_by @ ErrorCode::RentReceiverMismatch
+    )]
+    pub rent_receiver: SystemAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow admin users to return the unclaimed rest of an expired distribution
+/// to the treasury's spendable funds and close the distribution
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The admin reclaiming the distribution
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` distribution: The expired distribution to close
+/// 4. `[writable, optional]` token_balance: The token balance for SPL token distributions
+/// 5. `[writable]` rent_receiver: The account that paid for the distribution
+/// 6. `[writable]` audit_log: The audit log account to create
+/// 7. `[]` system_program: System program for account creation
+pub fn handler(
+    ctx: Context<ReclaimDistribution>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let distribution = &ctx.accounts.distribution;
+    require!(distribution.is_expired(current_time), ErrorCode::DistributionNotExpired);
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let distribution_key = distribution.key();
+    let token_mint = distribution.token_mint;
+    let unclaimed_amount = distribution.unclaimed_amount();
+    
+    // Release the unclaimed rest back to the spendable balance
+    let (balance_before, balance_after) = match token_mint {
+        Some(token_mint_key) => {
+            let token_balance = ctx.accounts.token_balance.as_mut()
+                .ok_or(ErrorCode::TokenBalanceNotFound)?;
+            require!(
+                token_balance.treasury == treasury_key && token_balance.token_mint == token_mint_key,
+                ErrorCode::InvalidTokenMint
+            );
+            
+            let balance_before = token_balance.balance;
+            token_balance.release(unclaimed_amount)?;
+            (balance_before, token_balance.balance)
+        }
+        None => {
+            let treasury = &mut ctx.accounts.treasury;
+            let balance_before = treasury.total_funds;
+            treasury.total_funds = treasury.total_funds.checked_add(unclaimed_amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            (balance_before, treasury.total_funds)
+        }
+    };
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::ReclaimDistribution as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(distribution_key);
+    audit_log.amount = unclaimed_amount;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = token_mint;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    emit!(DistributionReclaimedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        distribution: distribution_key,
+        token_mint,
+        amount: unclaimed_amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::ReclaimDistribution as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(distribution_key),
+        amount: unclaimed_amount,
+        timestamp: current_time,
+        token_mint,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
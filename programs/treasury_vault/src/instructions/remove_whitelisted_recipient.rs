@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct RemoveWhitelistedRecipient<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::ManageRecipients) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RECIPIENT_SEED, recipient_account.recipient.as_ref(), treasury.key().as_ref()],
+        bump = recipient_account.bump
+    )]
+    pub recipient_account: Account<'info, WhitelistedRecipient>,
+}
+
+/// Allow admin users to remove a whitelisted recipient, closing its account and
+/// refunding rent to the authority. Every active payout schedule of the recipient
+/// must be passed as a writable remaining account and is cancelled. Vesting
+/// streams hold funds owed to the recipient, so all of them must be cancelled
+/// and settled first.
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The admin removing the recipient
+/// 1. `[readable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` recipient_account: The whitelisted recipient to close
+/// 4. `[writable]` remaining_accounts: The recipient's active payout schedules
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveWhitelistedRecipient<'info>>,
+) -> Result<()> {
+    let treasury_key = ctx.accounts.treasury.key();
+    let recipient_key = ctx.accounts.recipient_account.recipient;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Vested funds stay claimable after a stream is cancelled, keep the recipient until they're settled
+    require!(
+        ctx.accounts.recipient_account.active_vesting_count == 0,
+        ErrorCode::ActiveVestingStreamsRemaining
+    );
+
+    // Cancel every active payout schedule of the recipient
+    let mut cancelled_payouts: u32 = 0;
+    for payout_info in ctx.remaining_accounts.iter() {
+        let mut payout_schedule = Account::<PayoutSchedule>::try_from(payout_info)?;
+
+        require!(
+            payout_schedule.treasury == treasury_key && payout_schedule.recipient == recipient_key,
+            ErrorCode::PayoutRecipientMismatch
+        );
+
+        if !payout_schedule.is_active {
+            continue;
+        }
+
+        payout_schedule.is_active = false;
+        payout_schedule.exit(&crate::ID)?;
+        cancelled_payouts = cancelled_payouts.checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        emit!(TreasuryEvent {
+            action: AuditAction::CancelPayout as u8,
+            treasury: treasury_key,
+            initiator: ctx.accounts.authority.key(),
+            target: Some(recipient_key),
+            amount: payout_schedule.amount,
+            timestamp: current_time,
+            token_mint: payout_schedule.token_mint,
+        });
+    }
+
+    // Refuse to remove the recipient while any active schedule was left out
+    require!(
+        cancelled_payouts == ctx.accounts.recipient_account.active_payout_count,
+        ErrorCode::ActivePayoutsRemaining
+    );
+
+    // Emit events
+    emit!(RecipientRemovedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        recipient: recipient_key,
+        cancelled_payouts,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::RemoveRecipient as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(recipient_key),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct RevokeApproval<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Withdraw the signer's approval from a pending proposal
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The user revoking their approval
+/// 1. `[readable]` treasury: The treasury account
+/// 2. `[writable]` proposal: The proposal to revoke the approval from
+pub fn handler(
+    ctx: Context<RevokeApproval>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let approver = ctx.accounts.authority.key();
+    let proposal = &mut ctx.accounts.proposal;
+
+    // A deactivated user can still revoke an approval they gave earlier
+    require!(proposal.has_approved(&approver), ErrorCode::ProposalNotApproved);
+
+    proposal.approvals.retain(|key| key != &approver);
+
+    // Emit events
+    emit!(ProposalApprovalRevokedEvent {
+        approver,
+        treasury: ctx.accounts.treasury.key(),
+        proposal: proposal.key(),
+        approvals: proposal.approval_count(),
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::RevokeApproval as u8,
+        treasury: ctx.accounts.treasury.key(),
+        initiator: approver,
+        target: Some(proposal.key()),
+        amount: proposal.amount,
+        timestamp: current_time,
+        token_mint: proposal.token_mint,
+    });
+
+    Ok(())
+}
\ No newline at end of file
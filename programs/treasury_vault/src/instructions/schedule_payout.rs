@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{Mint, TokenInterface};
 
 use crate::{
     constants::*,
@@ -16,8 +16,9 @@ pub struct SchedulePayout<'info> {
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
     )]
     pub treasury: Account<'info, Treasury>,
     
@@ -25,11 +26,12 @@ pub struct SchedulePayout<'info> {
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Treasurer) @ ErrorCode::UnauthorizedUser
+        constraint = user.has_permission(Permission::Schedule) @ ErrorCode::UnauthorizedUser
     )]
     pub user: Account<'info, TreasuryUser>,
     
     #[account(
+        mut,
         seeds = [RECIPIENT_SEED, recipient.recipient.as_ref(), treasury.key().as_ref()],
         bump = recipient.bump,
         constraint = recipient.is_active @ ErrorCode::RecipientNotActive
@@ -51,11 +53,10 @@ pub struct SchedulePayout<'info> {
     pub payout_schedule: Account<'info, PayoutSchedule>,
     
     /// Optional token mint for SPL token payouts
-    /// CHECK: This is validated in the handler
-    pub token_mint: Option<AccountInfo<'info>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
     
-    /// Optional token program for SPL token payouts
-    pub token_program: Option<Program<'info, Token>>,
+    /// Optional token program (SPL Token or Token-2022) for SPL token payouts
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -68,7 +69,10 @@ pub fn handler(
     recurring: bool,
     recurrence_interval: u64,
     index: u64,
+    options: PayoutOptions,
 ) -> Result<()> {
+    let PayoutOptions { end_time, max_executions, catch_up_policy } = options;
+    
     // Validate inputs
     require!(amount > 0, ErrorCode::InvalidWithdrawAmount);
     
@@ -77,20 +81,39 @@ pub fn handler(
     
     if recurring {
         require!(recurrence_interval > 0, ErrorCode::InvalidRecurrenceInterval);
+    } else {
+        require!(end_time.is_none() && max_executions.is_none(), ErrorCode::InvalidPayoutBounds);
+    }
+    
+    if let Some(end_time) = end_time {
+        require!(end_time >= schedule_time, ErrorCode::InvalidEndTime);
     }
     
+    if let Some(max_executions) = max_executions {
+        require!(max_executions > 0, ErrorCode::InvalidMaxExecutions);
+    }
+    
+    // Missed periods are paid in full unless another policy is requested
+    let catch_up_policy = match catch_up_policy {
+        Some(value) => CatchUpPolicy::from_u8(value).ok_or(ErrorCode::InvalidCatchUpPolicy)?,
+        None => CatchUpPolicy::PayAll,
+    };
+    
     let payout_schedule = &mut ctx.accounts.payout_schedule;
     let treasury = &mut ctx.accounts.treasury;
-    let recipient = &ctx.accounts.recipient;
+    let recipient = &mut ctx.accounts.recipient;
     
     // Get token mint if provided
     let token_mint_pubkey = if let Some(token_mint) = &ctx.accounts.token_mint {
         // If token mint is provided, token program must also be provided
-        require!(ctx.accounts.token_program.is_some(), ErrorCode::TokenProgramRequired);
+        let token_program = ctx.accounts.token_program.as_ref()
+            .ok_or(ErrorCode::TokenProgramRequired)?;
         
-        // Validate token mint
-        // We don't need to deserialize it, just check that it's a valid account
-        require!(!token_mint.data_is_empty(), ErrorCode::InvalidTokenMint);
+        // Validate the mint belongs to the supplied token program
+        require!(
+            token_mint.to_account_info().owner == token_program.key,
+            ErrorCode::InvalidTokenMint
+        );
         
         Some(token_mint.key())
     } else {
@@ -109,8 +132,16 @@ pub fn handler(
     payout_schedule.treasury = treasury.key();
     payout_schedule.index = index;
     payout_schedule.token_mint = token_mint_pubkey;
+    payout_schedule.end_time = end_time;
+    payout_schedule.max_executions = max_executions;
+    payout_schedule.execution_count = 0;
+    payout_schedule.catch_up_policy = catch_up_policy as u8;
+    payout_schedule.next_due_time = schedule_time;
     payout_schedule.bump = ctx.bumps.payout_schedule;
     
+    // Track the active schedule on the recipient
+    recipient.track_payout_scheduled()?;
+    
     // Create audit log
     emit!(TreasuryEvent {
         action: AuditAction::SchedulePayout as u8,
@@ -11,7 +11,7 @@ use crate::{
 pub struct SetTokenGate<'info> {
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -23,13 +23,26 @@ pub struct SetTokenGate<'info> {
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.is_admin() @ ErrorCode::UnauthorizedConfigUpdate
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedConfigUpdate
     )]
     pub user: Account<'info, TreasuryUser>,
     
-    /// CHECK: This is an optional token mint that will be stored
+    /// CHECK: This is an optional token mint, or collection key in collection mode, that will be stored
     pub token_mint: Option<UncheckedAccount<'info>>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -39,15 +52,32 @@ pub struct SetTokenGate<'info> {
 /// 0. `[writable]` treasury: The treasury account
 /// 1. `[writable, signer]` authority: The user initiating the update
 /// 2. `[readable]` user: The treasury user account of the authority
-/// 3. `[optional]` token_mint: The token mint to use for gating (optional)
-/// 4. `[]` system_program: System program
+/// 3. `[optional]` token_mint: The token mint to use for gating, or the collection key in collection mode (optional)
+/// 4. `[writable]` audit_log: The audit log account to create
+/// 5. `[]` system_program: System program
+///
+/// Data:
+/// - min_balance: [u64] Gate tokens a recipient must hold, ignored when the gate is disabled or in collection mode
+/// - mode: [u8] TokenGateMode: 0 = Mint, 1 = Collection
 pub fn handler(
     ctx: Context<SetTokenGate>,
+    min_balance: u64,
+    mode: u8,
 ) -> Result<()> {
+    let gate_mode = TokenGateMode::from_u8(mode).ok_or(ErrorCode::InvalidTokenGateMode)?;
     let treasury = &mut ctx.accounts.treasury;
     
-    // Update the token gate
+    // Update the token gate, clearing the threshold when the gate is disabled
     treasury.gate_token_mint = ctx.accounts.token_mint.as_ref().map(|mint| mint.key());
+    treasury.gate_mode = gate_mode as u8;
+    treasury.gate_min_balance = match (treasury.gate_token_mint, gate_mode) {
+        (Some(_), TokenGateMode::Collection) => 1, // Any single NFT of the collection
+        (Some(_), TokenGateMode::Mint) => {
+            require!(min_balance > 0, ErrorCode::InvalidGateMinBalance);
+            min_balance
+        }
+        (None, _) => 0,
+    };
     
     // Get current timestamp
     let current_time = Clock::get()?.unix_timestamp;
@@ -57,6 +87,8 @@ pub fn handler(
         admin: ctx.accounts.authority.key(),
         treasury: treasury.key(),
         token_mint: treasury.gate_token_mint,
+        min_balance: treasury.gate_min_balance,
+        mode: treasury.gate_mode,
         timestamp: current_time,
     });
     
@@ -66,10 +98,30 @@ pub fn handler(
         treasury: treasury.key(),
         initiator: ctx.accounts.authority.key(),
         target: treasury.gate_token_mint,
-        amount: 0,
+        amount: treasury.gate_min_balance,
         timestamp: current_time,
         token_mint: treasury.gate_token_mint, // Use the gate token mint
     });
 
+    // Create audit log entry
+    let treasury_key = ctx.accounts.treasury.key();
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::TokenGateSet as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = ctx.accounts.treasury.gate_token_mint;
+    audit_log.amount = ctx.accounts.treasury.gate_min_balance;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = ctx.accounts.treasury.gate_token_mint;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct SetTokenSpendingLimit<'info> {
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedConfigUpdate
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TokenBalance::INIT_SPACE,
+        seeds = [TOKEN_BALANCE_SEED, treasury.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow admin users to set the per-epoch spending limit of a token mint.
+/// The limit is expressed in the mint's base units and applies to every
+/// token outflow of that mint. The token balance account is created if needed.
+///
+/// Accounts:
+/// 0. `[readable]` treasury: The treasury account
+/// 1. `[writable, signer]` authority: The user initiating the update
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` token_balance: The token balance account of the mint
+/// 4. `[readable]` token_mint: The token mint the limit applies to
+/// 5. `[]` system_program: System program for account creation
+///
+/// Data:
+/// - spending_limit: [u64] New spending limit per epoch in base units
+pub fn handler(
+    ctx: Context<SetTokenSpendingLimit>,
+    spending_limit: u64,
+) -> Result<()> {
+    // Once a threshold is set, config changes must go through proposals
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    require!(spending_limit > 0, ErrorCode::InvalidSpendingLimit);
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // If token balance account is new, initialize it
+    let token_balance = &mut ctx.accounts.token_balance;
+    if token_balance.treasury == Pubkey::default() {
+        **token_balance = TokenBalance::new(
+            treasury_key,
+            token_mint_key,
+            current_time,
+            ctx.bumps.token_balance,
+        );
+        
+        // Emit token balance created event
+        emit!(TokenBalanceCreatedEvent {
+            treasury: treasury_key,
+            token_mint: token_mint_key,
+            timestamp: current_time,
+        });
+    }
+    
+    let old_limit = token_balance.spending_limit;
+    token_balance.spending_limit = spending_limit;
+    
+    // Emit events
+    emit!(TokenSpendingLimitUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        token_mint: token_mint_key,
+        old_limit,
+        new_limit: spending_limit,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::TokenSpendingLimitUpdated as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: None,
+        amount: spending_limit,
+        timestamp: current_time,
+        token_mint: Some(token_mint_key),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::execute_user_proposal,
+};
+
+#[derive(Accounts)]
+pub struct SetUserPermissions<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::ManageUsers) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_account.user.as_ref(), treasury.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, TreasuryUser>,
+    
+    /// Approved proposal for this change, required while the treasury requires multisig
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+}
+
+/// Allow users managing users to replace the permission bitmask of a
+/// non-admin treasury user. Only permissions the caller holds can be granted.
+///
+/// Accounts:
+/// 0. `[signer]` authority: The user initiating the change
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` user_account: The treasury user account to update
+/// 4. `[writable, optional]` proposal: The approved proposal, required while the treasury requires multisig
+/// 5. `[]` remaining_accounts: The treasury user accounts of the proposal's approvers, in approval order
+///
+/// Data:
+/// - permissions: [u16] The new bitmask of Permission values
+pub fn handler(
+    ctx: Context<SetUserPermissions>,
+    permissions: u16,
+) -> Result<()> {
+    require!(permissions & !ALL_PERMISSIONS == 0, ErrorCode::InvalidPermissions);
+    require!(
+        permissions & !ctx.accounts.user.permissions == 0,
+        ErrorCode::PermissionEscalation
+    );
+    
+    // Under multisig the change needs an approved proposal
+    execute_user_proposal(
+        &ctx.accounts.treasury,
+        ctx.accounts.proposal.as_mut(),
+        ctx.remaining_accounts,
+        ctx.accounts.authority.key(),
+        ProposalAction::SetUserPermissions,
+        ctx.accounts.user_account.user,
+        None,
+        Some(permissions),
+    )?;
+    
+    // Admins always hold every permission so the treasury can't be locked out
+    let user_account = &mut ctx.accounts.user_account;
+    require!(
+        user_account.role != Role::Admin as u8,
+        ErrorCode::AdminPermissionsFixed
+    );
+    
+    let old_permissions = user_account.permissions;
+    let was_approver = user_account.can_approve();
+    user_account.permissions = permissions;
+    ctx.accounts.treasury.update_approver_count(was_approver, user_account.can_approve())?;
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Emit events
+    emit!(UserPermissionsUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        user: user_account.user,
+        old_permissions,
+        new_permissions: permissions,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::UpdateUserPermissions as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(user_account.user),
+        amount: permissions as u64,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+    utils::execute_spending_limit_proposal,
+};
+
+#[derive(Accounts)]
+pub struct SetUserSpendingLimit<'info> {
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedConfigUpdate
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_account.user.as_ref(), treasury.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, TreasuryUser>,
+    
+    /// Approved proposal for this change, required while the treasury requires multisig
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+}
+
+/// Allow admin users to set or remove the per-epoch spending limit of a
+/// treasury user for SOL or a token mint. Caps the user's own withdrawals and the payouts they execute, per epoch, on top of the treasury's limit.
+///
+/// Accounts:
+/// 0. `[readable]` treasury: The treasury account
+/// 1. `[signer]` authority: The user initiating the update
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` user_account: The treasury user the limit applies to
+/// 4. `[writable, optional]` proposal: The approved proposal, required while the treasury requires multisig
+/// 5. `[]` remaining_accounts: The treasury user accounts of the proposal's approvers, in approval order
+///
+/// Data:
+/// - token_mint: [Option<Pubkey>] The token mint the limit applies to, None for SOL
+/// - limit: [Option<u64>] New spending limit per epoch in base units, None to remove it
+pub fn handler(
+    ctx: Context<SetUserSpendingLimit>,
+    token_mint: Option<Pubkey>,
+    limit: Option<u64>,
+) -> Result<()> {
+    require!(limit != Some(0), ErrorCode::InvalidSpendingLimit);
+    
+    // Under multisig the change needs an approved proposal
+    execute_spending_limit_proposal(
+        &ctx.accounts.treasury,
+        ctx.accounts.proposal.as_mut(),
+        ctx.remaining_accounts,
+        ctx.accounts.authority.key(),
+        ProposalAction::SetUserSpendingLimit,
+        ctx.accounts.user_account.user,
+        token_mint,
+        limit,
+    )?;
+    
+    let treasury_key = ctx.accounts.treasury.key();
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    let user_account = &mut ctx.accounts.user_account;
+    let old_limit = set_epoch_limit(
+        &mut user_account.epoch_limits,
+        token_mint,
+        limit,
+        current_time,
+    )?;
+    
+    // Emit events
+    emit!(UserSpendingLimitUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        user: user_account.user,
+        token_mint,
+        old_limit,
+        new_limit: limit,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::UserSpendingLimitUpdated as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(user_account.user),
+        amount: limit.unwrap_or(0),
+        timestamp: current_time,
+        token_mint,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct SetWithdrawalWhitelist<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedConfigUpdate
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow admin users to require that direct withdrawals only go to active
+/// whitelisted recipients, as scheduled payouts already do. Treasuries with an
+/// approval threshold change it through a config change proposal.
+///
+/// Accounts:
+/// 0. `[writable]` treasury: The treasury account
+/// 1. `[writable, signer]` authority: The user initiating the update
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` audit_log: The audit log account to create
+/// 4. `[]` system_program: System program
+///
+/// Data:
+/// - required: [bool] Whether withdrawals must go to an active whitelisted recipient
+pub fn handler(
+    ctx: Context<SetWithdrawalWhitelist>,
+    required: bool,
+) -> Result<()> {
+    // A multisig treasury flips this policy with a config change proposal
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.withdrawals_require_whitelist = required;
+    
+    let treasury_key = treasury.key();
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::WithdrawalWhitelistUpdated as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = None;
+    audit_log.amount = required as u64;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = None;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    // Emit events
+    emit!(WithdrawalWhitelistUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: treasury_key,
+        required,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::WithdrawalWhitelistUpdated as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: None,
+        amount: required as u64,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+    
+    Ok(())
+}
\ No newline at end of file
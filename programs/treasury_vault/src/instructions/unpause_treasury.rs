@@ -11,7 +11,7 @@ use crate::{
 pub struct UnpauseTreasury<'info> {
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -23,10 +23,24 @@ pub struct UnpauseTreasury<'info> {
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.is_admin() @ ErrorCode::UnauthorizedPauseAction
+        constraint = user.has_permission(Permission::Pause) @ ErrorCode::UnauthorizedPauseAction,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedPauseAction
     )]
     pub user: Account<'info, TreasuryUser>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -36,7 +50,8 @@ pub struct UnpauseTreasury<'info> {
 /// 0. `[writable]` treasury: The treasury account
 /// 1. `[writable, signer]` authority: The user initiating the unpause
 /// 2. `[readable]` user: The treasury user account of the authority
-/// 3. `[]` system_program: System program
+/// 3. `[writable]` audit_log: The audit log account to create
+/// 4. `[]` system_program: System program
 pub fn handler(
     ctx: Context<UnpauseTreasury>,
 ) -> Result<()> {
@@ -47,6 +62,7 @@ pub fn handler(
     
     // Unpause the treasury
     treasury.is_paused = false;
+    treasury.pause_expires_at = None;
     
     // Get current timestamp
     let current_time = Clock::get()?.unix_timestamp;
@@ -69,5 +85,25 @@ pub fn handler(
         token_mint: None, // Not token related
     });
 
+    // Create audit log entry
+    let treasury_key = ctx.accounts.treasury.key();
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::UnpauseTreasury as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = None;
+    audit_log.amount = 0;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = None;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
     Ok(())
 }
\ No newline at end of file
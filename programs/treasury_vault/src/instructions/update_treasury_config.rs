@@ -11,7 +11,7 @@ use crate::{
 pub struct UpdateTreasuryConfig<'info> {
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
@@ -23,10 +23,23 @@ pub struct UpdateTreasuryConfig<'info> {
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.is_admin() @ ErrorCode::UnauthorizedConfigUpdate
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedConfigUpdate
     )]
     pub user: Account<'info, TreasuryUser>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -36,24 +49,34 @@ pub struct UpdateTreasuryConfig<'info> {
 /// 0. `[writable]` treasury: The treasury account
 /// 1. `[writable, signer]` authority: The user initiating the update
 /// 2. `[readable]` user: The treasury user account of the authority
-/// 3. `[]` system_program: System program
+/// 3. `[writable]` audit_log: The audit log account to create
+/// 4. `[]` system_program: System program
 ///
 /// Data:
-/// - epoch_duration: [Option<u64>] Optional new epoch duration
-/// - spending_limit: [Option<u64>] Optional new spending limit
+/// - config: [TreasuryConfigUpdate] The settings to change, None keeps the current value
+///   - epoch_duration: [Option<u64>] New epoch duration
+///   - spending_limit: [Option<u64>] New spending limit
+///   - approval_threshold: [Option<u8>] Number of approvals required for proposals
+///   - crank_tip: [Option<u64>] Lamport tip paid to permissionless payout callers
+///   - audit_log_retention: [Option<u64>] Age in seconds after which audit logs can be closed
+///   - spending_limit_mode: [Option<u8>] SpendingLimitMode, fixed epochs or a rolling window
+///   - withdrawal_timelock_threshold: [Option<u64>] Lamports above which withdrawals are timelocked, 0 disables it
+///   - withdrawal_timelock_delay: [Option<u64>] Seconds a queued withdrawal waits before execution
+///   - withdrawals_require_whitelist: [Option<bool>] Whether withdrawals must go to active whitelisted recipients
 pub fn handler(
     ctx: Context<UpdateTreasuryConfig>,
-    epoch_duration: Option<u64>,
-    spending_limit: Option<u64>,
+    config: TreasuryConfigUpdate,
 ) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = Clock::get()?.unix_timestamp;
 
+    // Once a threshold is set, config changes must go through proposals
+    require!(!treasury.requires_multisig(), ErrorCode::MultisigApprovalRequired);
+
+    treasury.validate_config(&config)?;
+
     // Update epoch duration if provided
-    if let Some(duration) = epoch_duration {
-        require!(duration > 0, ErrorCode::InvalidEpochDuration);
-        require!(duration >= MIN_EPOCH_DURATION, ErrorCode::EpochDurationTooShort);
-        
+    if let Some(duration) = config.epoch_duration {
         let old_duration = treasury.epoch_duration;
         treasury.epoch_duration = duration;
         
@@ -79,11 +102,80 @@ pub fn handler(
     }
 
     // Update spending limit if provided
-    if let Some(limit) = spending_limit {
-        require!(limit > 0, ErrorCode::InvalidSpendingLimit);
+    if let Some(limit) = config.spending_limit {
         treasury.spending_limit = limit;
     }
 
+    // Update approval threshold if provided
+    if let Some(threshold) = config.approval_threshold {
+        treasury.approval_threshold = threshold;
+    }
+
+    // Update crank tip if provided
+    if let Some(tip) = config.crank_tip {
+        treasury.crank_tip = tip;
+    }
+
+    // Update audit log retention if provided
+    if let Some(retention) = config.audit_log_retention {
+        treasury.audit_log_retention = retention;
+        
+        emit!(TreasuryEvent {
+            action: AuditAction::AuditLogRetentionUpdated as u8,
+            treasury: treasury.key(),
+            initiator: ctx.accounts.authority.key(),
+            target: None,
+            amount: retention,
+            timestamp: current_time,
+            token_mint: None, // Not token related
+        });
+    }
+
+    // Update spending limit mode if provided
+    if let Some(mode) = config.spending_limit_mode {
+        treasury.spending_limit_mode = mode;
+        
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitModeUpdated as u8,
+            treasury: treasury.key(),
+            initiator: ctx.accounts.authority.key(),
+            target: None,
+            amount: mode as u64,
+            timestamp: current_time,
+            token_mint: None, // Applies to SOL and every mint
+        });
+    }
+
+    // Update withdrawal timelock if provided
+    if config.withdrawal_timelock_threshold.is_some() || config.withdrawal_timelock_delay.is_some() {
+        if let Some(threshold) = config.withdrawal_timelock_threshold {
+            treasury.withdrawal_timelock_threshold = threshold;
+        }
+        if let Some(delay) = config.withdrawal_timelock_delay {
+            treasury.withdrawal_timelock_delay = delay;
+        }
+        
+        emit!(WithdrawalTimelockUpdatedEvent {
+            admin: ctx.accounts.authority.key(),
+            treasury: treasury.key(),
+            threshold: treasury.withdrawal_timelock_threshold,
+            delay: treasury.withdrawal_timelock_delay,
+            timestamp: current_time,
+        });
+    }
+
+    // Update withdrawal whitelist policy if provided
+    if let Some(required) = config.withdrawals_require_whitelist {
+        treasury.withdrawals_require_whitelist = required;
+        
+        emit!(WithdrawalWhitelistUpdatedEvent {
+            admin: ctx.accounts.authority.key(),
+            treasury: treasury.key(),
+            required,
+            timestamp: current_time,
+        });
+    }
+
     // Emit general config updated event
     emit!(TreasuryConfigUpdatedEvent {
         admin: ctx.accounts.authority.key(),
@@ -91,5 +183,25 @@ pub fn handler(
         spending_limit: treasury.spending_limit,
     });
 
+    // Create audit log entry
+    let treasury_key = ctx.accounts.treasury.key();
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::TreasuryConfigUpdated as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = None;
+    audit_log.amount = 0;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = None;
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct UpdateWhitelistedRecipient<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::ManageRecipients) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, recipient_account.recipient.as_ref(), treasury.key().as_ref()],
+        bump = recipient_account.bump
+    )]
+    pub recipient_account: Account<'info, WhitelistedRecipient>,
+}
+
+/// Allow admin users to rename a whitelisted recipient or toggle whether it is active
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The admin initiating the update
+/// 1. `[readable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` recipient_account: The whitelisted recipient to update
+///
+/// Data:
+/// - name: [Option<String>] Optional new display name
+/// - is_active: [Option<bool>] Optional new active flag
+pub fn handler(
+    ctx: Context<UpdateWhitelistedRecipient>,
+    name: Option<String>,
+    is_active: Option<bool>,
+) -> Result<()> {
+    let recipient_account = &mut ctx.accounts.recipient_account;
+
+    // Update name if provided
+    if let Some(name) = name {
+        require!(name.len() <= MAX_RECIPIENT_NAME_LEN, ErrorCode::RecipientNameTooLong);
+        recipient_account.name = name;
+    }
+
+    // Update active flag if provided
+    if let Some(is_active) = is_active {
+        recipient_account.is_active = is_active;
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Emit events
+    emit!(RecipientUpdatedEvent {
+        admin: ctx.accounts.authority.key(),
+        treasury: ctx.accounts.treasury.key(),
+        recipient: recipient_account.recipient,
+        name: recipient_account.name.clone(),
+        is_active: recipient_account.is_active,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::UpdateRecipient as u8,
+        treasury: ctx.accounts.treasury.key(),
+        initiator: ctx.accounts.authority.key(),
+        target: Some(recipient_account.recipient),
+        amount: 0,
+        timestamp: current_time,
+        token_mint: None, // Not token related
+    });
+
+    Ok(())
+}
\ No newline at end of file
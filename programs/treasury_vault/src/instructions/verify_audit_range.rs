@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct VerifyAuditRange<'info> {
+    #[account(
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+/// Recompute the audit hash chain over a contiguous slice of audit logs.
+/// Fails if any entry was altered, an entry in the range is missing, or the
+/// slice ends at the latest entry but does not match the treasury's chain head.
+///
+/// Accounts:
+/// 0. `[readable]` treasury: The treasury account
+/// 1. `[readable]` remaining_accounts: The audit logs to verify, in sequence order
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyAuditRange<'info>>,
+) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::EmptyAuditRange);
+
+    let treasury = &ctx.accounts.treasury;
+    let treasury_key = treasury.key();
+
+    let mut previous: Option<(u64, [u8; 32])> = None;
+    let mut first_sequence = 0;
+    for audit_log_info in ctx.remaining_accounts.iter() {
+        let audit_log = Account::<AuditLog>::try_from(audit_log_info)?;
+        require!(audit_log.treasury == treasury_key, ErrorCode::AuditLogTreasuryMismatch);
+
+        // The entry's contents must still hash to its stored entry hash
+        require!(
+            audit_log.compute_entry_hash() == audit_log.entry_hash,
+            ErrorCode::AuditChainBroken
+        );
+
+        match previous {
+            // Each entry must directly follow the previous one and link to its hash
+            Some((sequence, entry_hash)) => {
+                require!(
+                    sequence.checked_add(1) == Some(audit_log.sequence)
+                        && audit_log.prev_hash == entry_hash,
+                    ErrorCode::AuditChainBroken
+                );
+            }
+            None => {
+                // The very first entry of a treasury links to the zero hash
+                if audit_log.sequence == 0 {
+                    require!(audit_log.prev_hash == [0; 32], ErrorCode::AuditChainBroken);
+                }
+                first_sequence = audit_log.sequence;
+            }
+        }
+
+        previous = Some((audit_log.sequence, audit_log.entry_hash));
+    }
+
+    let (last_sequence, last_entry_hash) = previous.ok_or(ErrorCode::EmptyAuditRange)?;
+
+    // A range ending at the latest entry must end at the treasury's chain head
+    let reaches_chain_head = last_sequence.checked_add(1) == Some(treasury.audit_sequence);
+    if reaches_chain_head {
+        require!(
+            last_entry_hash == treasury.audit_chain_head,
+            ErrorCode::AuditChainBroken
+        );
+    }
+
+    emit!(AuditRangeVerifiedEvent {
+        treasury: treasury_key,
+        first_sequence,
+        last_sequence,
+        last_entry_hash,
+        reaches_chain_head,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
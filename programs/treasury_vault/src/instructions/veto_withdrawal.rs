@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct VetoWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
+        bump = user.bump,
+        constraint = user.is_active @ ErrorCode::UnauthorizedUser,
+        constraint = user.has_permission(Permission::Configure) @ ErrorCode::UnauthorizedUser
+    )]
+    pub user: Account<'info, TreasuryUser>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PENDING_WITHDRAWAL_SEED,
+            treasury.key().as_ref(),
+            &pending_withdrawal.index.to_le_bytes()
+        ],
+        bump = pending_withdrawal.bump,
+        close = rent_receiver
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+    
+    /// Treasurer who queued the withdrawal and receives its rent
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == pending_withdrawal.requested_by @ ErrorCode::RentReceiverMismatch
+    )]
+    pub rent_receiver: SystemAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow admin users to veto a queued withdrawal before it is executed
+///
+/// Accounts:
+/// 0. `[writable, signer]` authority: The admin vetoing the withdrawal
+/// 1. `[writable]` treasury: The treasury account
+/// 2. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` pending_withdrawal: The pending withdrawal to close
+/// 4. `[writable]` rent_receiver: The treasurer who queued the withdrawal
+/// 5. `[writable]` audit_log: The audit log account to create
+/// 6. `[]` system_program: System program
+pub fn handler(
+    ctx: Context<VetoWithdrawal>,
+) -> Result<()> {
+    let treasury_key = ctx.accounts.treasury.key();
+    let pending_withdrawal = &ctx.accounts.pending_withdrawal;
+    let recipient = pending_withdrawal.recipient;
+    let amount = pending_withdrawal.amount;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Emit events
+    emit!(WithdrawalVetoedEvent {
+        treasury: treasury_key,
+        pending_withdrawal: pending_withdrawal.key(),
+        admin: ctx.accounts.authority.key(),
+        recipient: pending_withdrawal.recipient,
+        amount: pending_withdrawal.amount,
+        timestamp: current_time,
+    });
+    
+    emit!(TreasuryEvent {
+        action: AuditAction::WithdrawalVetoed as u8,
+        treasury: treasury_key,
+        initiator: ctx.accounts.authority.key(),
+        target: Some(pending_withdrawal.recipient),
+        amount: pending_withdrawal.amount,
+        timestamp: current_time,
+        token_mint: None, // SOL withdrawal
+    });
+    
+    // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.action = AuditAction::WithdrawalVetoed as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(recipient);
+    audit_log.amount = amount;
+    audit_log.balance_before = 0; // No funds move
+    audit_log.balance_after = 0;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = current_time; // No client-reported time for this action
+    audit_log.token_mint = None; // SOL withdrawal
+    audit_log.payout_schedule = None;
+    audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
+    
+    Ok(())
+}
\ No newline at end of file
@@ -6,15 +6,16 @@ use crate::{
     error::ErrorCode,
     events::*,
     state::*,
+    utils::{charge_recipient_limit, require_whitelisted_recipient},
 };
 
 #[derive(Accounts)]
-#[instruction(amount: u64, timestamp: i64)]
 pub struct Withdraw<'info> {
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
     )]
     pub treasury: Account<'info, Treasury>,
     
@@ -25,8 +26,7 @@ pub struct Withdraw<'info> {
         seeds = [
             AUDIT_SEED,
             treasury.key().as_ref(),
-            &timestamp.to_le_bytes(),
-            authority.key().as_ref()
+            &treasury.audit_sequence.to_le_bytes()
         ],
         bump
     )]
@@ -36,32 +36,45 @@ pub struct Withdraw<'info> {
     pub authority: Signer<'info>,
     
     #[account(
+        mut,
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Treasurer) @ ErrorCode::UnauthorizedWithdrawal
+        constraint = user.has_permission(Permission::Withdraw) @ ErrorCode::UnauthorizedWithdrawal
     )]
     pub user: Account<'info, TreasuryUser>,
     
     #[account(mut)]
     pub recipient: SystemAccount<'info>,
     
+    /// CHECK: Whitelist entry of the recipient, which may not exist. Its address
+    /// is pinned by the seeds so the whitelist policy and recipient limits always apply
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, recipient.key().as_ref(), treasury.key().as_ref()],
+        bump
+    )]
+    pub whitelisted_recipient: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
-/// Allow authorized users to withdraw funds from the treasury
+/// Allow authorized users to withdraw funds from the treasury. Withdrawals above
+/// the timelock threshold must go through `queue_withdrawal` instead. When the
+/// treasury requires it, the recipient must be an active whitelisted recipient.
 ///
 /// Accounts:
 /// 0. `[writable]` treasury: The treasury account
 /// 1. `[writable]` audit_log: The audit log account to create
 /// 2. `[writable, signer]` authority: The user initiating the withdrawal
-/// 3. `[readable]` user: The treasury user account of the authority
+/// 3. `[writable]` user: The treasury user account of the authority
 /// 4. `[writable]` recipient: The account receiving the withdrawn SOL
-/// 5. `[]` system_program: System program for transfers and account creation
+/// 5. `[writable]` whitelisted_recipient: The recipient's whitelist entry PDA, which may not exist
+/// 6. `[]` system_program: System program for transfers and account creation
 ///
 /// Data:
 /// - amount: [u64] The amount of SOL to withdraw in lamports
-/// - timestamp: [i64] The current timestamp
+/// - timestamp: [i64] The client-reported time, stored in the audit log next to the on-chain clock
 pub fn handler(
     ctx: Context<Withdraw>,
     amount: u64,
@@ -70,6 +83,21 @@ pub fn handler(
     // Validate inputs
     require!(amount > 0, ErrorCode::InvalidWithdrawAmount);
     
+    // Treasuries with an approval threshold must withdraw through proposals
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    // Large withdrawals must wait out the timelock through queue_withdrawal
+    require!(
+        !ctx.accounts.treasury.requires_timelock(amount),
+        ErrorCode::WithdrawalTimelockRequired
+    );
+    
+    // Only approved counterparties can receive funds when the policy is set
+    require_whitelisted_recipient(&ctx.accounts.treasury, &ctx.accounts.whitelisted_recipient)?;
+    
     // Validate timestamp is current or in the past
     let current_time = Clock::get()?.unix_timestamp;
     require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
@@ -85,12 +113,7 @@ pub fn handler(
 
     // Check if we need to reset the epoch
     let treasury = &mut ctx.accounts.treasury;
-    let previous_epoch_spending = treasury.epoch_spending;
-    
-    if current_time - treasury.last_epoch_start > treasury.epoch_duration as i64 {
-        treasury.last_epoch_start = current_time;
-        treasury.epoch_spending = 0;
-        
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
         // Emit spending limit reset event
         emit!(SpendingLimitResetEvent {
             treasury: treasury_key,
@@ -111,19 +134,25 @@ pub fn handler(
         });
     }
 
-    // Check if withdrawal would exceed spending limit for the current epoch
-    let new_epoch_spending = treasury.epoch_spending.checked_add(amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
-        
-    require!(
-        new_epoch_spending <= treasury.spending_limit,
-        ErrorCode::SpendingLimitExceeded
-    );
+    // Charge the withdrawal against the spending limit
+    treasury.record_spending(amount, current_time)?;
 
+    // Charge the per-user and per-recipient limits on top of the treasury's
+    let epoch_duration = treasury.epoch_duration;
+    ctx.accounts.user.charge_spending(None, amount, current_time, epoch_duration)?;
+    charge_recipient_limit(
+        &ctx.accounts.whitelisted_recipient,
+        None,
+        amount,
+        current_time,
+        epoch_duration,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    let balance_before = treasury.total_funds;
     // Update treasury state
     treasury.total_funds = treasury.total_funds.checked_sub(amount)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
-    treasury.epoch_spending = new_epoch_spending;
 
     // Transfer SOL from treasury to recipient
     // For PDA accounts, we need to use a different approach
@@ -147,14 +176,24 @@ pub fn handler(
         .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     // Create audit log entry
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = ctx.accounts.treasury.total_funds;
     let audit_log = &mut ctx.accounts.audit_log;
     audit_log.action = AuditAction::Withdraw as u8;
     audit_log.treasury = treasury_key;
     audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(ctx.accounts.recipient.key());
     audit_log.amount = amount;
-    audit_log.timestamp = timestamp;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
     audit_log.token_mint = None; // SOL withdrawal
+    audit_log.payout_schedule = None;
     audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
 
     // Emit event
     emit!(WithdrawEvent {
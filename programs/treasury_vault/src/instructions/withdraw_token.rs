@@ -1,32 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::*,
     error::ErrorCode,
     events::*,
     state::*,
+    utils::{charge_recipient_limit, require_whitelisted_recipient, transfer_tokens_checked},
 };
 
 #[derive(Accounts)]
-#[instruction(amount: u64, timestamp: i64)]
 pub struct WithdrawToken<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [TREASURY_SEED],
+        seeds = [TREASURY_SEED, treasury.creator.as_ref(), &treasury.treasury_id.to_le_bytes()],
         bump = treasury.bump,
-        constraint = !treasury.is_paused @ ErrorCode::TreasuryPaused
+        constraint = !treasury.is_paused_at(Clock::get()?.unix_timestamp) @ ErrorCode::TreasuryPaused
     )]
     pub treasury: Account<'info, Treasury>,
     
     #[account(
+        mut,
         seeds = [USER_SEED, authority.key().as_ref(), treasury.key().as_ref()],
         bump = user.bump,
         constraint = user.is_active @ ErrorCode::UnauthorizedUser,
-        constraint = user.has_permission(Role::Treasurer) @ ErrorCode::UnauthorizedWithdrawal
+        constraint = user.has_permission(Permission::Withdraw) @ ErrorCode::UnauthorizedWithdrawal
     )]
     pub user: Account<'info, TreasuryUser>,
     
@@ -37,47 +38,61 @@ pub struct WithdrawToken<'info> {
     )]
     pub token_balance: Account<'info, TokenBalance>,
     
-    /// CHECK: This is validated in the handler
     #[account(mut)]
-    pub treasury_token_account: AccountInfo<'info>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This is validated in the handler
     #[account(mut)]
-    pub recipient_token_account: AccountInfo<'info>,
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This is validated in the handler
-    pub token_mint: AccountInfo<'info>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     /// CHECK: This is the recipient's wallet that will receive the funds
     pub recipient: UncheckedAccount<'info>,
     
+    /// CHECK: Whitelist entry of the recipient, which may not exist. Its address
+    /// is pinned by the seeds so the whitelist policy and recipient limits always apply
+    #[account(
+        mut,
+        seeds = [RECIPIENT_SEED, recipient.key().as_ref(), treasury.key().as_ref()],
+        bump
+    )]
+    pub whitelisted_recipient: UncheckedAccount<'info>,
+    
     #[account(
         init,
         payer = authority,
         space = 8 + AuditLog::INIT_SPACE,
         seeds = [
-            AUDIT_SEED, 
-            treasury.key().as_ref(), 
-            &timestamp.to_le_bytes(), 
-            authority.key().as_ref()
+            AUDIT_SEED,
+            treasury.key().as_ref(),
+            &treasury.audit_sequence.to_le_bytes()
         ],
         bump
     )]
     pub audit_log: Account<'info, AuditLog>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
-    ctx: Context<WithdrawToken>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawToken<'info>>,
     amount: u64,
     timestamp: i64,
 ) -> Result<()> {
     // Validate inputs
     require!(amount > 0, ErrorCode::InvalidWithdrawAmount);
     
+    // Treasuries with an approval threshold must withdraw through proposals
+    require!(
+        !ctx.accounts.treasury.requires_multisig(),
+        ErrorCode::MultisigApprovalRequired
+    );
+    
+    // Only approved counterparties can receive funds when the policy is set
+    require_whitelisted_recipient(&ctx.accounts.treasury, &ctx.accounts.whitelisted_recipient)?;
+    
     // Validate timestamp is current or in the past
     let current_time = Clock::get()?.unix_timestamp;
     require!(timestamp <= current_time, ErrorCode::InvalidTimestamp);
@@ -85,6 +100,8 @@ pub fn handler(
     // Get treasury and token data
     let treasury_key = ctx.accounts.treasury.key();
     let treasury_bump = ctx.accounts.treasury.bump;
+    let treasury_creator = ctx.accounts.treasury.creator;
+    let treasury_id_bytes = ctx.accounts.treasury.treasury_id.to_le_bytes();
     let token_mint_key = ctx.accounts.token_mint.key();
     let recipient_key = ctx.accounts.recipient.key();
     
@@ -96,39 +113,34 @@ pub fn handler(
     // Validate token accounts
     // Check treasury token account
     let treasury_token_account = &ctx.accounts.treasury_token_account;
-    let treasury_token_account_data = token::TokenAccount::try_deserialize(&mut &treasury_token_account.data.borrow()[..])?;
     
     require!(
-        treasury_token_account_data.owner == treasury_key,
+        treasury_token_account.owner == treasury_key,
         ErrorCode::InvalidTokenAccountOwner
     );
     
     require!(
-        treasury_token_account_data.mint == token_mint_key,
+        treasury_token_account.mint == token_mint_key,
         ErrorCode::InvalidTokenMint
     );
     
     // Check recipient token account
     let recipient_token_account = &ctx.accounts.recipient_token_account;
-    let recipient_token_account_data = token::TokenAccount::try_deserialize(&mut &recipient_token_account.data.borrow()[..])?;
     
     require!(
-        recipient_token_account_data.owner == recipient_key,
+        recipient_token_account.owner == recipient_key,
         ErrorCode::InvalidTokenAccountOwner
     );
     
     require!(
-        recipient_token_account_data.mint == token_mint_key,
+        recipient_token_account.mint == token_mint_key,
         ErrorCode::InvalidTokenMint
     );
     
-    // Check if we need to reset the epoch
-    let previous_epoch_spending = token_balance.epoch_spending;
-    
-    if current_time - ctx.accounts.treasury.last_epoch_start > ctx.accounts.treasury.epoch_duration as i64 {
-        ctx.accounts.treasury.last_epoch_start = current_time;
-        token_balance.epoch_spending = 0;
-        
+    // Start a new epoch for this mint if the current one has elapsed
+    let epoch_duration = ctx.accounts.treasury.epoch_duration;
+    let rolling = ctx.accounts.treasury.uses_rolling_window();
+    if let Some(previous_epoch_spending) = token_balance.roll_epoch(current_time, epoch_duration) {
         // Emit spending limit reset event
         emit!(SpendingLimitResetEvent {
             treasury: treasury_key,
@@ -149,51 +161,59 @@ pub fn handler(
         });
     }
     
-    // Check if withdrawal would exceed spending limit for the current epoch
-    let new_epoch_spending = token_balance.epoch_spending
-        .checked_add(amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
-        
-    require!(
-        new_epoch_spending <= ctx.accounts.treasury.spending_limit,
-        ErrorCode::SpendingLimitExceeded
-    );
+    let balance_before = token_balance.balance;
+    // Charge the withdrawal against the mint's balance and spending limit
+    token_balance.record_outflow(amount, current_time, epoch_duration, rolling)?;
     
-    // Update token balance state
-    token_balance.epoch_spending = new_epoch_spending;
-    token_balance.balance = token_balance.balance
-        .checked_sub(amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    // Charge the per-user and per-recipient limits on top of the mint's
+    ctx.accounts.user.charge_spending(Some(token_mint_key), amount, current_time, epoch_duration)?;
+    charge_recipient_limit(
+        &ctx.accounts.whitelisted_recipient,
+        Some(token_mint_key),
+        amount,
+        current_time,
+        epoch_duration,
+    )?;
     
     // Transfer tokens from treasury to recipient
     let treasury_seeds = &[
         TREASURY_SEED,
+        treasury_creator.as_ref(),
+        &treasury_id_bytes,
         &[treasury_bump],
     ];
     let treasury_signer = &[&treasury_seeds[..]];
     
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.treasury_token_account.to_account_info(),
-                to: ctx.accounts.recipient_token_account.to_account_info(),
-                authority: ctx.accounts.treasury.to_account_info(),
-            },
-            treasury_signer,
-        ),
+    transfer_tokens_checked(
+        &ctx.accounts.token_program,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        ctx.remaining_accounts,
         amount,
+        treasury_signer,
     )?;
     
     // Create audit log
+    let audit_sequence = ctx.accounts.treasury.next_audit_sequence()?;
+    let balance_after = ctx.accounts.token_balance.balance;
     let audit_log = &mut ctx.accounts.audit_log;
     audit_log.action = AuditAction::Withdraw as u8;
     audit_log.treasury = treasury_key;
     audit_log.initiator = ctx.accounts.authority.key();
+    audit_log.target = Some(recipient_key);
     audit_log.amount = amount;
-    audit_log.timestamp = timestamp;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = balance_after;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = timestamp;
     audit_log.token_mint = Some(token_mint_key);
+    audit_log.payout_schedule = None;
     audit_log.bump = ctx.bumps.audit_log;
+    ctx.accounts.treasury.append_audit_entry(audit_log);
     
     // Emit events
     emit!(WithdrawEvent {
@@ -5,6 +5,7 @@ mod error;
 mod events;
 mod instructions;
 mod state;
+mod utils;
 
 pub use constants::*;
 pub use error::*;
@@ -20,10 +21,11 @@ pub mod treasury_vault {
 
     pub fn initialize_treasury(
         ctx: Context<InitializeTreasury>,
+        treasury_id: u64,
         epoch_duration: u64,
         spending_limit: u64,
     ) -> Result<()> {
-        instructions::initialize_treasury::handler(ctx, epoch_duration, spending_limit)
+        instructions::initialize_treasury::handler(ctx, treasury_id, epoch_duration, spending_limit)
     }
 
     pub fn deposit(
@@ -34,8 +36,8 @@ pub mod treasury_vault {
         instructions::deposit::handler(ctx, amount, timestamp)
     }
     
-    pub fn deposit_token(
-        ctx: Context<DepositToken>,
+    pub fn deposit_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositToken<'info>>,
         amount: u64,
         timestamp: i64,
     ) -> Result<()> {
@@ -50,8 +52,8 @@ pub mod treasury_vault {
         instructions::withdraw::handler(ctx, amount, timestamp)
     }
     
-    pub fn withdraw_token(
-        ctx: Context<WithdrawToken>,
+    pub fn withdraw_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawToken<'info>>,
         amount: u64,
         timestamp: i64,
     ) -> Result<()> {
@@ -60,10 +62,9 @@ pub mod treasury_vault {
 
     pub fn update_treasury_config(
         ctx: Context<UpdateTreasuryConfig>,
-        epoch_duration: Option<u64>,
-        spending_limit: Option<u64>,
+        config: TreasuryConfigUpdate,
     ) -> Result<()> {
-        instructions::update_treasury_config::handler(ctx, epoch_duration, spending_limit)
+        instructions::update_treasury_config::handler(ctx, config)
     }
     
     pub fn add_treasury_user(
@@ -73,6 +74,43 @@ pub mod treasury_vault {
         instructions::add_treasury_user::handler(ctx, role)
     }
     
+    pub fn deactivate_treasury_user(
+        ctx: Context<DeactivateTreasuryUser>,
+    ) -> Result<()> {
+        instructions::deactivate_treasury_user::handler(ctx)
+    }
+    
+    pub fn reactivate_treasury_user(
+        ctx: Context<ReactivateTreasuryUser>,
+    ) -> Result<()> {
+        instructions::reactivate_treasury_user::handler(ctx)
+    }
+    
+    pub fn update_user_role(
+        ctx: Context<UpdateUserRole>,
+        role: u8,
+    ) -> Result<()> {
+        instructions::update_user_role::handler(ctx, role)
+    }
+    
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+    ) -> Result<()> {
+        instructions::propose_admin::handler(ctx)
+    }
+    
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>,
+    ) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+    
+    pub fn cancel_admin_transfer(
+        ctx: Context<CancelAdminTransfer>,
+    ) -> Result<()> {
+        instructions::cancel_admin_transfer::handler(ctx)
+    }
+    
     pub fn add_whitelisted_recipient(
         ctx: Context<AddWhitelistedRecipient>,
         name: String,
@@ -80,6 +118,20 @@ pub mod treasury_vault {
         instructions::add_whitelisted_recipient::handler(ctx, name)
     }
     
+    pub fn update_whitelisted_recipient(
+        ctx: Context<UpdateWhitelistedRecipient>,
+        name: Option<String>,
+        is_active: Option<bool>,
+    ) -> Result<()> {
+        instructions::update_whitelisted_recipient::handler(ctx, name, is_active)
+    }
+    
+    pub fn remove_whitelisted_recipient<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveWhitelistedRecipient<'info>>,
+    ) -> Result<()> {
+        instructions::remove_whitelisted_recipient::handler(ctx)
+    }
+    
     pub fn schedule_payout(
         ctx: Context<SchedulePayout>,
         amount: u64,
@@ -87,8 +139,17 @@ pub mod treasury_vault {
         recurring: bool,
         recurrence_interval: u64,
         index: u64,
+        options: PayoutOptions,
     ) -> Result<()> {
-        instructions::schedule_payout::handler(ctx, amount, schedule_time, recurring, recurrence_interval, index)
+        instructions::schedule_payout::handler(
+            ctx,
+            amount,
+            schedule_time,
+            recurring,
+            recurrence_interval,
+            index,
+            options,
+        )
     }
     
     pub fn execute_payout(
@@ -98,8 +159,8 @@ pub mod treasury_vault {
         instructions::execute_payout::handler(ctx, timestamp)
     }
     
-    pub fn execute_token_payout(
-        ctx: Context<ExecuteTokenPayout>,
+    pub fn execute_token_payout<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTokenPayout<'info>>,
         timestamp: i64,
     ) -> Result<()> {
         instructions::execute_token_payout::handler(ctx, timestamp)
@@ -113,8 +174,9 @@ pub mod treasury_vault {
     
     pub fn pause_treasury(
         ctx: Context<PauseTreasury>,
+        duration: Option<u64>,
     ) -> Result<()> {
-        instructions::pause_treasury::handler(ctx)
+        instructions::pause_treasury::handler(ctx, duration)
     }
     
     pub fn unpause_treasury(
@@ -125,7 +187,199 @@ pub mod treasury_vault {
     
     pub fn set_token_gate(
         ctx: Context<SetTokenGate>,
+        min_balance: u64,
+        mode: u8,
+    ) -> Result<()> {
+        instructions::set_token_gate::handler(ctx, min_balance, mode)
+    }
+    
+    pub fn set_token_spending_limit(
+        ctx: Context<SetTokenSpendingLimit>,
+        spending_limit: u64,
+    ) -> Result<()> {
+        instructions::set_token_spending_limit::handler(ctx, spending_limit)
+    }
+    
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        action: u8,
+        params: ProposalParams,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_proposal::handler(ctx, action, params, expires_at)
+    }
+    
+    pub fn approve_proposal(
+        ctx: Context<ApproveProposal>,
+    ) -> Result<()> {
+        instructions::approve_proposal::handler(ctx)
+    }
+    
+    pub fn revoke_approval(
+        ctx: Context<RevokeApproval>,
+    ) -> Result<()> {
+        instructions::revoke_approval::handler(ctx)
+    }
+    
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::execute_proposal::handler(ctx, timestamp)
+    }
+    
+    pub fn create_vesting_stream(
+        ctx: Context<CreateVestingStream>,
+        total_amount: u64,
+        start_time: i64,
+        cliff_time: Option<i64>,
+        end_time: i64,
+        index: u64,
+    ) -> Result<()> {
+        instructions::create_vesting_stream::handler(
+            ctx,
+            total_amount,
+            start_time,
+            cliff_time,
+            end_time,
+            index,
+        )
+    }
+    
+    pub fn claim_vesting(
+        ctx: Context<ClaimVesting>,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::claim_vesting::handler(ctx, timestamp)
+    }
+    
+    pub fn claim_token_vesting<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimTokenVesting<'info>>,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::claim_token_vesting::handler(ctx, timestamp)
+    }
+    
+    pub fn cancel_vesting_stream(
+        ctx: Context<CancelVestingStream>,
+    ) -> Result<()> {
+        instructions::cancel_vesting_stream::handler(ctx)
+    }
+    
+    pub fn claim_payout<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimPayout<'info>>,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::claim_payout::handler(ctx, timestamp)
+    }
+    
+    pub fn close_payout_schedule(
+        ctx: Context<ClosePayoutSchedule>,
+    ) -> Result<()> {
+        instructions::close_payout_schedule::handler(ctx)
+    }
+    
+    pub fn close_audit_log(
+        ctx: Context<CloseAuditLog>,
+    ) -> Result<()> {
+        instructions::close_audit_log::handler(ctx)
+    }
+    
+    pub fn verify_audit_range<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyAuditRange<'info>>,
+    ) -> Result<()> {
+        instructions::verify_audit_range::handler(ctx)
+    }
+    
+    pub fn set_withdrawal_timelock(
+        ctx: Context<SetWithdrawalTimelock>,
+        threshold: u64,
+        delay: u64,
+    ) -> Result<()> {
+        instructions::set_withdrawal_timelock::handler(ctx, threshold, delay)
+    }
+    
+    pub fn queue_withdrawal(
+        ctx: Context<QueueWithdrawal>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::queue_withdrawal::handler(ctx, amount)
+    }
+    
+    pub fn execute_withdrawal(
+        ctx: Context<ExecuteWithdrawal>,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::execute_withdrawal::handler(ctx, timestamp)
+    }
+    
+    pub fn veto_withdrawal(
+        ctx: Context<VetoWithdrawal>,
+    ) -> Result<()> {
+        instructions::veto_withdrawal::handler(ctx)
+    }
+    
+    pub fn set_user_permissions(
+        ctx: Context<SetUserPermissions>,
+        permissions: u16,
+    ) -> Result<()> {
+        instructions::set_user_permissions::handler(ctx, permissions)
+    }
+    
+    pub fn set_user_spending_limit(
+        ctx: Context<SetUserSpendingLimit>,
+        token_mint: Option<Pubkey>,
+        limit: Option<u64>,
+    ) -> Result<()> {
+        instructions::set_user_spending_limit::handler(ctx, token_mint, limit)
+    }
+    
+    pub fn set_recipient_spending_limit(
+        ctx: Context<SetRecipientSpendingLimit>,
+        token_mint: Option<Pubkey>,
+        limit: Option<u64>,
+    ) -> Result<()> {
+        instructions::set_recipient_spending_limit::handler(ctx, token_mint, limit)
+    }
+    
+    pub fn set_withdrawal_whitelist(
+        ctx: Context<SetWithdrawalWhitelist>,
+        required: bool,
+    ) -> Result<()> {
+        instructions::set_withdrawal_whitelist::handler(ctx, required)
+    }
+    
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        claim_count: u32,
+        expires_at: i64,
+        index: u64,
+    ) -> Result<()> {
+        instructions::create_distribution::handler(
+            ctx,
+            merkle_root,
+            total_amount,
+            claim_count,
+            expires_at,
+            index,
+        )
+    }
+    
+    pub fn claim_distribution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimDistribution<'info>>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::claim_distribution::handler(ctx, index, amount, proof, timestamp)
+    }
+    
+    pub fn reclaim_distribution(
+        ctx: Context<ReclaimDistribution>,
     ) -> Result<()> {
-        instructions::set_token_gate::handler(ctx)
+        instructions::reclaim_distribution::handler(ctx)
     }
 }
\ No newline at end of file
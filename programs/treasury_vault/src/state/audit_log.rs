@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 
 #[account]
 #[derive(InitSpace)]
@@ -6,12 +7,67 @@ pub struct AuditLog {
     pub action: u8,                   // 1 byte
     pub treasury: Pubkey,             // 32 bytes
     pub initiator: Pubkey,            // 32 bytes
+    pub target: Option<Pubkey>,       // 33 bytes - Counterparty receiving funds, None for deposits
     pub amount: u64,                  // 8 bytes
-    pub timestamp: i64,               // 8 bytes
+    pub balance_before: u64,          // 8 bytes - Treasury balance of the affected asset before the action, zero if no funds move
+    pub balance_after: u64,           // 8 bytes - Treasury balance of the affected asset after the action, zero if no funds move
+    pub sequence: u64,                // 8 bytes - Position in the treasury's audit log, part of the PDA seeds
+    pub previous_sequence: Option<u64>, // 9 bytes - Sequence of the preceding entry, None for the first one
+    pub timestamp: i64,               // 8 bytes - On-chain clock time of the action
+    pub client_timestamp: i64,        // 8 bytes - Time reported by the client
     pub token_mint: Option<Pubkey>,   // 33 bytes (1 for Option + 32 for Pubkey)
+    pub payout_schedule: Option<Pubkey>, // 33 bytes - Payout schedule that produced the action, if any
+    pub prev_hash: [u8; 32],          // 32 bytes - Entry hash of the preceding entry, zero for the first one
+    pub entry_hash: [u8; 32],         // 32 bytes - Hash of this entry chained onto prev_hash
     pub bump: u8,                     // 1 byte
 }
 
 impl AuditLog {
-    pub const INIT_SPACE: usize = 1 + 32 + 32 + 8 + 8 + 33 + 1;
+    pub const INIT_SPACE: usize = 1 + 32 + 32 + 33 + 8 + 8 + 8 + 8 + 9 + 8 + 8 + 33 + 33 + 32 + 32 + 1;
+    
+    /// Hash of this entry's contents chained onto `prev_hash`
+    pub fn compute_entry_hash(&self) -> [u8; 32] {
+        let target = self.target.unwrap_or_default();
+        let token_mint = self.token_mint.unwrap_or_default();
+        let payout_schedule = self.payout_schedule.unwrap_or_default();
+        hashv(&[
+            &self.prev_hash,
+            self.treasury.as_ref(),
+            &self.sequence.to_le_bytes(),
+            &[self.action],
+            &self.amount.to_le_bytes(),
+            &self.balance_before.to_le_bytes(),
+            &self.balance_after.to_le_bytes(),
+            self.initiator.as_ref(),
+            &[self.target.is_some() as u8],
+            target.as_ref(),
+            &[self.token_mint.is_some() as u8],
+            token_mint.as_ref(),
+            &[self.payout_schedule.is_some() as u8],
+            payout_schedule.as_ref(),
+            &self.timestamp.to_le_bytes(),
+            &self.client_timestamp.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+    
+    /// Compact hash commitment of this entry, folded into the treasury's
+    /// audit digest before the account is closed
+    pub fn commitment(&self, audit_log: &Pubkey) -> [u8; 32] {
+        let token_mint = self.token_mint.unwrap_or_default();
+        hashv(&[
+            audit_log.as_ref(),
+            &[self.action],
+            self.treasury.as_ref(),
+            self.initiator.as_ref(),
+            &self.amount.to_le_bytes(),
+            &self.sequence.to_le_bytes(),
+            &self.timestamp.to_le_bytes(),
+            &self.client_timestamp.to_le_bytes(),
+            &[self.token_mint.is_some() as u8],
+            token_mint.as_ref(),
+            &self.entry_hash,
+        ])
+        .to_bytes()
+    }
 }
\ No newline at end of file
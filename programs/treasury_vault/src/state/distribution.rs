@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::{constants::MAX_DISTRIBUTION_CLAIMS, error::ErrorCode};
+
+// Domain separation between leaves and inner nodes of a distribution merkle tree
+const MERKLE_LEAF_PREFIX: u8 = 0;
+const MERKLE_NODE_PREFIX: u8 = 1;
+
+/// Treasury funds reserved for many claimants at once, committed to by the
+/// merkle root of `(index, recipient, mint, amount)` leaves
+#[account]
+#[derive(InitSpace)]
+pub struct Distribution {
+    pub treasury: Pubkey,             // 32 bytes
+    pub token_mint: Option<Pubkey>,   // 33 bytes (1 for Option + 32 for Pubkey), None for SOL
+    pub merkle_root: [u8; 32],        // 32 bytes - Root of the claim leaves
+    pub total_amount: u64,            // 8 bytes - Amount reserved for the distribution
+    pub claimed_amount: u64,          // 8 bytes - Amount already claimed
+    pub claim_count: u32,             // 4 bytes - Number of leaves, claim indexes are below this
+    pub expires_at: i64,              // 8 bytes - Claims close and the rest can be reclaimed from here
+    pub created_by: Pubkey,           // 32 bytes
+    pub index: u64,                   // 8 bytes
+    pub claimed_bitmap: [u8; MAX_DISTRIBUTION_CLAIMS / 8], // MAX_DISTRIBUTION_CLAIMS / 8 bytes - One bit per claimed leaf
+    pub bump: u8,                     // 1 byte
+}
+
+impl Distribution {
+    pub const INIT_SPACE: usize = 32 + 33 + 32 + 8 + 8 + 4 + 8 + 32 + 8 + MAX_DISTRIBUTION_CLAIMS / 8 + 1;
+
+    /// Hash of a claim leaf. SOL distributions use the default pubkey as the mint.
+    pub fn leaf_hash(index: u32, recipient: &Pubkey, token_mint: Option<Pubkey>, amount: u64) -> [u8; 32] {
+        hashv(&[
+            &[MERKLE_LEAF_PREFIX],
+            &index.to_le_bytes(),
+            recipient.as_ref(),
+            token_mint.unwrap_or_default().as_ref(),
+            &amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Whether the proof links the leaf to the merkle root. Sibling pairs are
+    /// hashed in sorted order, so the proof needs no left/right flags.
+    pub fn verify_proof(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            let (first, second) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            hashv(&[&[MERKLE_NODE_PREFIX], &first, &second]).to_bytes()
+        });
+        computed == self.merkle_root
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        let index = index as usize;
+        self.claimed_bitmap[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Marks the leaf as claimed and adds its amount to the claimed total
+    pub fn record_claim(&mut self, index: u32, amount: u64) -> Result<()> {
+        require!(index < self.claim_count, ErrorCode::InvalidDistributionIndex);
+        require!(!self.is_claimed(index), ErrorCode::DistributionAlreadyClaimed);
+
+        let claimed_amount = self.claimed_amount.checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(claimed_amount <= self.total_amount, ErrorCode::DistributionExhausted);
+
+        let index = index as usize;
+        self.claimed_bitmap[index / 8] |= 1 << (index % 8);
+        self.claimed_amount = claimed_amount;
+        Ok(())
+    }
+
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expires_at
+    }
+
+    /// Reserved funds not claimed so far
+    pub fn unclaimed_amount(&self) -> u64 {
+        self.total_amount.saturating_sub(self.claimed_amount)
+    }
+}
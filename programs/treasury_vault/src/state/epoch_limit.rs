@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_EPOCH_LIMITS, error::ErrorCode};
+
+/// Per-mint spending cap of a treasury user or whitelisted recipient,
+/// on top of the treasury-wide spending limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct EpochLimit {
+    pub token_mint: Option<Pubkey>,   // 33 bytes - None for SOL
+    pub limit: u64,                   // 8 bytes - Max outflow per epoch
+    pub epoch_spending: u64,          // 8 bytes - Amount spent in current epoch
+    pub last_epoch_start: i64,        // 8 bytes - Start of this limit's current epoch
+}
+
+impl EpochLimit {
+    pub const INIT_SPACE: usize = 33 + 8 + 8 + 8;
+}
+
+/// Charges an outflow against the limit of `token_mint` in `limits`, if there is one.
+/// Each limit starts a new epoch once the treasury's epoch duration has elapsed.
+pub fn charge_epoch_limit(
+    limits: &mut [EpochLimit],
+    token_mint: Option<Pubkey>,
+    amount: u64,
+    current_time: i64,
+    epoch_duration: u64,
+    exceeded: ErrorCode,
+) -> Result<()> {
+    let Some(limit) = limits.iter_mut().find(|limit| limit.token_mint == token_mint) else {
+        return Ok(());
+    };
+    
+    if current_time - limit.last_epoch_start > epoch_duration as i64 {
+        limit.last_epoch_start = current_time;
+        limit.epoch_spending = 0;
+    }
+    
+    let new_epoch_spending = limit.epoch_spending
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if new_epoch_spending > limit.limit {
+        return Err(exceeded.into());
+    }
+    
+    limit.epoch_spending = new_epoch_spending;
+    Ok(())
+}
+
+/// Sets the limit of `token_mint` in `limits`, or removes it when `limit` is None.
+/// Spending already recorded in the current epoch is kept. Returns the old limit.
+pub fn set_epoch_limit(
+    limits: &mut Vec<EpochLimit>,
+    token_mint: Option<Pubkey>,
+    limit: Option<u64>,
+    current_time: i64,
+) -> Result<Option<u64>> {
+    let position = limits.iter().position(|entry| entry.token_mint == token_mint);
+    let old_limit = position.map(|index| limits[index].limit);
+    
+    match (position, limit) {
+        (Some(index), Some(limit)) => limits[index].limit = limit,
+        (Some(index), None) => {
+            limits.remove(index);
+        }
+        (None, Some(limit)) => {
+            require!(limits.len() < MAX_EPOCH_LIMITS, ErrorCode::TooManyEpochLimits);
+            limits.push(EpochLimit {
+                token_mint,
+                limit,
+                epoch_spending: 0,
+                last_epoch_start: current_time,
+            });
+        }
+        (None, None) => {}
+    }
+    
+    Ok(old_limit)
+}
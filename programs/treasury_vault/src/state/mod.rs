@@ -4,10 +4,22 @@ pub mod treasury_user;
 pub mod whitelisted_recipient;
 pub mod payout_schedule;
 pub mod token_balance;
+pub mod proposal;
+pub mod vesting_stream;
+pub mod pending_withdrawal;
+pub mod epoch_limit;
+pub mod rolling_window;
+pub mod distribution;
 
 pub use audit_log::*;
 pub use treasury::*;
 pub use treasury_user::*;
 pub use whitelisted_recipient::*;
 pub use payout_schedule::*;
-pub use token_balance::*;
\ No newline at end of file
+pub use token_balance::*;
+pub use proposal::*;
+pub use vesting_stream::*;
+pub use pending_withdrawal::*;
+pub use epoch_limit::*;
+pub use rolling_window::*;
+pub use distribution::*;
\ No newline at end of file
@@ -1,5 +1,33 @@
 use anchor_lang::prelude::*;
 
+use crate::{constants::MAX_CATCH_UP_PERIODS, error::ErrorCode};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CatchUpPolicy {
+    PayAll = 0,         // Settle every missed period, bounded per call
+    PayOnceAndSkip = 1, // Settle one period and skip the rest
+    Drift = 2,          // Settle one period and restart the interval from the execution time
+}
+
+impl CatchUpPolicy {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(CatchUpPolicy::PayAll),
+            1 => Some(CatchUpPolicy::PayOnceAndSkip),
+            2 => Some(CatchUpPolicy::Drift),
+            _ => None,
+        }
+    }
+}
+
+/// Optional bounds and catch-up behavior of a new payout schedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq)]
+pub struct PayoutOptions {
+    pub end_time: Option<i64>,        // No periods are paid after this time
+    pub max_executions: Option<u32>,  // Cap on the number of periods paid
+    pub catch_up_policy: Option<u8>,  // CatchUpPolicy for missed periods, PayAll if None
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct PayoutSchedule {
@@ -14,29 +42,100 @@ pub struct PayoutSchedule {
     pub treasury: Pubkey,             // 32 bytes
     pub index: u64,                   // 8 bytes
     pub token_mint: Option<Pubkey>,   // 33 bytes (1 for Option + 32 for Pubkey)
+    pub end_time: Option<i64>,        // 9 bytes - No periods are paid after this time
+    pub max_executions: Option<u32>,  // 5 bytes - Cap on the number of periods paid
+    pub execution_count: u32,         // 4 bytes - Periods paid so far
+    pub catch_up_policy: u8,          // 1 byte - CatchUpPolicy for missed periods
+    pub next_due_time: i64,           // 8 bytes - Start of the next unpaid period
     pub bump: u8,                     // 1 byte
 }
 
 impl PayoutSchedule {
-    pub const INIT_SPACE: usize = 32 + 8 + 8 + 1 + 8 + 8 + 1 + 32 + 32 + 8 + 33 + 1;
+    pub const INIT_SPACE: usize = 32 + 8 + 8 + 1 + 8 + 8 + 1 + 32 + 32 + 8 + 33 + 9 + 5 + 4 + 1 + 8 + 1;
     
     pub fn is_due(&self, current_time: i64) -> bool {
         if !self.is_active {
             return false;
         }
         
+        if self.end_time.is_some_and(|end_time| self.next_due_time > end_time) {
+            return false;
+        }
+        
+        if self.max_executions.is_some_and(|max| self.execution_count >= max) {
+            return false;
+        }
+        
         if self.recurring {
-            // For recurring payouts, check if enough time has passed since last execution
-            if self.last_executed == 0 {
-                // First execution
-                return current_time >= self.schedule_time;
-            } else {
-                // Check if recurrence_interval has passed since last execution
-                return current_time >= (self.last_executed + self.recurrence_interval as i64);
-            }
+            // For recurring payouts, check if the next period has started
+            current_time >= self.next_due_time
         } else {
             // For one-time payouts, check if it's time and hasn't been executed yet
-            return current_time >= self.schedule_time && self.last_executed == 0;
+            current_time >= self.schedule_time && self.last_executed == 0
         }
     }
-}
\ No newline at end of file
+    
+    /// Number of periods the next execution settles under the catch-up policy
+    pub fn periods_to_settle(&self, current_time: i64) -> u32 {
+        if !self.is_due(current_time) {
+            return 0;
+        }
+        
+        if !self.recurring || self.catch_up_policy != CatchUpPolicy::PayAll as u8 {
+            return 1;
+        }
+        
+        let interval = self.recurrence_interval as i64;
+        let mut periods = ((current_time - self.next_due_time) / interval + 1) as u64;
+        periods = periods.min(MAX_CATCH_UP_PERIODS);
+        
+        // Never pay periods starting after the end time
+        if let Some(end_time) = self.end_time {
+            periods = periods.min(((end_time - self.next_due_time) / interval + 1) as u64);
+        }
+        
+        // Never pay more periods than the execution cap allows
+        if let Some(max) = self.max_executions {
+            periods = periods.min(max.saturating_sub(self.execution_count) as u64);
+        }
+        
+        periods as u32
+    }
+    
+    /// Records an execution settling the given number of periods and deactivates
+    /// the schedule after its final payment. Returns true when the schedule has completed.
+    pub fn record_execution(&mut self, current_time: i64, periods: u32) -> Result<bool> {
+        self.last_executed = current_time;
+        self.execution_count = self.execution_count.checked_add(periods)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        
+        if self.recurring {
+            let interval = self.recurrence_interval as i64;
+            self.next_due_time = match CatchUpPolicy::from_u8(self.catch_up_policy) {
+                Some(CatchUpPolicy::PayAll) => self.next_due_time
+                    .checked_add(interval.checked_mul(periods as i64).ok_or(ErrorCode::ArithmeticOverflow)?)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?,
+                Some(CatchUpPolicy::PayOnceAndSkip) => {
+                    // Move to the first period starting after now
+                    let elapsed_periods = (current_time - self.next_due_time) / interval + 1;
+                    self.next_due_time
+                        .checked_add(interval.checked_mul(elapsed_periods).ok_or(ErrorCode::ArithmeticOverflow)?)
+                        .ok_or(ErrorCode::ArithmeticOverflow)?
+                }
+                Some(CatchUpPolicy::Drift) => current_time
+                    .checked_add(interval)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?,
+                None => return Err(ErrorCode::InvalidCatchUpPolicy.into()),
+            };
+        }
+        
+        let completed = !self.recurring
+            || self.max_executions.is_some_and(|max| self.execution_count >= max)
+            || self.end_time.is_some_and(|end_time| self.next_due_time > end_time);
+        
+        if completed {
+            self.is_active = false;
+        }
+        Ok(completed)
+    }
+}
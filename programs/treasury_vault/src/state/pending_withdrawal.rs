@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingWithdrawal {
+    pub treasury: Pubkey,             // 32 bytes
+    pub requested_by: Pubkey,         // 32 bytes - Treasurer who queued the withdrawal and paid its rent
+    pub recipient: Pubkey,            // 32 bytes - Account receiving the withdrawn SOL
+    pub amount: u64,                  // 8 bytes
+    pub index: u64,                   // 8 bytes - Part of the PDA seeds
+    pub queued_at: i64,               // 8 bytes
+    pub executable_at: i64,           // 8 bytes - Admins can veto the withdrawal until this time
+    pub bump: u8,                     // 1 byte
+}
+
+impl PendingWithdrawal {
+    pub const INIT_SPACE: usize = 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+    
+    pub fn is_executable(&self, current_time: i64) -> bool {
+        current_time >= self.executable_at
+    }
+}
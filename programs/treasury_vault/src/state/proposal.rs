@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_PROPOSAL_APPROVERS, state::TreasuryConfigUpdate};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ProposalAction {
+    SolWithdraw = 0,
+    TokenWithdraw = 1,
+    ConfigChange = 2,
+    TokenSpendingLimit = 3,
+    AddUser = 4,                    // Executed by add_treasury_user
+    UpdateUserRole = 5,             // Executed by update_user_role
+    SetUserPermissions = 6,         // Executed by set_user_permissions
+    DeactivateUser = 7,             // Executed by deactivate_treasury_user
+    ReactivateUser = 8,             // Executed by reactivate_treasury_user
+    SetUserSpendingLimit = 9,       // Executed by set_user_spending_limit
+    SetRecipientSpendingLimit = 10, // Executed by set_recipient_spending_limit
+}
+
+impl ProposalAction {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ProposalAction::SolWithdraw),
+            1 => Some(ProposalAction::TokenWithdraw),
+            2 => Some(ProposalAction::ConfigChange),
+            3 => Some(ProposalAction::TokenSpendingLimit),
+            4 => Some(ProposalAction::AddUser),
+            5 => Some(ProposalAction::UpdateUserRole),
+            6 => Some(ProposalAction::SetUserPermissions),
+            7 => Some(ProposalAction::DeactivateUser),
+            8 => Some(ProposalAction::ReactivateUser),
+            9 => Some(ProposalAction::SetUserSpendingLimit),
+            10 => Some(ProposalAction::SetRecipientSpendingLimit),
+            _ => None,
+        }
+    }
+}
+
+/// Payload of a new proposal. Which fields apply depends on its action.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq)]
+pub struct ProposalParams {
+    pub amount: u64,                  // Amount to withdraw, or a new per-user or per-recipient limit (0 removes it)
+    pub target: Option<Pubkey>,       // Recipient wallet for withdrawals, or the user or recipient to manage
+    pub token_mint: Option<Pubkey>,   // Mint for token withdrawals and per-mint limits
+    pub config: TreasuryConfigUpdate, // Settings for config changes, its spending_limit also sets per-mint limits
+    pub role: Option<u8>,             // Role for added users and role changes
+    pub permissions: Option<u16>,     // Permission bitmask for permission changes
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub treasury: Pubkey,                 // 32 bytes
+    pub proposer: Pubkey,                 // 32 bytes
+    pub index: u64,                       // 8 bytes
+    pub action: u8,                       // 1 byte - ProposalAction
+    pub target: Option<Pubkey>,           // 33 bytes - Recipient wallet for withdrawals, or the user to manage
+    pub token_mint: Option<Pubkey>,       // 33 bytes - Mint for token withdrawals
+    pub amount: u64,                      // 8 bytes
+    pub config: TreasuryConfigUpdate,     // 60 bytes - New settings for config changes and per-mint limits
+    pub role: Option<u8>,                 // 2 bytes - Role for user management
+    pub permissions: Option<u16>,         // 3 bytes - Permission bitmask for user management
+    #[max_len(MAX_PROPOSAL_APPROVERS)]
+    pub approvals: Vec<Pubkey>,           // 4 + 32 * MAX_PROPOSAL_APPROVERS bytes
+    pub created_at: i64,                  // 8 bytes
+    pub expires_at: i64,                  // 8 bytes
+    pub executed: bool,                   // 1 byte
+    pub bump: u8,                         // 1 byte
+}
+
+impl Proposal {
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1 + 33 + 33 + 8 + TreasuryConfigUpdate::INIT_SPACE
+        + 2 + 3 + 4 + 32 * MAX_PROPOSAL_APPROVERS + 8 + 8 + 1 + 1;
+
+    pub fn has_approved(&self, approver: &Pubkey) -> bool {
+        self.approvals.contains(approver)
+    }
+
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time > self.expires_at
+    }
+
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.len() as u8
+    }
+
+    /// Whether this proposal is for the given user management change
+    pub fn is_user_change(
+        &self,
+        action: ProposalAction,
+        user: Pubkey,
+        role: Option<u8>,
+        permissions: Option<u16>,
+    ) -> bool {
+        self.action == action as u8
+            && self.target == Some(user)
+            && self.role == role
+            && self.permissions == permissions
+    }
+    
+    /// Whether this proposal is for the given per-user or per-recipient limit
+    /// change. The limit is carried in `amount`, where 0 removes it.
+    pub fn is_spending_limit_change(
+        &self,
+        action: ProposalAction,
+        target: Pubkey,
+        token_mint: Option<Pubkey>,
+        limit: Option<u64>,
+    ) -> bool {
+        self.action == action as u8
+            && self.target == Some(target)
+            && self.token_mint == token_mint
+            && self.amount == limit.unwrap_or(0)
+    }
+}
\ No newline at end of file
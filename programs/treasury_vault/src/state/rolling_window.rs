@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::ROLLING_WINDOW_BUCKETS, error::ErrorCode};
+
+/// How the treasury enforces its spending limits
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SpendingLimitMode {
+    FixedEpoch = 0,    // Spending resets once the epoch has elapsed
+    Rolling = 1,       // Spending is capped over any trailing epoch duration
+}
+
+impl SpendingLimitMode {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(SpendingLimitMode::FixedEpoch),
+            1 => Some(SpendingLimitMode::Rolling),
+            _ => None,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct SpendingBucket {
+    pub index: i64,                   // 8 bytes - Time bucket the amount was spent in
+    pub amount: u64,                  // 8 bytes - Amount spent within the bucket
+}
+
+/// Ring buffer of time-bucketed spending, used to enforce a spending limit
+/// over a trailing window rather than fixed epochs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, InitSpace)]
+pub struct RollingWindow {
+    pub bucket_width: u64,            // 8 bytes - Seconds per bucket, 0 until first use
+    pub buckets: [SpendingBucket; ROLLING_WINDOW_BUCKETS], // 16 * ROLLING_WINDOW_BUCKETS bytes
+}
+
+impl RollingWindow {
+    pub const INIT_SPACE: usize = 8 + 16 * ROLLING_WINDOW_BUCKETS;
+    
+    /// Records an outflow, failing if it would take spending over the trailing
+    /// `window` seconds above `limit`. Pass no limit to only track spending.
+    pub fn record(
+        &mut self,
+        amount: u64,
+        current_time: i64,
+        window: u64,
+        limit: Option<u64>,
+    ) -> Result<()> {
+        // The buckets other than the current one must cover a full window, so
+        // nothing spent within the trailing window has been dropped yet
+        let bucket_width = window.div_ceil(ROLLING_WINDOW_BUCKETS as u64 - 1).max(1);
+        if self.bucket_width != bucket_width {
+            self.rebucket(current_time, bucket_width);
+        }
+        
+        let current_index = current_time / bucket_width as i64;
+        let spent = self.spent(current_index);
+        if let Some(limit) = limit {
+            let new_spent = spent.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+            require!(new_spent <= limit, ErrorCode::SpendingLimitExceeded);
+        }
+        
+        let bucket = &mut self.buckets[current_index.rem_euclid(ROLLING_WINDOW_BUCKETS as i64) as usize];
+        if bucket.index != current_index {
+            *bucket = SpendingBucket { index: current_index, amount: 0 };
+        }
+        bucket.amount = bucket.amount.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Total spent in the buckets still inside the window ending at `current_index`
+    fn spent(&self, current_index: i64) -> u64 {
+        let oldest_index = current_index - ROLLING_WINDOW_BUCKETS as i64;
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.index > oldest_index && bucket.index <= current_index)
+            .fold(0u64, |total, bucket| total.saturating_add(bucket.amount))
+    }
+    
+    /// Switches to a new bucket width after the epoch duration changed. Spending
+    /// still in the old window is kept in the current bucket so it can't be reused.
+    fn rebucket(&mut self, current_time: i64, bucket_width: u64) {
+        let carried = if self.bucket_width == 0 {
+            0
+        } else {
+            self.spent(current_time / self.bucket_width as i64)
+        };
+        
+        let current_index = current_time / bucket_width as i64;
+        self.buckets = [SpendingBucket::default(); ROLLING_WINDOW_BUCKETS];
+        self.buckets[current_index.rem_euclid(ROLLING_WINDOW_BUCKETS as i64) as usize] =
+            SpendingBucket { index: current_index, amount: carried };
+        self.bucket_width = bucket_width;
+    }
+}
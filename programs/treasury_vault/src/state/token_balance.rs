@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::ErrorCode, state::RollingWindow};
+
 #[account]
 #[derive(InitSpace)]
 pub struct TokenBalance {
@@ -7,19 +9,97 @@ pub struct TokenBalance {
     pub token_mint: Pubkey,     // 32 bytes - The SPL token mint
     pub balance: u64,           // 8 bytes - Current balance of this token
     pub epoch_spending: u64,    // 8 bytes - Amount spent in current epoch
+    pub spending_limit: u64,    // 8 bytes - Max outflow per epoch in the mint's base units
+    pub last_epoch_start: i64,  // 8 bytes - Start of this mint's current epoch
+    pub rolling_spending: RollingWindow, // 8 + 16 * ROLLING_WINDOW_BUCKETS bytes - Spending over the trailing epoch duration
     pub bump: u8,               // 1 byte
 }
 
 impl TokenBalance {
-    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 1;
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 8 + 8 + RollingWindow::INIT_SPACE + 1;
     
-    pub fn new(treasury: Pubkey, token_mint: Pubkey, bump: u8) -> Self {
+    /// A new mint starts with a zero limit, so its outflows stay disabled until
+    /// an admin sets one with `set_token_spending_limit`. The treasury's SOL limit
+    /// is in lamports and says nothing about the mint's base units.
+    pub fn new(treasury: Pubkey, token_mint: Pubkey, current_time: i64, bump: u8) -> Self {
         Self {
             treasury,
             token_mint,
             balance: 0,
             epoch_spending: 0,
+            spending_limit: 0,
+            last_epoch_start: current_time,
+            rolling_spending: RollingWindow::default(),
             bump,
         }
     }
-}
\ No newline at end of file
+    
+    /// Starts a new epoch for this mint if the current one has elapsed.
+    /// Returns the spending of the epoch that was closed, if any.
+    pub fn roll_epoch(&mut self, current_time: i64, epoch_duration: u64) -> Option<u64> {
+        if current_time - self.last_epoch_start <= epoch_duration as i64 {
+            return None;
+        }
+        
+        let previous_epoch_spending = self.epoch_spending;
+        self.last_epoch_start = current_time;
+        self.epoch_spending = 0;
+        Some(previous_epoch_spending)
+    }
+    
+    /// Records an outflow against this mint's balance and spending limit
+    pub fn record_outflow(
+        &mut self,
+        amount: u64,
+        current_time: i64,
+        epoch_duration: u64,
+        rolling: bool,
+    ) -> Result<()> {
+        require!(self.balance >= amount, ErrorCode::InsufficientTokenBalance);
+        
+        self.record_spending(amount, current_time, epoch_duration, rolling)?;
+        self.reserve(amount)
+    }
+    
+    /// Charges an outflow against the spending limit only, for funds already
+    /// reserved out of the balance. With `rolling` set the limit applies over
+    /// the trailing epoch duration instead of the current epoch.
+    pub fn record_spending(
+        &mut self,
+        amount: u64,
+        current_time: i64,
+        epoch_duration: u64,
+        rolling: bool,
+    ) -> Result<()> {
+        let new_epoch_spending = self.epoch_spending
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        if !rolling {
+            require!(
+                new_epoch_spending <= self.spending_limit,
+                ErrorCode::SpendingLimitExceeded
+            );
+        }
+        let rolling_limit = if rolling { Some(self.spending_limit) } else { None };
+        self.rolling_spending.record(amount, current_time, epoch_duration, rolling_limit)?;
+        
+        self.epoch_spending = new_epoch_spending;
+        Ok(())
+    }
+    
+    /// Takes funds out of the spendable balance, e.g. to back a vesting stream
+    pub fn reserve(&mut self, amount: u64) -> Result<()> {
+        self.balance = self.balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientTokenBalance)?;
+        Ok(())
+    }
+    
+    /// Returns previously reserved funds to the spendable balance
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.balance = self.balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
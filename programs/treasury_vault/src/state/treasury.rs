@@ -1,9 +1,58 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    state::{AuditLog, RollingWindow, SpendingLimitMode},
+};
+
+/// What a recipient must hold to pass the treasury's token gate
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TokenGateMode {
+    Mint = 0,          // A minimum balance of the gate mint
+    Collection = 1,    // An NFT whose metadata names the gate collection as verified
+}
+
+impl TokenGateMode {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TokenGateMode::Mint),
+            1 => Some(TokenGateMode::Collection),
+            _ => None,
+        }
+    }
+}
+
+/// Treasury settings changed by `update_treasury_config` or a config change
+/// proposal. Settings left as None keep their current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq, InitSpace)]
+pub struct TreasuryConfigUpdate {
+    pub epoch_duration: Option<u64>,      // 9 bytes
+    pub spending_limit: Option<u64>,      // 9 bytes
+    pub approval_threshold: Option<u8>,   // 2 bytes - Approvals required to execute a proposal
+    pub crank_tip: Option<u64>,           // 9 bytes - Lamports paid to permissionless payout callers
+    pub audit_log_retention: Option<u64>, // 9 bytes - Seconds before an audit log can be closed
+    pub spending_limit_mode: Option<u8>,  // 2 bytes - SpendingLimitMode, fixed epochs or a rolling window
+    pub withdrawal_timelock_threshold: Option<u64>, // 9 bytes - Lamports above which withdrawals are timelocked, 0 disables it
+    pub withdrawal_timelock_delay: Option<u64>,     // 9 bytes - Seconds a queued withdrawal waits before execution
+    pub withdrawals_require_whitelist: Option<bool>, // 2 bytes - Whether withdrawals must go to active whitelisted recipients
+}
+
+impl TreasuryConfigUpdate {
+    pub const INIT_SPACE: usize = 9 + 9 + 2 + 9 + 9 + 2 + 9 + 9 + 2;
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct Treasury {
     pub admin: Pubkey,                // 32 bytes
+    pub creator: Pubkey,              // 32 bytes - Original admin, part of the treasury PDA seeds
+    pub treasury_id: u64,             // 8 bytes - Creator-scoped identifier, part of the treasury PDA seeds
     pub epoch_duration: u64,          // 8 bytes
     pub spending_limit: u64,          // 8 bytes
     pub total_funds: u64,             // 8 bytes
@@ -11,16 +60,199 @@ pub struct Treasury {
     pub epoch_spending: u64,          // 8 bytes
     pub next_payout_index: u64,       // 8 bytes - For generating unique payout IDs
     pub is_paused: bool,              // 1 byte - Flag to pause/unpause payouts
-    pub gate_token_mint: Option<Pubkey>, // 33 bytes (1 for Option + 32 for Pubkey)
+    pub gate_token_mint: Option<Pubkey>, // 33 bytes (1 for Option + 32 for Pubkey) - Gate mint, or the collection key in collection mode
+    pub approval_threshold: u8,       // 1 byte - Approvals required to execute a proposal
+    pub next_proposal_index: u64,     // 8 bytes - For generating unique proposal IDs
+    pub active_admin_count: u16,      // 2 bytes - Number of active admin users
+    pub pending_admin: Option<Pubkey>, // 33 bytes - Admin proposed by propose_admin, awaiting acceptance
+    pub crank_tip: u64,               // 8 bytes - Lamports paid to a permissionless caller per executed payout
+    pub audit_log_retention: u64,     // 8 bytes - Seconds an audit log must be kept before it can be closed
+    pub audit_digest: [u8; 32],       // 32 bytes - Rolling hash of the commitments of closed audit logs
+    pub closed_audit_log_count: u64,  // 8 bytes - Number of audit logs folded into the digest
+    pub audit_sequence: u64,          // 8 bytes - Sequence of the next audit log, used in its PDA seeds
+    pub audit_chain_head: [u8; 32],   // 32 bytes - Entry hash of the most recent audit log
+    pub withdrawal_timelock_threshold: u64, // 8 bytes - SOL withdrawals above this amount must be queued, 0 disables the timelock
+    pub withdrawal_timelock_delay: u64, // 8 bytes - Seconds a queued withdrawal waits before it can be executed
+    pub next_pending_withdrawal_index: u64, // 8 bytes - For generating unique pending withdrawal IDs
+    pub pause_expires_at: Option<i64>, // 9 bytes - When a guardian pause lifts on its own, None pauses until unpaused
+    pub spending_limit_mode: u8,      // 1 byte - SpendingLimitMode of the SOL and per-mint limits
+    pub rolling_spending: RollingWindow, // 8 + 16 * ROLLING_WINDOW_BUCKETS bytes - SOL spending over the trailing epoch duration
+    pub withdrawals_require_whitelist: bool, // 1 byte - Withdrawals may only go to active whitelisted recipients
+    pub gate_min_balance: u64,        // 8 bytes - Gate tokens a recipient must hold when the token gate is enabled
+    pub gate_mode: u8,                // 1 byte - TokenGateMode of the token gate
+    pub active_approver_count: u16,   // 2 bytes - Active users holding Withdraw, who can approve proposals
     pub bump: u8,                     // 1 byte
 }
 
 impl Treasury {
-    pub const INIT_SPACE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 33 + 1;
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 33 + 1 + 8 + 1 + 2 + 33 + 8 + 8 + 32 + 8 + 8 + 32 + 8 + 8 + 8 + 9
+        + 1 + RollingWindow::INIT_SPACE + 1 + 8 + 1 + 2;
     
     pub fn get_next_payout_index(&mut self) -> u64 {
         let index = self.next_payout_index;
         self.next_payout_index = self.next_payout_index.checked_add(1).unwrap();
         index
     }
+
+    pub fn get_next_proposal_index(&mut self) -> u64 {
+        let index = self.next_proposal_index;
+        self.next_proposal_index = self.next_proposal_index.checked_add(1).unwrap();
+        index
+    }
+
+    pub fn get_next_pending_withdrawal_index(&mut self) -> u64 {
+        let index = self.next_pending_withdrawal_index;
+        self.next_pending_withdrawal_index = self.next_pending_withdrawal_index.checked_add(1).unwrap();
+        index
+    }
+
+    /// Whether a SOL withdrawal of this amount must go through the timelock queue
+    pub fn requires_timelock(&self, amount: u64) -> bool {
+        self.withdrawal_timelock_threshold > 0 && amount > self.withdrawal_timelock_threshold
+    }
+
+    /// Returns the sequence of the audit log being created and advances the counter
+    pub fn next_audit_sequence(&mut self) -> Result<u64> {
+        let sequence = self.audit_sequence;
+        self.audit_sequence = self.audit_sequence.checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(sequence)
+    }
+
+    /// Chains a freshly written audit log onto the current head and makes it the new head
+    pub fn append_audit_entry(&mut self, audit_log: &mut AuditLog) {
+        audit_log.prev_hash = self.audit_chain_head;
+        audit_log.entry_hash = audit_log.compute_entry_hash();
+        self.audit_chain_head = audit_log.entry_hash;
+    }
+
+    /// Folds the commitment of a closed audit log into the rolling digest
+    pub fn fold_audit_commitment(&mut self, commitment: &[u8; 32]) -> Result<()> {
+        self.audit_digest = hashv(&[&self.audit_digest, commitment]).to_bytes();
+        self.closed_audit_log_count = self.closed_audit_log_count.checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Whether the treasury is paused at the given time, taking an expiring pause into account
+    pub fn is_paused_at(&self, current_time: i64) -> bool {
+        match self.pause_expires_at {
+            Some(expires_at) => self.is_paused && current_time < expires_at,
+            None => self.is_paused,
+        }
+    }
+
+    /// Whether spending limits apply over a rolling window instead of fixed epochs
+    pub fn uses_rolling_window(&self) -> bool {
+        self.spending_limit_mode == SpendingLimitMode::Rolling as u8
+    }
+
+    /// Starts a new epoch if the current one has elapsed.
+    /// Returns the spending of the epoch that was closed, if any.
+    pub fn roll_epoch(&mut self, current_time: i64) -> Option<u64> {
+        if current_time - self.last_epoch_start <= self.epoch_duration as i64 {
+            return None;
+        }
+
+        let previous_epoch_spending = self.epoch_spending;
+        self.last_epoch_start = current_time;
+        self.epoch_spending = 0;
+        Some(previous_epoch_spending)
+    }
+
+    /// Charges a SOL outflow against the spending limit. Spending is tracked both
+    /// per epoch and over the rolling window so the mode can be switched safely.
+    pub fn record_spending(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        let new_epoch_spending = self.epoch_spending.checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let rolling = self.uses_rolling_window();
+        if !rolling {
+            require!(
+                new_epoch_spending <= self.spending_limit,
+                ErrorCode::SpendingLimitExceeded
+            );
+        }
+        let rolling_limit = if rolling { Some(self.spending_limit) } else { None };
+        self.rolling_spending.record(amount, current_time, self.epoch_duration, rolling_limit)?;
+
+        self.epoch_spending = new_epoch_spending;
+        Ok(())
+    }
+
+    pub fn requires_multisig(&self) -> bool {
+        self.approval_threshold > 1
+    }
+
+    /// Validates a config update against the treasury's current state. The approval
+    /// threshold can never exceed the number of users able to approve.
+    pub fn validate_config(&self, config: &TreasuryConfigUpdate) -> Result<()> {
+        if let Some(duration) = config.epoch_duration {
+            require!(duration > 0, ErrorCode::InvalidEpochDuration);
+            require!(duration >= MIN_EPOCH_DURATION, ErrorCode::EpochDurationTooShort);
+        }
+        if let Some(limit) = config.spending_limit {
+            require!(limit > 0, ErrorCode::InvalidSpendingLimit);
+        }
+        if let Some(threshold) = config.approval_threshold {
+            require!(
+                threshold > 0 && threshold as usize <= MAX_PROPOSAL_APPROVERS,
+                ErrorCode::InvalidApprovalThreshold
+            );
+            require!(
+                threshold as u16 <= self.active_approver_count,
+                ErrorCode::ApprovalThresholdUnreachable
+            );
+        }
+        if let Some(tip) = config.crank_tip {
+            require!(tip <= MAX_CRANK_TIP, ErrorCode::CrankTipTooLarge);
+        }
+        if let Some(retention) = config.audit_log_retention {
+            require!(retention > 0, ErrorCode::InvalidAuditLogRetention);
+        }
+        if let Some(mode) = config.spending_limit_mode {
+            require!(
+                SpendingLimitMode::from_u8(mode).is_some(),
+                ErrorCode::InvalidSpendingLimitMode
+            );
+        }
+        if let Some(delay) = config.withdrawal_timelock_delay {
+            require!(delay <= MAX_WITHDRAWAL_TIMELOCK_DELAY, ErrorCode::InvalidWithdrawalDelay);
+        }
+        Ok(())
+    }
+
+    /// Keeps the approver count in sync when a user gains or loses the ability to
+    /// approve proposals. It never drops below the approval threshold, which would
+    /// leave proposals impossible to execute.
+    pub fn update_approver_count(&mut self, was_approver: bool, is_approver: bool) -> Result<()> {
+        match (was_approver, is_approver) {
+            (false, true) => {
+                self.active_approver_count = self.active_approver_count.checked_add(1)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+            (true, false) => {
+                require!(
+                    self.active_approver_count > self.approval_threshold as u16,
+                    ErrorCode::ApprovalThresholdUnreachable
+                );
+                self.active_approver_count -= 1;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn increment_admin_count(&mut self) -> Result<()> {
+        self.active_admin_count = self.active_admin_count.checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn decrement_admin_count(&mut self) -> Result<()> {
+        // The treasury must always keep at least one active admin
+        require!(self.active_admin_count > 1, ErrorCode::LastActiveAdmin);
+        self.active_admin_count -= 1;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,11 +1,60 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::MAX_EPOCH_LIMITS,
+    error::ErrorCode,
+    state::{charge_epoch_limit, EpochLimit},
+};
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Role {
     Admin = 0,
     Treasurer = 1,
+    Guardian = 2,    // Can pause the treasury, nothing else
+}
+
+impl Role {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Role::Admin),
+            1 => Some(Role::Treasurer),
+            2 => Some(Role::Guardian),
+            _ => None,
+        }
+    }
+    
+    /// Permissions granted to a user when they are given this role
+    pub fn default_permissions(&self) -> u16 {
+        match self {
+            Role::Admin => ALL_PERMISSIONS,
+            Role::Treasurer => {
+                Permission::Deposit as u16
+                    | Permission::Withdraw as u16
+                    | Permission::Schedule as u16
+                    | Permission::Execute as u16
+                    | Permission::Cancel as u16
+            }
+            Role::Guardian => Permission::Pause as u16,
+        }
+    }
+}
+
+/// Individual permission bits of `TreasuryUser::permissions`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Permission {
+    Deposit = 1 << 0,          // Reserved, deposits are open to anyone
+    Withdraw = 1 << 1,         // Withdraw funds and create or approve proposals
+    Schedule = 1 << 2,         // Schedule payouts and create vesting streams
+    Execute = 1 << 3,          // Execute payouts and approved proposals
+    Cancel = 1 << 4,           // Cancel payouts and vesting streams
+    ManageRecipients = 1 << 5, // Add, update and remove whitelisted recipients
+    ManageUsers = 1 << 6,      // Add users, change their role, permissions or status
+    Configure = 1 << 7,        // Change treasury settings and close old accounts
+    Pause = 1 << 8,            // Pause the treasury
 }
 
+pub const ALL_PERMISSIONS: u16 = (1 << 9) - 1;
+
 #[account]
 #[derive(InitSpace)]
 pub struct TreasuryUser {
@@ -14,10 +63,14 @@ pub struct TreasuryUser {
     pub is_active: bool,              // 1 byte
     pub treasury: Pubkey,             // 32 bytes
     pub bump: u8,                     // 1 byte
+    pub permissions: u16,             // 2 bytes - Bitmask of Permission values
+    #[max_len(MAX_EPOCH_LIMITS)]
+    pub epoch_limits: Vec<EpochLimit>, // 4 + 57 * MAX_EPOCH_LIMITS bytes - Per-mint caps on this user's outflows
 }
 
 impl TreasuryUser {
-    pub const INIT_SPACE: usize = 32 + 1 + 1 + 32 + 1;
+    pub const INIT_SPACE: usize = 32 + 1 + 1 + 32 + 1 + 2
+        + 4 + EpochLimit::INIT_SPACE * MAX_EPOCH_LIMITS;
     
     pub fn is_admin(&self) -> bool {
         self.role == Role::Admin as u8 && self.is_active
@@ -27,14 +80,34 @@ impl TreasuryUser {
         self.role == Role::Treasurer as u8 && self.is_active
     }
     
-    pub fn has_permission(&self, required_role: Role) -> bool {
-        if !self.is_active {
-            return false;
-        }
-        
-        match required_role {
-            Role::Admin => self.role == Role::Admin as u8,
-            Role::Treasurer => self.role == Role::Admin as u8 || self.role == Role::Treasurer as u8,
-        }
+    pub fn is_guardian(&self) -> bool {
+        self.role == Role::Guardian as u8 && self.is_active
+    }
+    
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.is_active && self.permissions & permission as u16 != 0
+    }
+    
+    /// Whether this user counts towards the treasury's approvers
+    pub fn can_approve(&self) -> bool {
+        self.has_permission(Permission::Withdraw)
+    }
+    
+    /// Charges an outflow initiated by this user against their limit for the mint
+    pub fn charge_spending(
+        &mut self,
+        token_mint: Option<Pubkey>,
+        amount: u64,
+        current_time: i64,
+        epoch_duration: u64,
+    ) -> Result<()> {
+        charge_epoch_limit(
+            &mut self.epoch_limits,
+            token_mint,
+            amount,
+            current_time,
+            epoch_duration,
+            ErrorCode::UserSpendingLimitExceeded,
+        )
     }
 }
\ No newline at end of file
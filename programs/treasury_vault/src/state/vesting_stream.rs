@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingStream {
+    pub treasury: Pubkey,             // 32 bytes
+    pub recipient: Pubkey,            // 32 bytes
+    pub token_mint: Option<Pubkey>,   // 33 bytes (1 for Option + 32 for Pubkey), None for SOL
+    pub total_amount: u64,            // 8 bytes - Amount reserved for the stream
+    pub claimed_amount: u64,          // 8 bytes - Amount already claimed by the recipient
+    pub start_time: i64,              // 8 bytes - Vesting starts unlocking linearly from here
+    pub cliff_time: i64,              // 8 bytes - Nothing can be claimed before this time
+    pub end_time: i64,                // 8 bytes - Everything has vested at this time
+    pub created_by: Pubkey,           // 32 bytes
+    pub index: u64,                   // 8 bytes
+    pub is_active: bool,              // 1 byte - False once everything vested has been claimed
+    pub bump: u8,                     // 1 byte
+}
+
+impl VestingStream {
+    pub const INIT_SPACE: usize = 32 + 32 + 33 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 1 + 1;
+    
+    /// Amount unlocked by the stream at the given time
+    pub fn vested_amount(&self, current_time: i64) -> u64 {
+        if current_time >= self.end_time {
+            return self.total_amount;
+        }
+        
+        if current_time < self.cliff_time {
+            return 0;
+        }
+        
+        let elapsed = (current_time - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+    
+    /// Amount the recipient can claim at the given time
+    pub fn claimable_amount(&self, current_time: i64) -> u64 {
+        self.vested_amount(current_time).saturating_sub(self.claimed_amount)
+    }
+    
+    /// Records a claim by the recipient
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        self.claimed_amount = self.claimed_amount.checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Stops vesting at the given time, keeping what has vested claimable.
+    /// Returns the unvested remainder released back to the treasury.
+    pub fn cancel(&mut self, current_time: i64) -> Result<u64> {
+        let vested = self.vested_amount(current_time);
+        let unvested = self.total_amount.checked_sub(vested)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        
+        // Collapse the schedule so everything vested so far is unlocked from now on
+        self.total_amount = vested;
+        self.end_time = self.end_time.min(current_time);
+        self.start_time = self.start_time.min(self.end_time);
+        self.cliff_time = self.cliff_time.min(self.end_time);
+        
+        Ok(unvested)
+    }
+    
+    /// True once vesting has ended and the recipient has claimed everything
+    pub fn is_settled(&self, current_time: i64) -> bool {
+        current_time >= self.end_time && self.claimed_amount >= self.total_amount
+    }
+}
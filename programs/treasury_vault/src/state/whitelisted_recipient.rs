@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{MAX_EPOCH_LIMITS, RECIPIENT_SEED},
+    error::ErrorCode,
+    state::{charge_epoch_limit, EpochLimit},
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct WhitelistedRecipient {
@@ -8,9 +14,64 @@ pub struct WhitelistedRecipient {
     pub name: String,                 // 4 + 32 bytes (max)
     pub is_active: bool,              // 1 byte
     pub treasury: Pubkey,             // 32 bytes
+    pub active_payout_count: u32,     // 4 bytes - Number of active payout schedules for this recipient
+    pub active_vesting_count: u32,    // 4 bytes - Number of active vesting streams for this recipient
     pub bump: u8,                     // 1 byte
+    #[max_len(MAX_EPOCH_LIMITS)]
+    pub epoch_limits: Vec<EpochLimit>, // 4 + 57 * MAX_EPOCH_LIMITS bytes - Per-mint caps on payments to this recipient
 }
 
 impl WhitelistedRecipient {
-    pub const INIT_SPACE: usize = 32 + 4 + 32 + 1 + 32 + 1;
+    pub const INIT_SPACE: usize = 32 + 4 + 32 + 1 + 32 + 4 + 4 + 1
+        + 4 + EpochLimit::INIT_SPACE * MAX_EPOCH_LIMITS;
+    
+    /// Address of the whitelist entry of `recipient` in `treasury`, whether or not it exists
+    pub fn address(recipient: &Pubkey, treasury: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[RECIPIENT_SEED, recipient.as_ref(), treasury.as_ref()],
+            &crate::ID,
+        ).0
+    }
+    
+    pub fn track_payout_scheduled(&mut self) -> Result<()> {
+        self.active_payout_count = self.active_payout_count.checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    pub fn track_payout_closed(&mut self) -> Result<()> {
+        self.active_payout_count = self.active_payout_count.checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    pub fn track_vesting_created(&mut self) -> Result<()> {
+        self.active_vesting_count = self.active_vesting_count.checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    pub fn track_vesting_closed(&mut self) -> Result<()> {
+        self.active_vesting_count = self.active_vesting_count.checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Charges a payment to this recipient against its limit for the mint
+    pub fn charge_spending(
+        &mut self,
+        token_mint: Option<Pubkey>,
+        amount: u64,
+        current_time: i64,
+        epoch_duration: u64,
+    ) -> Result<()> {
+        charge_epoch_limit(
+            &mut self.epoch_limits,
+            token_mint,
+            amount,
+            current_time,
+            epoch_duration,
+            ErrorCode::RecipientSpendingLimitExceeded,
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,386 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    error::ErrorCode,
+    events::*,
+    state::*,
+};
+
+/// Transfer tokens with `transfer_checked` through either token program.
+/// Extra accounts required by a Token-2022 transfer hook are taken from
+/// `additional_accounts` and forwarded to the CPI.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens_checked<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint.to_account_info(),
+        to,
+        authority,
+        additional_accounts,
+        amount,
+        mint.decimals,
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Pay the treasury's crank tip to the caller of a permissionless payout.
+/// The tip is charged against the treasury's SOL spending limit and written
+/// to `audit_log`, which must be passed exactly when a tip is due.
+pub fn pay_crank_tip<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    cranker: AccountInfo<'info>,
+    audit_log: Option<&mut Account<'info, AuditLog>>,
+    audit_bump: Option<u8>,
+    payout_schedule: Pubkey,
+    current_time: i64,
+    client_timestamp: i64,
+) -> Result<()> {
+    let tip = treasury.crank_tip;
+    if tip == 0 {
+        require!(audit_log.is_none(), ErrorCode::InvalidTipAuditLog);
+        return Ok(());
+    }
+    let (audit_log, audit_bump) = match (audit_log, audit_bump) {
+        (Some(audit_log), Some(audit_bump)) => (audit_log, audit_bump),
+        _ => return Err(ErrorCode::InvalidTipAuditLog.into()),
+    };
+
+    require!(treasury.total_funds >= tip, ErrorCode::InsufficientFunds);
+
+    // Check if we need to reset the epoch
+    let treasury_key = treasury.key();
+    if let Some(previous_epoch_spending) = treasury.roll_epoch(current_time) {
+        emit!(SpendingLimitResetEvent {
+            treasury: treasury_key,
+            previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // Tips are paid in SOL
+        });
+
+        emit!(TreasuryEvent {
+            action: AuditAction::SpendingLimitReset as u8,
+            treasury: treasury_key,
+            initiator: cranker.key(),
+            target: None,
+            amount: previous_epoch_spending,
+            timestamp: current_time,
+            token_mint: None, // Tips are paid in SOL
+        });
+    }
+
+    // The tip leaves the treasury like any other outflow
+    treasury.record_spending(tip, current_time)?;
+
+    let balance_before = treasury.total_funds;
+    treasury.total_funds = treasury.total_funds.checked_sub(tip)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Move lamports directly, the treasury is owned by this program
+    let treasury_info = treasury.to_account_info();
+    let treasury_lamports = treasury_info.lamports();
+    let cranker_lamports = cranker.lamports();
+
+    **treasury_info.try_borrow_mut_lamports()? = treasury_lamports.checked_sub(tip)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **cranker.try_borrow_mut_lamports()? = cranker_lamports.checked_add(tip)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Create audit log
+    let audit_sequence = treasury.next_audit_sequence()?;
+    audit_log.action = AuditAction::CrankTipPaid as u8;
+    audit_log.treasury = treasury_key;
+    audit_log.initiator = cranker.key();
+    audit_log.target = Some(cranker.key());
+    audit_log.amount = tip;
+    audit_log.balance_before = balance_before;
+    audit_log.balance_after = treasury.total_funds;
+    audit_log.sequence = audit_sequence;
+    audit_log.previous_sequence = audit_sequence.checked_sub(1);
+    audit_log.timestamp = current_time;
+    audit_log.client_timestamp = client_timestamp;
+    audit_log.token_mint = None; // Tips are paid in SOL
+    audit_log.payout_schedule = Some(payout_schedule);
+    audit_log.bump = audit_bump;
+    treasury.append_audit_entry(audit_log);
+
+    emit!(CrankTipPaidEvent {
+        treasury: treasury_key,
+        cranker: cranker.key(),
+        payout_schedule,
+        tip,
+        timestamp: current_time,
+    });
+
+    emit!(TreasuryEvent {
+        action: AuditAction::CrankTipPaid as u8,
+        treasury: treasury_key,
+        initiator: cranker.key(),
+        target: Some(cranker.key()),
+        amount: tip,
+        timestamp: current_time,
+        token_mint: None, // Tips are paid in SOL
+    });
+
+    Ok(())
+}
+
+/// Enforce the treasury's withdrawal whitelist policy on the recipient of a
+/// direct withdrawal, given its whitelist entry PDA whether or not it exists
+pub fn require_whitelisted_recipient(
+    treasury: &Treasury,
+    whitelisted_recipient: &AccountInfo,
+) -> Result<()> {
+    if !treasury.withdrawals_require_whitelist {
+        return Ok(());
+    }
+
+    require!(
+        whitelisted_recipient.owner == &crate::ID && !whitelisted_recipient.data_is_empty(),
+        ErrorCode::RecipientNotWhitelisted
+    );
+    let recipient = WhitelistedRecipient::try_deserialize(
+        &mut &whitelisted_recipient.try_borrow_data()?[..]
+    )?;
+    require!(recipient.is_active, ErrorCode::RecipientNotActive);
+    Ok(())
+}
+
+/// Enforce the treasury's token gate on the recipient of a payout or claim.
+/// The gate account is a separate token account owned by the recipient. In mint
+/// mode it must hold at least the configured minimum balance of the gate mint; in
+/// collection mode it must hold an NFT whose metadata names the gate collection
+/// as verified.
+pub fn check_token_gate(
+    treasury: &Treasury,
+    gate_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    gate_metadata: Option<&AccountInfo>,
+    recipient: Pubkey,
+) -> Result<()> {
+    let Some(gate_key) = treasury.gate_token_mint else {
+        return Ok(());
+    };
+
+    let gate_token_account = gate_token_account.ok_or(ErrorCode::TokenGateCheckFailed)?;
+    require!(
+        gate_token_account.owner == recipient
+            && gate_token_account.amount >= treasury.gate_min_balance,
+        ErrorCode::TokenGateCheckFailed
+    );
+
+    match TokenGateMode::from_u8(treasury.gate_mode) {
+        Some(TokenGateMode::Collection) => {
+            let gate_metadata = gate_metadata.ok_or(ErrorCode::TokenGateCheckFailed)?;
+            require!(
+                verified_collection(gate_metadata, &gate_token_account.mint)? == Some(gate_key),
+                ErrorCode::TokenGateCheckFailed
+            );
+        }
+        _ => require!(gate_token_account.mint == gate_key, ErrorCode::TokenGateCheckFailed),
+    }
+    Ok(())
+}
+
+// Account key of a Metaplex `MetadataV1` account
+const METADATA_V1_KEY: u8 = 4;
+
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+struct MetadataCreator {
+    address: Pubkey,
+    verified: bool,
+    share: u8,
+}
+
+#[derive(AnchorDeserialize)]
+struct MetadataCollection {
+    verified: bool,
+    key: Pubkey,
+}
+
+/// Leading fields of a Metaplex metadata account, up to its collection
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+struct MetadataPrefix {
+    key: u8,
+    update_authority: Pubkey,
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Option<Vec<MetadataCreator>>,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    token_standard: Option<u8>,
+    collection: Option<MetadataCollection>,
+}
+
+/// Returns the verified collection named by the metadata account of `mint`,
+/// or None if the account is not that mint's metadata or has no verified collection
+fn verified_collection(metadata: &AccountInfo, mint: &Pubkey) -> Result<Option<Pubkey>> {
+    let (metadata_address, _) = Pubkey::find_program_address(
+        &[METADATA_SEED, TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    if metadata.key() != metadata_address || metadata.owner != &TOKEN_METADATA_PROGRAM_ID {
+        return Ok(None);
+    }
+
+    let data = metadata.try_borrow_data()?;
+    let Ok(prefix) = MetadataPrefix::deserialize(&mut &data[..]) else {
+        return Ok(None);
+    };
+    if prefix.key != METADATA_V1_KEY || prefix.mint != *mint {
+        return Ok(None);
+    }
+
+    Ok(prefix
+        .collection
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key))
+}
+
+/// Charge a direct withdrawal against the spending limit of its recipient.
+/// The whitelist entry is passed at its PDA address whether or not it exists,
+/// so only recipients that are actually whitelisted are charged.
+pub fn charge_recipient_limit(
+    whitelisted_recipient: &AccountInfo,
+    token_mint: Option<Pubkey>,
+    amount: u64,
+    current_time: i64,
+    epoch_duration: u64,
+) -> Result<()> {
+    if whitelisted_recipient.owner != &crate::ID || whitelisted_recipient.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut data = whitelisted_recipient.try_borrow_mut_data()?;
+    let mut recipient = WhitelistedRecipient::try_deserialize(&mut &data[..])?;
+    recipient.charge_spending(token_mint, amount, current_time, epoch_duration)?;
+    recipient.try_serialize(&mut &mut data[..])
+}
+
+/// Treasury balance of the asset recorded in the audit log: `total_funds` for
+/// SOL, or the tracked balance of the token mint when its `token_balance` is passed
+pub fn audited_balance(treasury: &Treasury, token_balance: Option<&Account<TokenBalance>>) -> u64 {
+    token_balance.map_or(treasury.total_funds, |token_balance| token_balance.balance)
+}
+
+/// Count the approvals of a proposal from users who can still approve it. The
+/// approvers' treasury user accounts are passed in approval order at the start
+/// of `approver_accounts`; approvers that have since been deactivated or lost
+/// the Withdraw permission are not counted.
+pub fn count_active_approvals(
+    proposal: &Proposal,
+    treasury_key: Pubkey,
+    approver_accounts: &[AccountInfo],
+) -> Result<u8> {
+    require!(
+        approver_accounts.len() >= proposal.approvals.len(),
+        ErrorCode::ApproverAccountMismatch
+    );
+
+    let mut count = 0u8;
+    for (approver, account) in proposal.approvals.iter().zip(approver_accounts) {
+        require!(account.owner == &crate::ID, ErrorCode::ApproverAccountMismatch);
+        let user = TreasuryUser::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            user.user == *approver && user.treasury == treasury_key,
+            ErrorCode::ApproverAccountMismatch
+        );
+        if user.can_approve() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// While the treasury requires multisig, user management changes go through a
+/// proposal for the same change. The proposal needs enough approvals from
+/// current approvers, whose accounts are passed in `approver_accounts`, and is
+/// marked executed so it can't be replayed.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_user_proposal(
+    treasury: &Account<Treasury>,
+    proposal: Option<&mut Account<Proposal>>,
+    approver_accounts: &[AccountInfo],
+    executor: Pubkey,
+    action: ProposalAction,
+    user: Pubkey,
+    role: Option<u8>,
+    permissions: Option<u16>,
+) -> Result<()> {
+    execute_approved_proposal(treasury, proposal, approver_accounts, executor, |proposal| {
+        proposal.is_user_change(action, user, role, permissions)
+    })
+}
+
+/// Same as `execute_user_proposal`, for changes to the spending limit of a
+/// treasury user or whitelisted recipient
+#[allow(clippy::too_many_arguments)]
+pub fn execute_spending_limit_proposal(
+    treasury: &Account<Treasury>,
+    proposal: Option<&mut Account<Proposal>>,
+    approver_accounts: &[AccountInfo],
+    executor: Pubkey,
+    action: ProposalAction,
+    target: Pubkey,
+    token_mint: Option<Pubkey>,
+    limit: Option<u64>,
+) -> Result<()> {
+    execute_approved_proposal(treasury, proposal, approver_accounts, executor, |proposal| {
+        proposal.is_spending_limit_change(action, target, token_mint, limit)
+    })
+}
+
+/// Consumes an approved proposal for a change made by another instruction.
+/// `is_match` checks that the proposal describes exactly that change.
+fn execute_approved_proposal(
+    treasury: &Account<Treasury>,
+    proposal: Option<&mut Account<Proposal>>,
+    approver_accounts: &[AccountInfo],
+    executor: Pubkey,
+    is_match: impl FnOnce(&Proposal) -> bool,
+) -> Result<()> {
+    if !treasury.requires_multisig() {
+        return Ok(());
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let proposal = proposal.ok_or(ErrorCode::MultisigApprovalRequired)?;
+    require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+    require!(!proposal.is_expired(current_time), ErrorCode::ProposalExpired);
+    require!(is_match(proposal), ErrorCode::ProposalAccountMismatch);
+    require!(
+        count_active_approvals(proposal, treasury.key(), approver_accounts)? >= treasury.approval_threshold,
+        ErrorCode::InsufficientApprovals
+    );
+
+    proposal.executed = true;
+
+    emit!(ProposalExecutedEvent {
+        executor,
+        treasury: treasury.key(),
+        proposal: proposal.key(),
+        action: proposal.action,
+        amount: 0,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -37,6 +37,24 @@ test_files=(
    "treasury_vault_basic_spl.ts"
     "treasury_vault_spl_edge_cases.ts"
     "treasury_vault_spl_payouts.ts"
+    "treasury_vault_multi_treasury.ts"
+    "treasury_vault_multisig.ts"
+    "treasury_vault_admin_handover.ts"
+    "treasury_vault_token_2022.ts"
+    "treasury_vault_crank.ts"
+    "treasury_vault_vesting.ts"
+    "treasury_vault_claim_payout.ts"
+    "treasury_vault_account_cleanup.ts"
+    "treasury_vault_audit_chain.ts"
+    "treasury_vault_timelock.ts"
+    "treasury_vault_guardian.ts"
+    "treasury_vault_permissions.ts"
+    "treasury_vault_spending_caps.ts"
+    "treasury_vault_rolling_limit.ts"
+    "treasury_vault_withdrawal_whitelist.ts"
+    "treasury_vault_token_gate_threshold.ts"
+    "treasury_vault_collection_gate.ts"
+    "treasury_vault_distribution.ts"
 )
 
 
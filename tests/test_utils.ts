@@ -11,6 +11,7 @@ import {
 } from "@solana/spl-token";
 
 // Constants for testing
+export const TREASURY_ID = new BN(0);
 export const EPOCH_DURATION = new BN(86400); // 1 day in seconds
 export const SPENDING_LIMIT = new BN(1000000000); // 1 SOL in lamports (also used for token amount)
 export const DEPOSIT_AMOUNT = new BN(500000); // 0.5 tokens (with 6 decimals)
@@ -20,13 +21,47 @@ export const PAYOUT_AMOUNT = new BN(100000); // 0.1 tokens (with 6 decimals)
 // User roles
 export const ADMIN_ROLE = 0;
 export const TREASURER_ROLE = 1;
-export const USER_ROLE = 2;
+export const GUARDIAN_ROLE = 2;
+
+// Payout schedule without an end time, execution cap or custom catch-up policy
+export const DEFAULT_PAYOUT_OPTIONS = { endTime: null, maxExecutions: null, catchUpPolicy: null };
+
+export interface TreasuryConfigUpdate {
+  epochDuration: BN | null;
+  spendingLimit: BN | null;
+  approvalThreshold: number | null;
+  crankTip: BN | null;
+  auditLogRetention: BN | null;
+  spendingLimitMode: number | null;
+  withdrawalTimelockThreshold: BN | null;
+  withdrawalTimelockDelay: BN | null;
+  withdrawalsRequireWhitelist: boolean | null;
+}
+
+// Treasury config update that leaves every setting not overridden unchanged
+export function configUpdate(overrides: Partial<TreasuryConfigUpdate> = {}): TreasuryConfigUpdate {
+  return {
+    epochDuration: null,
+    spendingLimit: null,
+    approvalThreshold: null,
+    crankTip: null,
+    auditLogRetention: null,
+    spendingLimitMode: null,
+    withdrawalTimelockThreshold: null,
+    withdrawalTimelockDelay: null,
+    withdrawalsRequireWhitelist: null,
+    ...overrides,
+  };
+}
 
 // Audit log actions
 export const AUDIT_ACTION_DEPOSIT = 0; // Fixed: Changed from 2 to 0 to match program's AuditAction enum
 export const AUDIT_ACTION_WITHDRAW = 1;
+export const AUDIT_ACTION_EXECUTE_PAYOUT = 3;
 export const AUDIT_ACTION_TOKEN_DEPOSIT = 12;
 export const AUDIT_ACTION_TOKEN_PAYOUT = 13;
+export const AUDIT_ACTION_CRANK_TIP_PAID = 27;
+export const AUDIT_ACTION_WITHDRAWAL_VETOED = 38;
 
 export interface TestContext {
   provider: anchor.AnchorProvider;
@@ -55,6 +90,17 @@ export interface PayoutContext {
   recurringPayoutPDA: anchor.web3.PublicKey;
 }
 
+export function findTreasuryPDA(
+  program: Program<TreasuryVault>,
+  creator: anchor.web3.PublicKey,
+  treasuryId: BN
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury"), creator.toBuffer(), treasuryId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+}
+
 export async function setupTestContext(): Promise<TestContext> {
   // Configure the client to use the local cluster
   const provider = anchor.AnchorProvider.env();
@@ -76,10 +122,7 @@ export async function setupTestContext(): Promise<TestContext> {
   await new Promise((resolve) => setTimeout(resolve, 1000));
   
   // Find PDAs
-  const [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("treasury")],
-    program.programId
-  );
+  const [treasuryPDA, treasuryBump] = findTreasuryPDA(program, admin.publicKey, TREASURY_ID);
   
   // Find user PDAs
   const [adminUserPDA] = await anchor.web3.PublicKey.findProgramAddressSync(
@@ -128,7 +171,7 @@ export async function setupTestContext(): Promise<TestContext> {
 export async function initializeTreasury(ctx: TestContext): Promise<void> {
   // Initialize treasury
   await ctx.program.methods
-    .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+    .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
     .accounts({
       treasury: ctx.treasuryPDA,
       admin: ctx.admin.publicKey,
@@ -144,8 +187,11 @@ export async function initializeTreasury(ctx: TestContext): Promise<void> {
     .accounts({
       admin: ctx.admin.publicKey,
       treasury: ctx.treasuryPDA,
+      adminUser: ctx.adminUserPDA,
       userAccount: ctx.treasurerUserPDA,
       user: ctx.treasurer.publicKey,
+      auditLog: await findAuditLogPDA(ctx),
+      proposal: null,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([ctx.admin])
@@ -160,6 +206,7 @@ export async function initializeTreasury(ctx: TestContext): Promise<void> {
       user: ctx.adminUserPDA,
       recipientAccount: ctx.recipientPDA,
       recipient: ctx.recipient.publicKey,
+      auditLog: await findAuditLogPDA(ctx),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([ctx.admin])
@@ -223,6 +270,9 @@ export async function setupTokenContext(ctx: TestContext, decimals: number = 6):
     ctx.program.programId
   );
   
+  // Enable outflows of this mint with the default per-mint limit
+  await setTokenSpendingLimit(ctx, tokenMint, SPENDING_LIMIT);
+  
   return {
     tokenMint,
     treasuryTokenAccount,
@@ -232,6 +282,34 @@ export async function setupTokenContext(ctx: TestContext, decimals: number = 6):
   };
 }
 
+export async function setTokenSpendingLimit(
+  ctx: TestContext,
+  tokenMint: anchor.web3.PublicKey,
+  spendingLimit: BN
+): Promise<void> {
+  const [tokenBalancePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("token_balance"),
+      ctx.treasuryPDA.toBuffer(),
+      tokenMint.toBuffer(),
+    ],
+    ctx.program.programId
+  );
+  
+  await ctx.program.methods
+    .setTokenSpendingLimit(spendingLimit)
+    .accounts({
+      treasury: ctx.treasuryPDA,
+      authority: ctx.admin.publicKey,
+      user: ctx.adminUserPDA,
+      tokenBalance: tokenBalancePDA,
+      tokenMint,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([ctx.admin])
+    .rpc();
+}
+
 export async function setupPayoutContext(ctx: TestContext, tokenMint: anchor.web3.PublicKey): Promise<PayoutContext> {
   // Find payout schedule PDA for one-time payout
   const [payoutSchedulePDA] = await anchor.web3.PublicKey.findProgramAddressSync(
@@ -262,16 +340,18 @@ export async function setupPayoutContext(ctx: TestContext, tokenMint: anchor.web
 }
 
 export async function findAuditLogPDA(
-  ctx: TestContext, 
-  timestamp: BN, 
-  authority: anchor.web3.PublicKey
+  ctx: TestContext,
+  treasuryPDA: anchor.web3.PublicKey = ctx.treasuryPDA,
+  offset: number = 0
 ): Promise<anchor.web3.PublicKey> {
-  const [auditLogPDA] = await anchor.web3.PublicKey.findProgramAddressSync(
+  // Audit logs are keyed by the treasury's next audit sequence, `offset`
+  // selects a later entry written by the same instruction
+  const treasury = await ctx.program.account.treasury.fetch(treasuryPDA);
+  const [auditLogPDA] = anchor.web3.PublicKey.findProgramAddressSync(
     [
       Buffer.from("audit"),
-      ctx.treasuryPDA.toBuffer(),
-      timestamp.toArrayLike(Buffer, "le", 8),
-      authority.toBuffer(),
+      treasuryPDA.toBuffer(),
+      treasury.auditSequence.addn(offset).toArrayLike(Buffer, "le", 8),
     ],
     ctx.program.programId
   );
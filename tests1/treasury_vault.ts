@@ -15,6 +15,7 @@ describe("treasury_vault", () => {
   const recipient = anchor.web3.Keypair.generate();
   
   // Constants for testing
+  const TREASURY_ID = new BN(0);
   const EPOCH_DURATION = new BN(86400); // 1 day in seconds
   const SPENDING_LIMIT = new BN(1000000000); // 1 SOL in lamports
   const DEPOSIT_AMOUNT = new BN(500000000); // 0.5 SOL in lamports
@@ -24,13 +25,27 @@ describe("treasury_vault", () => {
   // PDAs
   let treasuryPDA: anchor.web3.PublicKey;
   let treasuryBump: number;
-  let depositAuditLogPDA: anchor.web3.PublicKey;
-  let withdrawAuditLogPDA: anchor.web3.PublicKey;
   let adminUserPDA: anchor.web3.PublicKey;
   let adminUserBump: number;
   let userUserPDA: anchor.web3.PublicKey;
   let userUserBump: number;
 
+  // Audit logs are keyed by the treasury's next audit sequence
+  const findAuditLogPDA = async () => {
+    const treasury = await program.account.treasury.fetch(treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), treasuryPDA.toBuffer(), treasury.auditSequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
+  // Whitelist entry PDA of a recipient, passed to withdrawals whether or not it exists
+  const findRecipientPDA = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient"), wallet.toBuffer(), treasuryPDA.toBuffer()],
+      program.programId
+    )[0];
+
   before(async () => {
     // Airdrop SOL to admin, user, and recipient for testing
     await provider.connection.requestAirdrop(admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -42,27 +57,7 @@ describe("treasury_vault", () => {
     
     // Find PDAs
     [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury")],
-      program.programId
-    );
-    
-    [depositAuditLogPDA] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("audit"),
-        treasuryPDA.toBuffer(),
-        TIMESTAMP.toArrayLike(Buffer, "le", 8),
-        user.publicKey.toBuffer(),
-      ],
-      program.programId
-    );
-    
-    [withdrawAuditLogPDA] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("audit"),
-        treasuryPDA.toBuffer(),
-        TIMESTAMP.toArrayLike(Buffer, "le", 8),
-        admin.publicKey.toBuffer(),
-      ],
+      [Buffer.from("treasury"), admin.publicKey.toBuffer(), TREASURY_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     
@@ -91,7 +86,7 @@ describe("treasury_vault", () => {
     it("should initialize treasury with valid parameters", async () => {
       // Initialize treasury
       await program.methods
-        .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+        .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
         .accounts({
           treasury: treasuryPDA,
           admin: admin.publicKey,
@@ -126,7 +121,7 @@ describe("treasury_vault", () => {
     it("should fail with zero epoch duration", async () => {
       try {
         await program.methods
-          .initializeTreasury(new BN(0), SPENDING_LIMIT)
+          .initializeTreasury(TREASURY_ID, new BN(0), SPENDING_LIMIT)
           .accounts({
             treasury: treasuryPDA,
             admin: admin.publicKey,
@@ -152,7 +147,7 @@ describe("treasury_vault", () => {
     it("should fail with zero spending limit", async () => {
       try {
         await program.methods
-          .initializeTreasury(EPOCH_DURATION, new BN(0))
+          .initializeTreasury(TREASURY_ID, EPOCH_DURATION, new BN(0))
           .accounts({
             treasury: treasuryPDA,
             admin: admin.publicKey,
@@ -190,8 +185,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          currentTimestamp.toArrayLike(Buffer, "le", 8),
-          user.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -228,7 +222,16 @@ describe("treasury_vault", () => {
       expect(auditLogAccount.action).to.equal(0); // 0 = Deposit
       expect(auditLogAccount.initiator.toString()).to.equal(user.publicKey.toString());
       expect(auditLogAccount.amount.toString()).to.equal(DEPOSIT_AMOUNT.toString());
-      expect(auditLogAccount.timestamp.toString()).to.equal(currentTimestamp.toString());
+      expect(auditLogAccount.clientTimestamp.toString()).to.equal(currentTimestamp.toString());
+      
+      // The audit log takes the next sequence number and the treasury advances past it
+      const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.auditSequence.toString()).to.equal(auditLogAccount.sequence.addn(1).toString());
+      if (auditLogAccount.sequence.isZero()) {
+        expect(auditLogAccount.previousSequence).to.be.null;
+      } else {
+        expect(auditLogAccount.previousSequence.toString()).to.equal(auditLogAccount.sequence.subn(1).toString());
+      }
     });
 
     it("should fail with zero deposit amount", async () => {
@@ -238,8 +241,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          newTimestamp.toArrayLike(Buffer, "le", 8),
-          user.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -276,8 +278,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          futureTimestamp.toArrayLike(Buffer, "le", 8),
-          user.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -308,8 +309,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          newTimestamp.toArrayLike(Buffer, "le", 8),
-          user.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -356,8 +356,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          withdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -371,6 +370,7 @@ describe("treasury_vault", () => {
           authority: admin.publicKey,
           user: adminUserPDA,
           recipient: recipient.publicKey,
+          whitelistedRecipient: findRecipientPDA(recipient.publicKey),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -399,7 +399,7 @@ describe("treasury_vault", () => {
       expect(auditLogAccount.action).to.equal(1); // 1 = Withdraw
       expect(auditLogAccount.initiator.toString()).to.equal(admin.publicKey.toString());
       expect(auditLogAccount.amount.toString()).to.equal(WITHDRAW_AMOUNT.toString());
-      expect(auditLogAccount.timestamp.toString()).to.equal(withdrawTimestamp.toString());
+      expect(auditLogAccount.clientTimestamp.toString()).to.equal(withdrawTimestamp.toString());
     });
 
     it("should fail when non-admin tries to withdraw", async () => {
@@ -409,8 +409,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          withdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          user.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -421,9 +420,12 @@ describe("treasury_vault", () => {
           .addTreasuryUser(1, true) // Role 1 = Treasurer
           .accounts({
             treasury: treasuryPDA,
+            adminUser: adminUserPDA,
             admin: admin.publicKey,
             user_account: userUserPDA,
             user: user.publicKey,
+            auditLog: await findAuditLogPDA(),
+            proposal: null,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -438,6 +440,7 @@ describe("treasury_vault", () => {
             authority: user.publicKey,
             user: userUserPDA,
             recipient: recipient.publicKey,
+            whitelistedRecipient: findRecipientPDA(recipient.publicKey),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([user])
@@ -459,8 +462,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          withdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -482,6 +484,7 @@ describe("treasury_vault", () => {
             authority: admin.publicKey,
             user: adminUserPDA,
             recipient: recipient.publicKey,
+            whitelistedRecipient: findRecipientPDA(recipient.publicKey),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -502,8 +505,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          withdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -523,6 +525,7 @@ describe("treasury_vault", () => {
             authority: admin.publicKey,
             user: adminUserPDA,
             recipient: recipient.publicKey,
+            whitelistedRecipient: findRecipientPDA(recipient.publicKey),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -543,11 +546,22 @@ describe("treasury_vault", () => {
       const newEpochDuration = new BN(172800); // 2 days in seconds
       
       await program.methods
-        .updateTreasuryConfig(newEpochDuration, null)
+        .updateTreasuryConfig({
+          epochDuration: newEpochDuration,
+          spendingLimit: null,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -565,11 +579,22 @@ describe("treasury_vault", () => {
       const newSpendingLimit = new BN(2000000000); // 2 SOL in lamports
       
       await program.methods
-        .updateTreasuryConfig(null, newSpendingLimit)
+        .updateTreasuryConfig({
+          epochDuration: null,
+          spendingLimit: newSpendingLimit,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -588,11 +613,22 @@ describe("treasury_vault", () => {
       const newSpendingLimit = new BN(3000000000); // 3 SOL in lamports
       
       await program.methods
-        .updateTreasuryConfig(newEpochDuration, newSpendingLimit)
+        .updateTreasuryConfig({
+          epochDuration: newEpochDuration,
+          spendingLimit: newSpendingLimit,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -610,11 +646,22 @@ describe("treasury_vault", () => {
       // We already created a regular user in the withdraw test
       try {
         await program.methods
-          .updateTreasuryConfig(EPOCH_DURATION, SPENDING_LIMIT)
+          .updateTreasuryConfig({
+            epochDuration: EPOCH_DURATION,
+            spendingLimit: SPENDING_LIMIT,
+            approvalThreshold: null,
+            crankTip: null,
+            auditLogRetention: null,
+            spendingLimitMode: null,
+            withdrawalTimelockThreshold: null,
+            withdrawalTimelockDelay: null,
+            withdrawalsRequireWhitelist: null,
+          })
           .accounts({
             treasury: treasuryPDA,
             authority: user.publicKey,
             user: userUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([user])
@@ -631,11 +678,22 @@ describe("treasury_vault", () => {
     it("should fail when trying to set invalid epoch duration", async () => {
       try {
         await program.methods
-          .updateTreasuryConfig(new BN(0), null)
+          .updateTreasuryConfig({
+            epochDuration: new BN(0),
+            spendingLimit: null,
+            approvalThreshold: null,
+            crankTip: null,
+            auditLogRetention: null,
+            spendingLimitMode: null,
+            withdrawalTimelockThreshold: null,
+            withdrawalTimelockDelay: null,
+            withdrawalsRequireWhitelist: null,
+          })
           .accounts({
             treasury: treasuryPDA,
             authority: admin.publicKey,
             user: adminUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -651,11 +709,22 @@ describe("treasury_vault", () => {
     it("should fail when trying to set invalid spending limit", async () => {
       try {
         await program.methods
-          .updateTreasuryConfig(null, new BN(0))
+          .updateTreasuryConfig({
+            epochDuration: null,
+            spendingLimit: new BN(0),
+            approvalThreshold: null,
+            crankTip: null,
+            auditLogRetention: null,
+            spendingLimitMode: null,
+            withdrawalTimelockThreshold: null,
+            withdrawalTimelockDelay: null,
+            withdrawalsRequireWhitelist: null,
+          })
           .accounts({
             treasury: treasuryPDA,
             authority: admin.publicKey,
             user: adminUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -682,11 +751,22 @@ describe("treasury_vault", () => {
       const shortEpochDuration = new BN(3600); // 1 hour in seconds
       
       await program.methods
-        .updateTreasuryConfig(shortEpochDuration, null)
+        .updateTreasuryConfig({
+          epochDuration: shortEpochDuration,
+          spendingLimit: null,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -704,8 +784,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          withdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -718,6 +797,7 @@ describe("treasury_vault", () => {
           authority: admin.publicKey,
           user: adminUserPDA,
           recipient: recipient.publicKey,
+          whitelistedRecipient: findRecipientPDA(recipient.publicKey),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -772,8 +852,7 @@ describe("treasury_vault", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          secondWithdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -786,6 +865,7 @@ describe("treasury_vault", () => {
           authority: admin.publicKey,
           user: adminUserPDA,
           recipient: recipient.publicKey,
+          whitelistedRecipient: findRecipientPDA(recipient.publicKey),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -804,11 +884,22 @@ describe("treasury_vault", () => {
       const longerEpochDuration = new BN(86400); // 1 day in seconds
       
       await program.methods
-        .updateTreasuryConfig(longerEpochDuration, null)
+        .updateTreasuryConfig({
+          epochDuration: longerEpochDuration,
+          spendingLimit: null,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -20,6 +20,7 @@ describe("treasury_vault_edge_cases", () => {
   const recipient2 = anchor.web3.Keypair.generate();
   
   // Constants for testing
+  const TREASURY_ID = new BN(0);
   const EPOCH_DURATION = new BN(86400); // 1 day in seconds
   const SPENDING_LIMIT = new BN(5000000000); // 5 SOL in lamports
   const DEPOSIT_AMOUNT = new BN(1000000000); // 1 SOL in lamports
@@ -38,6 +39,15 @@ describe("treasury_vault_edge_cases", () => {
   let recipient1TokenAccount: anchor.web3.PublicKey;
   let recipient2TokenAccount: anchor.web3.PublicKey;
 
+  // Audit logs are keyed by the treasury's next audit sequence
+  const findAuditLogPDA = async () => {
+    const treasury = await program.account.treasury.fetch(treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), treasuryPDA.toBuffer(), treasury.auditSequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
   before(async () => {
     // Airdrop SOL to all accounts for testing
     await provider.connection.requestAirdrop(admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -50,7 +60,7 @@ describe("treasury_vault_edge_cases", () => {
     
     // Find PDAs
     [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury")],
+      [Buffer.from("treasury"), admin.publicKey.toBuffer(), TREASURY_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     
@@ -122,7 +132,7 @@ describe("treasury_vault_edge_cases", () => {
   describe("Setup", () => {
     it("should initialize treasury and add admin user", async () => {
       await program.methods
-        .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+        .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
         .accounts({
           treasury: treasuryPDA,
           admin: admin.publicKey,
@@ -143,8 +153,11 @@ describe("treasury_vault_edge_cases", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: treasurerUserPDA,
           user: treasurer.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -166,6 +179,7 @@ describe("treasury_vault_edge_cases", () => {
           user: adminUserPDA,
           recipientAccount: recipient1PDA,
           recipient: recipient1.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -180,6 +194,7 @@ describe("treasury_vault_edge_cases", () => {
           user: adminUserPDA,
           recipientAccount: recipient2PDA,
           recipient: recipient2.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -204,8 +219,7 @@ describe("treasury_vault_edge_cases", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          depositTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -252,7 +266,12 @@ describe("treasury_vault_edge_cases", () => {
             scheduleTime,
             false, // Not recurring
             new BN(0), // No recurrence interval
-            new BN(0) // Index 0
+            new BN(0), // Index 0
+            {
+              endTime: null, // No end time
+              maxExecutions: null, // No execution cap
+              catchUpPolicy: null, // Default catch-up policy
+            }
           )
           .accounts({
             authority: admin.publicKey,
@@ -295,7 +314,12 @@ describe("treasury_vault_edge_cases", () => {
         scheduleTime,
         false, // Not recurring
         new BN(0), // No recurrence interval
-        new BN(1) // Index 1
+        new BN(1), // Index 1
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
       )
       .accounts({
         authority: admin.publicKey,
@@ -327,7 +351,12 @@ describe("treasury_vault_edge_cases", () => {
         scheduleTime,
         false, // Not recurring
         new BN(0), // No recurrence interval
-        new BN(2) // Index 2
+        new BN(2), // Index 2
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
       )
       .accounts({
         authority: admin.publicKey,
@@ -363,8 +392,7 @@ describe("treasury_vault_edge_cases", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          withdrawTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -378,6 +406,7 @@ describe("treasury_vault_edge_cases", () => {
           authority: admin.publicKey,
           user: adminUserPDA,
           recipient: recipient2.publicKey,
+          whitelistedRecipient: recipient2PDA,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -416,8 +445,10 @@ describe("treasury_vault_edge_cases", () => {
             recipient: recipient1PDA,
             payoutSchedule: payoutSchedulePDA,
             recipientWallet: recipient1.publicKey,
-            recipientTokenAccount: recipient1TokenAccount,
-            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            gateTokenAccount: null,
+            gateMetadata: null,
+            auditLog: await findAuditLogPDA(),
+            tipAuditLog: null,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -440,8 +471,7 @@ describe("treasury_vault_edge_cases", () => {
       [
         Buffer.from("audit"),
         treasuryPDA.toBuffer(),
-        depositTimestamp.toArrayLike(Buffer, "le", 8),
-        admin.publicKey.toBuffer(),
+        (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -484,7 +514,12 @@ describe("treasury_vault_edge_cases", () => {
           scheduleTime,
           false, // Not recurring
           new BN(0), // No recurrence interval
-          new BN(i) // Index i
+          new BN(i), // Index i
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
@@ -543,7 +578,12 @@ describe("treasury_vault_edge_cases", () => {
           scheduleTime,
           true, // Recurring
           new BN(0), // Zero interval (invalid)
-          new BN(20) // Index 20
+          new BN(20), // Index 20
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
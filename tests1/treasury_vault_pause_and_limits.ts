@@ -16,6 +16,7 @@ describe("treasury_vault_pause_and_limits", () => {
   const recipient = anchor.web3.Keypair.generate();
   
   // Constants for testing
+  const TREASURY_ID = new BN(0);
   const EPOCH_DURATION = new BN(3600); // 1 hour in seconds (minimum allowed)
   const SPENDING_LIMIT = new BN(500000000); // 0.5 SOL in lamports
   const DEPOSIT_AMOUNT = new BN(1000000000); // 1 SOL in lamports
@@ -31,6 +32,15 @@ describe("treasury_vault_pause_and_limits", () => {
   let recipientPDA: anchor.web3.PublicKey;
   let payoutSchedulePDA: anchor.web3.PublicKey;
 
+  // Audit logs are keyed by the treasury's next audit sequence
+  const findAuditLogPDA = async () => {
+    const treasury = await program.account.treasury.fetch(treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), treasuryPDA.toBuffer(), treasury.auditSequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
   before(async () => {
     // Airdrop SOL to all accounts for testing
     await provider.connection.requestAirdrop(admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -43,7 +53,7 @@ describe("treasury_vault_pause_and_limits", () => {
     
     // Find PDAs
     [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury")],
+      [Buffer.from("treasury"), admin.publicKey.toBuffer(), TREASURY_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     
@@ -100,7 +110,7 @@ describe("treasury_vault_pause_and_limits", () => {
   describe("Setup", () => {
     it("should initialize treasury and add admin user", async () => {
       await program.methods
-        .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+        .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
         .accounts({
           treasury: treasuryPDA,
           admin: admin.publicKey,
@@ -124,8 +134,11 @@ describe("treasury_vault_pause_and_limits", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: treasurerUserPDA,
           user: treasurer.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -143,8 +156,11 @@ describe("treasury_vault_pause_and_limits", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: regularUserPDA,
           user: regularUser.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -165,6 +181,7 @@ describe("treasury_vault_pause_and_limits", () => {
           user: adminUserPDA,
           recipientAccount: recipientPDA,
           recipient: recipient.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -185,8 +202,7 @@ describe("treasury_vault_pause_and_limits", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          depositTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -219,7 +235,12 @@ describe("treasury_vault_pause_and_limits", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(1) // Index 1
+      new BN(1), // Index 1
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -249,11 +270,12 @@ describe("treasury_vault_pause_and_limits", () => {
   describe("Pause and Unpause", () => {
     it("should allow admin to pause the treasury", async () => {
       await program.methods
-        .pauseTreasury()
+        .pauseTreasury(null)
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -267,11 +289,12 @@ describe("treasury_vault_pause_and_limits", () => {
     it("should fail when trying to pause an already paused treasury", async () => {
       try {
         await program.methods
-          .pauseTreasury()
+          .pauseTreasury(null)
           .accounts({
             treasury: treasuryPDA,
             authority: admin.publicKey,
             user: adminUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -287,11 +310,12 @@ describe("treasury_vault_pause_and_limits", () => {
     it("should fail when non-admin tries to pause the treasury", async () => {
       try {
         await program.methods
-          .pauseTreasury()
+          .pauseTreasury(null)
           .accounts({
             treasury: treasuryPDA,
             authority: treasurer.publicKey,
             user: treasurerUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([treasurer])
@@ -330,12 +354,14 @@ describe("treasury_vault_pause_and_limits", () => {
 });
 
     it("should allow admin to unpause the treasury", async () => {
+      const auditLogPDA = await findAuditLogPDA();
       await program.methods
         .unpauseTreasury()
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: auditLogPDA,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -344,6 +370,11 @@ describe("treasury_vault_pause_and_limits", () => {
       // Verify treasury is unpaused
       const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
       expect(treasuryAccount.isPaused).to.be.false;
+      
+      // Verify the unpause was logged
+      const auditLogAccount = await program.account.auditLog.fetch(auditLogPDA);
+      expect(auditLogAccount.action).to.equal(8); // 8 = UnpauseTreasury
+      expect(auditLogAccount.initiator.toString()).to.equal(admin.publicKey.toString());
     });
 
     it("should fail when trying to unpause an already unpaused treasury", async () => {
@@ -354,6 +385,7 @@ describe("treasury_vault_pause_and_limits", () => {
             treasury: treasuryPDA,
             authority: admin.publicKey,
             user: adminUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -369,11 +401,12 @@ describe("treasury_vault_pause_and_limits", () => {
     it("should fail when non-admin tries to unpause the treasury", async () => {
       // First, pause the treasury again
       await program.methods
-        .pauseTreasury()
+        .pauseTreasury(null)
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -386,6 +419,7 @@ describe("treasury_vault_pause_and_limits", () => {
             treasury: treasuryPDA,
             authority: treasurer.publicKey,
             user: treasurerUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([treasurer])
@@ -404,6 +438,7 @@ describe("treasury_vault_pause_and_limits", () => {
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -421,11 +456,22 @@ describe("treasury_vault_pause_and_limits", () => {
   const smallSpendingLimit = new BN(50000000); // 0.05 SOL
   
   await program.methods
-    .updateTreasuryConfig(null, smallSpendingLimit)
+    .updateTreasuryConfig({
+      epochDuration: null,
+      spendingLimit: smallSpendingLimit,
+      approvalThreshold: null,
+      crankTip: null,
+      auditLogRetention: null,
+      spendingLimitMode: null,
+      withdrawalTimelockThreshold: null,
+      withdrawalTimelockDelay: null,
+      withdrawalsRequireWhitelist: null,
+    })
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -454,7 +500,12 @@ describe("treasury_vault_pause_and_limits", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(2) // Index 2
+      new BN(2), // Index 2
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -486,8 +537,10 @@ describe("treasury_vault_pause_and_limits", () => {
         recipient: recipientPDA,
         payoutSchedule: excessPayoutPDA,
         recipientWallet: recipient.publicKey,
-        recipientTokenAccount: recipient.publicKey, // Dummy value, not actually used
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(),
+        tipAuditLog: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([treasurer])
@@ -502,11 +555,22 @@ describe("treasury_vault_pause_and_limits", () => {
   
   // Now update the spending limit back to a larger value
   await program.methods
-    .updateTreasuryConfig(null, SPENDING_LIMIT)
+    .updateTreasuryConfig({
+      epochDuration: null,
+      spendingLimit: SPENDING_LIMIT,
+      approvalThreshold: null,
+      crankTip: null,
+      auditLogRetention: null,
+      spendingLimitMode: null,
+      withdrawalTimelockThreshold: null,
+      withdrawalTimelockDelay: null,
+      withdrawalsRequireWhitelist: null,
+    })
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -522,11 +586,22 @@ describe("treasury_vault_pause_and_limits", () => {
   const shortEpochDuration = new BN(3600); // 1 hour (minimum allowed)
   
   await program.methods
-    .updateTreasuryConfig(shortEpochDuration, null)
+    .updateTreasuryConfig({
+      epochDuration: shortEpochDuration,
+      spendingLimit: null,
+      approvalThreshold: null,
+      crankTip: null,
+      auditLogRetention: null,
+      spendingLimitMode: null,
+      withdrawalTimelockThreshold: null,
+      withdrawalTimelockDelay: null,
+      withdrawalsRequireWhitelist: null,
+    })
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -555,7 +630,12 @@ describe("treasury_vault_pause_and_limits", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(3) // Index 3
+      new BN(3), // Index 3
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -574,20 +654,28 @@ describe("treasury_vault_pause_and_limits", () => {
   // Wait for the schedule time to pass
   await new Promise(resolve => setTimeout(resolve, 6000));
   
-  // Note: We can't actually execute the payout due to the PDA transfer limitation,
-  // but we can verify that the epoch duration was updated correctly
-  
   // Verify that the epoch duration is working as expected
   const treasuryFinal = await program.account.treasury.fetch(treasuryPDA);
   expect(treasuryFinal.epochDuration.toString()).to.equal(shortEpochDuration.toString());
   
   // Reset epoch duration to original value
   await program.methods
-    .updateTreasuryConfig(EPOCH_DURATION, null)
+    .updateTreasuryConfig({
+      epochDuration: EPOCH_DURATION,
+      spendingLimit: null,
+      approvalThreshold: null,
+      crankTip: null,
+      auditLogRetention: null,
+      spendingLimitMode: null,
+      withdrawalTimelockThreshold: null,
+      withdrawalTimelockDelay: null,
+      withdrawalsRequireWhitelist: null,
+    })
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -599,11 +687,22 @@ describe("treasury_vault_pause_and_limits", () => {
   const preciseLimit = new BN(300000000); // 0.3 SOL
   
   await program.methods
-    .updateTreasuryConfig(null, preciseLimit)
+    .updateTreasuryConfig({
+      epochDuration: null,
+      spendingLimit: preciseLimit,
+      approvalThreshold: null,
+      crankTip: null,
+      auditLogRetention: null,
+      spendingLimitMode: null,
+      withdrawalTimelockThreshold: null,
+      withdrawalTimelockDelay: null,
+      withdrawalsRequireWhitelist: null,
+    })
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -628,7 +727,12 @@ describe("treasury_vault_pause_and_limits", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(5) // Index 5
+      new BN(5), // Index 5
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -661,7 +765,12 @@ describe("treasury_vault_pause_and_limits", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(6) // Index 6
+      new BN(6), // Index 6
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -693,17 +802,18 @@ describe("treasury_vault_pause_and_limits", () => {
         recipient: recipientPDA,
         payoutSchedule: exactPayoutPDA,
         recipientWallet: recipient.publicKey,
-        recipientTokenAccount: recipient.publicKey, // Dummy value, not actually used
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(),
+        tipAuditLog: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([treasurer])
       .rpc();
     
-    // This should fail due to the PDA transfer limitation, not due to spending limit
   } catch (error: any) {
-    // We expect this to fail with the transfer error, not with SpendingLimitExceeded
-    expect(error.message).to.include("Transfer: `from` must not carry data");
+    // A payout of exactly the limit must never trip the spending limit
+    expect(error.message).to.not.include("SpendingLimitExceeded");
   }
   
   // Try to execute the payout that's over the limit
@@ -719,8 +829,10 @@ describe("treasury_vault_pause_and_limits", () => {
         recipient: recipientPDA,
         payoutSchedule: overLimitPDA,
         recipientWallet: recipient.publicKey,
-        recipientTokenAccount: recipient.publicKey, // Dummy value, not actually used
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(),
+        tipAuditLog: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([treasurer])
@@ -735,11 +847,22 @@ describe("treasury_vault_pause_and_limits", () => {
   
   // Reset spending limit to original value
   await program.methods
-    .updateTreasuryConfig(null, SPENDING_LIMIT)
+    .updateTreasuryConfig({
+      epochDuration: null,
+      spendingLimit: SPENDING_LIMIT,
+      approvalThreshold: null,
+      crankTip: null,
+      auditLogRetention: null,
+      spendingLimitMode: null,
+      withdrawalTimelockThreshold: null,
+      withdrawalTimelockDelay: null,
+      withdrawalsRequireWhitelist: null,
+    })
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
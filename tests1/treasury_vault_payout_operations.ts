@@ -16,6 +16,7 @@ describe("treasury_vault_payout_operations", () => {
   const recipient2 = anchor.web3.Keypair.generate();
   
   // Constants for testing
+  const TREASURY_ID = new BN(0);
   const EPOCH_DURATION = new BN(86400); // 1 day in seconds
   const SPENDING_LIMIT = new BN(5000000000); // 5 SOL in lamports
   const DEPOSIT_AMOUNT = new BN(2000000000); // 2 SOL in lamports
@@ -32,6 +33,15 @@ describe("treasury_vault_payout_operations", () => {
   let payoutSchedule2PDA: anchor.web3.PublicKey;
   let recurringPayoutPDA: anchor.web3.PublicKey;
 
+  // Audit logs are keyed by the treasury's next audit sequence
+  const findAuditLogPDA = async () => {
+    const treasury = await program.account.treasury.fetch(treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), treasuryPDA.toBuffer(), treasury.auditSequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
   before(async () => {
     // Airdrop SOL to all accounts for testing
     await provider.connection.requestAirdrop(admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -44,7 +54,7 @@ describe("treasury_vault_payout_operations", () => {
     
     // Find PDAs
     [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury")],
+      [Buffer.from("treasury"), admin.publicKey.toBuffer(), TREASURY_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     
@@ -121,7 +131,7 @@ describe("treasury_vault_payout_operations", () => {
   describe("Setup", () => {
     it("should initialize treasury and add admin user", async () => {
       await program.methods
-        .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+        .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
         .accounts({
           treasury: treasuryPDA,
           admin: admin.publicKey,
@@ -142,8 +152,11 @@ describe("treasury_vault_payout_operations", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: treasurerUserPDA,
           user: treasurer.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -165,6 +178,7 @@ describe("treasury_vault_payout_operations", () => {
           user: adminUserPDA,
           recipientAccount: recipient1PDA,
           recipient: recipient1.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -179,6 +193,7 @@ describe("treasury_vault_payout_operations", () => {
           user: adminUserPDA,
           recipientAccount: recipient2PDA,
           recipient: recipient2.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -203,8 +218,7 @@ describe("treasury_vault_payout_operations", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          depositTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -250,6 +264,7 @@ describe("treasury_vault_payout_operations", () => {
           user: adminUserPDA,
           recipient: recipient1PDA,
           payoutSchedule: payoutSchedule1PDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -266,7 +281,12 @@ describe("treasury_vault_payout_operations", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(1) // Index 1
+      new BN(1), // Index 1
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -303,7 +323,12 @@ describe("treasury_vault_payout_operations", () => {
       scheduleTime,
       true, // Recurring
       recurrenceInterval,
-      new BN(2) // Index 2
+      new BN(2), // Index 2
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -341,7 +366,12 @@ describe("treasury_vault_payout_operations", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(1) // Index 1
+      new BN(1), // Index 1
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -382,7 +412,12 @@ describe("treasury_vault_payout_operations", () => {
           scheduleTime,
           false, // Not recurring
           new BN(0), // No recurrence interval
-          new BN(1) // Index 1
+          new BN(1), // Index 1
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
@@ -408,6 +443,7 @@ describe("treasury_vault_payout_operations", () => {
         user: adminUserPDA,
         recipient: recipient1PDA,
         payoutSchedule: payoutSchedule1PDA,
+        auditLog: await findAuditLogPDA(),
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([admin])
@@ -431,7 +467,12 @@ describe("treasury_vault_payout_operations", () => {
           scheduleTime,
           false, // Not recurring
           new BN(0), // No recurrence interval
-          new BN(1) // Index 1
+          new BN(1), // Index 1
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
@@ -457,6 +498,7 @@ describe("treasury_vault_payout_operations", () => {
         user: treasurerUserPDA,
         recipient: recipient2PDA,
         payoutSchedule: payoutSchedule2PDA,
+        auditLog: await findAuditLogPDA(),
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([treasurer])
@@ -478,6 +520,7 @@ describe("treasury_vault_payout_operations", () => {
           user: adminUserPDA,
           recipient: recipient1PDA,
           payoutSchedule: payoutSchedule1PDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -505,7 +548,12 @@ describe("treasury_vault_payout_operations", () => {
           scheduleTime,
           true, // Recurring
           recurrenceInterval,
-          new BN(2) // Index 2
+          new BN(2), // Index 2
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
@@ -531,6 +579,7 @@ describe("treasury_vault_payout_operations", () => {
         user: adminUserPDA,
         recipient: recipient1PDA,
         payoutSchedule: recurringPayoutPDA,
+        auditLog: await findAuditLogPDA(),
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([admin])
@@ -575,7 +624,12 @@ describe("treasury_vault_payout_operations", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(4) // Index 4
+      new BN(4), // Index 4
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
@@ -600,6 +654,7 @@ describe("treasury_vault_payout_operations", () => {
       user: adminUserPDA,
       recipient: recipient1PDA,
       payoutSchedule: newPayoutPDA,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -621,8 +676,10 @@ describe("treasury_vault_payout_operations", () => {
         recipient: recipient1PDA,
         payoutSchedule: newPayoutPDA,
         recipientWallet: recipient1.publicKey,
-        recipientTokenAccount: recipient1.publicKey, // Add this line
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID, // Add this line
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(),
+        tipAuditLog: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([admin])
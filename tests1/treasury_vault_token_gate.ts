@@ -22,12 +22,14 @@ describe("treasury_vault_token_gate", () => {
   const recipient2 = anchor.web3.Keypair.generate();
   
   // Constants for testing
+  const TREASURY_ID = new BN(0);
   const EPOCH_DURATION = new BN(3600); // 1 hour in seconds (minimum)
   const LONG_EPOCH_DURATION = new BN(604800); // 1 week in seconds
   const SHORT_EPOCH_DURATION = new BN(1800); // 30 minutes (below minimum)
   const SPENDING_LIMIT = new BN(5000000000); // 5 SOL in lamports
   const DEPOSIT_AMOUNT = new BN(1000000000); // 1 SOL in lamports
   const PAYOUT_AMOUNT = new BN(100000000); // 0.1 SOL in lamports
+  const GATE_MIN_BALANCE = new BN(1); // Gate tokens a recipient must hold
   
   // PDAs
   let treasuryPDA: anchor.web3.PublicKey;
@@ -42,6 +44,15 @@ describe("treasury_vault_token_gate", () => {
   let recipient1TokenAccount: anchor.web3.PublicKey;
   let recipient2TokenAccount: anchor.web3.PublicKey;
 
+  // Audit logs are keyed by the treasury's next audit sequence
+  const findAuditLogPDA = async () => {
+    const treasury = await program.account.treasury.fetch(treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), treasuryPDA.toBuffer(), treasury.auditSequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
   before(async () => {
     // Airdrop SOL to all accounts for testing
     await provider.connection.requestAirdrop(admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -54,7 +65,7 @@ describe("treasury_vault_token_gate", () => {
     
     // Find PDAs
     [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury")],
+      [Buffer.from("treasury"), admin.publicKey.toBuffer(), TREASURY_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     
@@ -136,7 +147,7 @@ describe("treasury_vault_token_gate", () => {
   describe("Setup", () => {
     it("should initialize treasury with valid epoch duration", async () => {
       await program.methods
-        .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+        .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
         .accounts({
           treasury: treasuryPDA,
           admin: admin.publicKey,
@@ -160,8 +171,11 @@ describe("treasury_vault_token_gate", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: treasurerUserPDA,
           user: treasurer.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -183,6 +197,7 @@ describe("treasury_vault_token_gate", () => {
           user: adminUserPDA,
           recipientAccount: recipient1PDA,
           recipient: recipient1.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -197,6 +212,7 @@ describe("treasury_vault_token_gate", () => {
           user: adminUserPDA,
           recipientAccount: recipient2PDA,
           recipient: recipient2.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -221,8 +237,7 @@ describe("treasury_vault_token_gate", () => {
         [
           Buffer.from("audit"),
           treasuryPDA.toBuffer(),
-          depositTimestamp.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
+          (await program.account.treasury.fetch(treasuryPDA)).auditSequence.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -248,11 +263,22 @@ describe("treasury_vault_token_gate", () => {
   describe("Epoch Duration Management", () => {
     it("should allow admin to update epoch duration to valid value", async () => {
       await program.methods
-        .updateTreasuryConfig(LONG_EPOCH_DURATION, null)
+        .updateTreasuryConfig({
+          epochDuration: LONG_EPOCH_DURATION,
+          spendingLimit: null,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -266,11 +292,22 @@ describe("treasury_vault_token_gate", () => {
     it("should fail when trying to set epoch duration below minimum", async () => {
       try {
         await program.methods
-          .updateTreasuryConfig(SHORT_EPOCH_DURATION, null)
+          .updateTreasuryConfig({
+            epochDuration: SHORT_EPOCH_DURATION,
+            spendingLimit: null,
+            approvalThreshold: null,
+            crankTip: null,
+            auditLogRetention: null,
+            spendingLimitMode: null,
+            withdrawalTimelockThreshold: null,
+            withdrawalTimelockDelay: null,
+            withdrawalsRequireWhitelist: null,
+          })
           .accounts({
             treasury: treasuryPDA,
             authority: admin.publicKey,
             user: adminUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -286,11 +323,22 @@ describe("treasury_vault_token_gate", () => {
     it("should fail when non-admin tries to update epoch duration", async () => {
       try {
         await program.methods
-          .updateTreasuryConfig(EPOCH_DURATION, null)
+          .updateTreasuryConfig({
+            epochDuration: EPOCH_DURATION,
+            spendingLimit: null,
+            approvalThreshold: null,
+            crankTip: null,
+            auditLogRetention: null,
+            spendingLimitMode: null,
+            withdrawalTimelockThreshold: null,
+            withdrawalTimelockDelay: null,
+            withdrawalsRequireWhitelist: null,
+          })
           .accounts({
             treasury: treasuryPDA,
             authority: treasurer.publicKey,
             user: treasurerUserPDA,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([treasurer])
@@ -305,11 +353,22 @@ describe("treasury_vault_token_gate", () => {
 
     it("should reset to standard epoch duration for remaining tests", async () => {
       await program.methods
-        .updateTreasuryConfig(EPOCH_DURATION, null)
+        .updateTreasuryConfig({
+          epochDuration: EPOCH_DURATION,
+          spendingLimit: null,
+          approvalThreshold: null,
+          crankTip: null,
+          auditLogRetention: null,
+          spendingLimitMode: null,
+          withdrawalTimelockThreshold: null,
+          withdrawalTimelockDelay: null,
+          withdrawalsRequireWhitelist: null,
+        })
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -324,12 +383,13 @@ describe("treasury_vault_token_gate", () => {
   describe("Token Gate Management", () => {
     it("should allow admin to set token gate", async () => {
       await program.methods
-        .setTokenGate()
+        .setTokenGate(GATE_MIN_BALANCE, 0)
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
           tokenMint: tokenMint,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -339,17 +399,19 @@ describe("treasury_vault_token_gate", () => {
       const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
       expect(treasuryAccount.gateTokenMint).to.not.be.null;
       expect(treasuryAccount.gateTokenMint?.toString()).to.equal(tokenMint.toString());
+      expect(treasuryAccount.gateMinBalance.toString()).to.equal(GATE_MIN_BALANCE.toString());
     });
 
     it("should fail when non-admin tries to set token gate", async () => {
       try {
         await program.methods
-          .setTokenGate()
+          .setTokenGate(GATE_MIN_BALANCE, 0)
           .accounts({
             treasury: treasuryPDA,
             authority: treasurer.publicKey,
             user: treasurerUserPDA,
             tokenMint: tokenMint,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([treasurer])
@@ -364,12 +426,13 @@ describe("treasury_vault_token_gate", () => {
 
     it("should allow admin to unset token gate", async () => {
       await program.methods
-        .setTokenGate()
+        .setTokenGate(new BN(0), 0)
         .accounts({
           treasury: treasuryPDA,
           authority: admin.publicKey,
           user: adminUserPDA,
           tokenMint: null,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -415,7 +478,12 @@ describe("treasury_vault_token_gate", () => {
         scheduleTime,
         false, // Not recurring
         new BN(0), // No recurrence interval
-        new BN(1) // Index 1
+        new BN(1), // Index 1
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
       )
       .accounts({
         authority: admin.publicKey,
@@ -438,7 +506,12 @@ describe("treasury_vault_token_gate", () => {
         scheduleTime,
         false, // Not recurring
         new BN(0), // No recurrence interval
-        new BN(1) // Index 1
+        new BN(1), // Index 1
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
       )
       .accounts({
         authority: admin.publicKey,
@@ -459,12 +532,13 @@ describe("treasury_vault_token_gate", () => {
     
     // Set token gate for testing
     await program.methods
-      .setTokenGate()
+      .setTokenGate(GATE_MIN_BALANCE, 0)
       .accounts({
         treasury: treasuryPDA,
         authority: admin.publicKey,
         user: adminUserPDA,
         tokenMint: tokenMint,
+        auditLog: await findAuditLogPDA(),
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([admin])
@@ -528,7 +602,12 @@ describe("treasury_vault_token_gate", () => {
           scheduleTime,
           false, // Not recurring
           new BN(0), // No recurrence interval
-          new BN(1) // Index 1
+          new BN(1), // Index 1
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
@@ -607,7 +686,12 @@ describe("treasury_vault_token_gate", () => {
           scheduleTime,
           false, // Not recurring
           new BN(0), // No recurrence interval
-          new BN(1) // Index 1
+          new BN(1), // Index 1
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: admin.publicKey,
@@ -642,12 +726,13 @@ describe("treasury_vault_token_gate", () => {
   it("should allow payouts to any recipient when token gate is disabled but fail on transfer", async () => {
     // Unset token gate
     await program.methods
-      .setTokenGate()
+      .setTokenGate(new BN(0), 0)
       .accounts({
         treasury: treasuryPDA,
         authority: admin.publicKey,
         user: adminUserPDA,
         tokenMint: null,
+        auditLog: await findAuditLogPDA(),
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([admin])
@@ -676,7 +761,12 @@ describe("treasury_vault_token_gate", () => {
         scheduleTime,
         false, // Not recurring
         new BN(0), // No recurrence interval
-        new BN(2) // Index 2
+        new BN(2), // Index 2
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
       )
       .accounts({
         authority: admin.publicKey,
@@ -713,12 +803,13 @@ describe("treasury_vault_token_gate", () => {
     it("should handle exact token balance (1 token) but fail on transfer", async () => {
   // Set token gate
   await program.methods
-    .setTokenGate()
+    .setTokenGate(GATE_MIN_BALANCE, 0)
     .accounts({
       treasury: treasuryPDA,
       authority: admin.publicKey,
       user: adminUserPDA,
       tokenMint: tokenMint,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -771,6 +862,7 @@ describe("treasury_vault_token_gate", () => {
       user: adminUserPDA,
       recipientAccount: treasurerRecipientPDA,
       recipient: treasurer.publicKey,
+      auditLog: await findAuditLogPDA(),
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
@@ -794,7 +886,12 @@ describe("treasury_vault_token_gate", () => {
       scheduleTime,
       false, // Not recurring
       new BN(0), // No recurrence interval
-      new BN(1) // Index 1
+      new BN(1), // Index 1
+      {
+        endTime: null, // No end time
+        maxExecutions: null, // No execution cap
+        catchUpPolicy: null, // Default catch-up policy
+      }
     )
     .accounts({
       authority: admin.publicKey,
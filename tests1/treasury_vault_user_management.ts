@@ -19,6 +19,7 @@ describe("treasury_vault_user_management", () => {
   const recipient3 = anchor.web3.Keypair.generate();
   
   // Constants for testing
+  const TREASURY_ID = new BN(0);
   const EPOCH_DURATION = new BN(86400); // 1 day in seconds
   const SPENDING_LIMIT = new BN(5000000000); // 5 SOL in lamports
   
@@ -33,6 +34,15 @@ describe("treasury_vault_user_management", () => {
   let recipient2PDA: anchor.web3.PublicKey;
   let recipient3PDA: anchor.web3.PublicKey;
 
+  // Audit logs are keyed by the treasury's next audit sequence
+  const findAuditLogPDA = async () => {
+    const treasury = await program.account.treasury.fetch(treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), treasuryPDA.toBuffer(), treasury.auditSequence.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+
   before(async () => {
     // Airdrop SOL to all accounts for testing
     await provider.connection.requestAirdrop(admin.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -48,7 +58,7 @@ describe("treasury_vault_user_management", () => {
     
     // Find PDAs
     [treasuryPDA, treasuryBump] = await anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("treasury")],
+      [Buffer.from("treasury"), admin.publicKey.toBuffer(), TREASURY_ID.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     
@@ -121,7 +131,7 @@ describe("treasury_vault_user_management", () => {
   describe("Initialize Treasury", () => {
     it("should initialize treasury and add admin user", async () => {
       await program.methods
-        .initializeTreasury(EPOCH_DURATION, SPENDING_LIMIT)
+        .initializeTreasury(TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
         .accounts({
           treasury: treasuryPDA,
           admin: admin.publicKey,
@@ -151,8 +161,11 @@ describe("treasury_vault_user_management", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: user1UserPDA,
           user: user1.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -172,8 +185,11 @@ describe("treasury_vault_user_management", () => {
         .accounts({
           admin: admin.publicKey,
           treasury: treasuryPDA,
+          adminUser: adminUserPDA,
           userAccount: user2UserPDA,
           user: user2.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -188,50 +204,26 @@ describe("treasury_vault_user_management", () => {
     });
 
     it("should allow a newly added admin to add another user", async () => {
-      // Looking at the add_treasury_user.rs file, we see that only the treasury admin can add users
-      // The constraint is: constraint = treasury.admin == admin.key() @ ErrorCode::UnauthorizedUser
-      // So we need to update the treasury admin to user2 first
-      
-      // Update treasury admin to user2
+      // Adding users is gated by the admin role, not by the treasury admin key
       await program.methods
-        .updateTreasuryConfig(null, null)
+        .addTreasuryUser(1) // 1 = Treasurer role
         .accounts({
+          admin: user2.publicKey,
           treasury: treasuryPDA,
-          authority: admin.publicKey,
-          user: adminUserPDA,
+          adminUser: user2UserPDA,
+          userAccount: user3UserPDA,
+          user: user3.publicKey,
+          auditLog: await findAuditLogPDA(),
+          proposal: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([admin])
-        .instruction()
-        .then(async (ix) => {
-          // Modify the instruction data to update the admin
-          // This is a workaround since there's no direct method to update the admin
-          // In a real implementation, there should be a proper method for this
-          
-          // For this test, we'll skip this part and just test that a non-treasury admin can't add users
-          expect(true).to.be.true;
-        });
+        .signers([user2])
+        .rpc();
       
-      // Instead, we'll test that only the treasury admin can add users
-      try {
-        await program.methods
-          .addTreasuryUser(1) // 1 = Treasurer role
-          .accounts({
-            admin: user2.publicKey, // user2 is an admin user but not the treasury admin
-            treasury: treasuryPDA,
-            userAccount: user3UserPDA,
-            user: user3.publicKey,
-            systemProgram: anchor.web3.SystemProgram.programId,
-          })
-          .signers([user2])
-          .rpc();
-        
-        // This should fail because user2 is not the treasury admin
-        expect.fail("Expected error was not thrown");
-      } catch (error: any) {
-        // This is expected to fail with UnauthorizedUser
-        expect(error.message).to.include("UnauthorizedUser");
-      }
+      const treasurerAccount = await program.account.treasuryUser.fetch(user3UserPDA);
+      expect(treasurerAccount.user.toString()).to.equal(user3.publicKey.toString());
+      expect(treasurerAccount.role).to.equal(1);
+      expect(treasurerAccount.isActive).to.be.true;
     });
 
     it("should fail when non-admin tries to add a user", async () => {
@@ -253,8 +245,11 @@ describe("treasury_vault_user_management", () => {
           .accounts({
             admin: user1.publicKey, // user1 is a treasurer, not admin
             treasury: treasuryPDA,
+            adminUser: user1UserPDA,
             userAccount: newUserPDA,
             user: newUser.publicKey,
+            auditLog: await findAuditLogPDA(),
+            proposal: null,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([user1])
@@ -281,14 +276,17 @@ describe("treasury_vault_user_management", () => {
       );
       
       try {
-        // Try to add a user with an invalid role (2)
+        // Try to add a user with an invalid role (3)
         await program.methods
-          .addTreasuryUser(2) // Invalid role
+          .addTreasuryUser(3) // Invalid role
           .accounts({
             admin: admin.publicKey,
             treasury: treasuryPDA,
+            adminUser: adminUserPDA,
             userAccount: newUserPDA,
             user: newUser.publicKey,
+            auditLog: await findAuditLogPDA(),
+            proposal: null,
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -297,7 +295,7 @@ describe("treasury_vault_user_management", () => {
         // Should not reach here
         expect.fail("Expected error was not thrown");
       } catch (error: any) {
-        // This should fail because 2 is not a valid role
+        // This should fail because 3 is not a valid role
         expect(error.message).to.include("InvalidRole");
       }
     });
@@ -313,6 +311,7 @@ describe("treasury_vault_user_management", () => {
           user: adminUserPDA,
           recipientAccount: recipient1PDA,
           recipient: recipient1.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([admin])
@@ -335,6 +334,7 @@ describe("treasury_vault_user_management", () => {
           user: user2UserPDA,
           recipientAccount: recipient2PDA,
           recipient: recipient2.publicKey,
+          auditLog: await findAuditLogPDA(),
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([user2])
@@ -362,6 +362,7 @@ describe("treasury_vault_user_management", () => {
             user: user1UserPDA,
             recipientAccount: recipient3PDA,
             recipient: recipient3.publicKey,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([user1])
@@ -386,6 +387,7 @@ describe("treasury_vault_user_management", () => {
             user: adminUserPDA,
             recipientAccount: recipient1PDA, // This account already exists
             recipient: recipient1.publicKey,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([admin])
@@ -426,6 +428,7 @@ describe("treasury_vault_user_management", () => {
             user: adminUserPDA, // This doesn't match the authority
             recipientAccount: newRecipientPDA,
             recipient: newRecipient.publicKey,
+            auditLog: await findAuditLogPDA(),
             systemProgram: anchor.web3.SystemProgram.programId,
           })
           .signers([randomUser])
@@ -439,4 +442,284 @@ describe("treasury_vault_user_management", () => {
       }
     });
   });
+
+  describe("Manage Treasury Users", () => {
+    it("should deactivate a treasurer user", async () => {
+      await program.methods
+        .deactivateTreasuryUser()
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          userAccount: user1UserPDA,
+          proposal: null,
+        })
+        .signers([admin])
+        .rpc();
+      
+      const treasurerAccount = await program.account.treasuryUser.fetch(user1UserPDA);
+      expect(treasurerAccount.isActive).to.be.false;
+    });
+
+    it("should fail to deactivate an already inactive user", async () => {
+      try {
+        await program.methods
+          .deactivateTreasuryUser()
+          .accounts({
+            authority: admin.publicKey,
+            treasury: treasuryPDA,
+            user: adminUserPDA,
+            userAccount: user1UserPDA,
+            proposal: null,
+          })
+          .signers([admin])
+          .rpc();
+        
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("UserAlreadyInactive");
+      }
+    });
+
+    it("should reactivate a deactivated user", async () => {
+      await program.methods
+        .reactivateTreasuryUser()
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          userAccount: user1UserPDA,
+          proposal: null,
+        })
+        .signers([admin])
+        .rpc();
+      
+      const treasurerAccount = await program.account.treasuryUser.fetch(user1UserPDA);
+      expect(treasurerAccount.isActive).to.be.true;
+    });
+
+    it("should fail when a treasurer tries to deactivate a user", async () => {
+      try {
+        await program.methods
+          .deactivateTreasuryUser()
+          .accounts({
+            authority: user1.publicKey,
+            treasury: treasuryPDA,
+            user: user1UserPDA,
+            userAccount: user2UserPDA,
+            proposal: null,
+          })
+          .signers([user1])
+          .rpc();
+        
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedUser");
+      }
+    });
+
+    it("should update a user's role", async () => {
+      await program.methods
+        .updateUserRole(0) // Promote to Admin
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          userAccount: user1UserPDA,
+          proposal: null,
+        })
+        .signers([admin])
+        .rpc();
+      
+      let userAccount = await program.account.treasuryUser.fetch(user1UserPDA);
+      expect(userAccount.role).to.equal(0);
+      
+      await program.methods
+        .updateUserRole(1) // Demote back to Treasurer
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          userAccount: user1UserPDA,
+          proposal: null,
+        })
+        .signers([admin])
+        .rpc();
+      
+      userAccount = await program.account.treasuryUser.fetch(user1UserPDA);
+      expect(userAccount.role).to.equal(1);
+    });
+
+    it("should not allow the last active admin to be removed", async () => {
+      // Deactivate the second admin so only the original admin remains
+      await program.methods
+        .deactivateTreasuryUser()
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          userAccount: user2UserPDA,
+          proposal: null,
+        })
+        .signers([admin])
+        .rpc();
+      
+      try {
+        await program.methods
+          .deactivateTreasuryUser()
+          .accounts({
+            authority: admin.publicKey,
+            treasury: treasuryPDA,
+            user: adminUserPDA,
+            userAccount: adminUserPDA,
+            proposal: null,
+          })
+          .signers([admin])
+          .rpc();
+        
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("LastActiveAdmin");
+      }
+      
+      try {
+        await program.methods
+          .updateUserRole(1)
+          .accounts({
+            authority: admin.publicKey,
+            treasury: treasuryPDA,
+            user: adminUserPDA,
+            userAccount: adminUserPDA,
+            proposal: null,
+          })
+          .signers([admin])
+          .rpc();
+        
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("LastActiveAdmin");
+      }
+      
+      const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.activeAdminCount).to.equal(1);
+    });
+  });
+
+  describe("Manage Whitelisted Recipients", () => {
+    let payoutSchedulePDA: anchor.web3.PublicKey;
+
+    it("should rename and deactivate a recipient", async () => {
+      await program.methods
+        .updateWhitelistedRecipient("Renamed Recipient", false)
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          recipientAccount: recipient2PDA,
+        })
+        .signers([admin])
+        .rpc();
+      
+      const recipientAccount = await program.account.whitelistedRecipient.fetch(recipient2PDA);
+      expect(recipientAccount.name).to.equal("Renamed Recipient");
+      expect(recipientAccount.isActive).to.be.false;
+    });
+
+    it("should fail when a treasurer tries to update a recipient", async () => {
+      try {
+        await program.methods
+          .updateWhitelistedRecipient(null, true)
+          .accounts({
+            authority: user1.publicKey,
+            treasury: treasuryPDA,
+            user: user1UserPDA,
+            recipientAccount: recipient2PDA,
+          })
+          .signers([user1])
+          .rpc();
+        
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedUser");
+      }
+    });
+
+    it("should require active payouts to be passed when removing a recipient", async () => {
+      [payoutSchedulePDA] = await anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("payout"),
+          recipient1.publicKey.toBuffer(),
+          treasuryPDA.toBuffer(),
+          new BN(1).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      
+      await program.methods
+        .schedulePayout(
+          new BN(1000000),
+          new BN(Math.floor(Date.now() / 1000) + 3600),
+          true, // Recurring
+          new BN(86400),
+          new BN(1),
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
+        )
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          recipient: recipient1PDA,
+          payoutSchedule: payoutSchedulePDA,
+          tokenMint: null,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin])
+        .rpc();
+      
+      try {
+        await program.methods
+          .removeWhitelistedRecipient()
+          .accounts({
+            authority: admin.publicKey,
+            treasury: treasuryPDA,
+            user: adminUserPDA,
+            recipientAccount: recipient1PDA,
+          })
+          .signers([admin])
+          .rpc();
+        
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("ActivePayoutsRemaining");
+      }
+    });
+
+    it("should remove a recipient and cancel its payouts", async () => {
+      await program.methods
+        .removeWhitelistedRecipient()
+        .accounts({
+          authority: admin.publicKey,
+          treasury: treasuryPDA,
+          user: adminUserPDA,
+          recipientAccount: recipient1PDA,
+        })
+        .remainingAccounts([
+          { pubkey: payoutSchedulePDA, isWritable: true, isSigner: false },
+        ])
+        .signers([admin])
+        .rpc();
+      
+      const recipientInfo = await provider.connection.getAccountInfo(recipient1PDA);
+      expect(recipientInfo).to.be.null;
+      
+      const payoutSchedule = await program.account.payoutSchedule.fetch(payoutSchedulePDA);
+      expect(payoutSchedule.isActive).to.be.false;
+    });
+  });
 });
\ No newline at end of file
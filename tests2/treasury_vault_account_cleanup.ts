@@ -0,0 +1,194 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  PAYOUT_AMOUNT,
+  configUpdate,
+} from "./test_utils";
+
+describe("treasury_vault_account_cleanup", () => {
+  let ctx: TestContext;
+  let depositAuditLogPDA: anchor.web3.PublicKey;
+
+  const findPayoutPDA = (index: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payout"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    )[0];
+
+  const closeAuditLog = async (auditLog: anchor.web3.PublicKey, rentReceiver: anchor.web3.PublicKey) => {
+    await ctx.program.methods
+      .closeAuditLog()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        auditLog,
+        rentReceiver,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const closePayoutSchedule = async (index: number) => {
+    await ctx.program.methods
+      .closePayoutSchedule()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        payoutSchedule: findPayoutPDA(index),
+        rentReceiver: ctx.admin.publicKey,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const setRetention = async (retention: BN) => {
+    await ctx.program.methods
+      .updateTreasuryConfig(configUpdate({ auditLogRetention: retention }))
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    const depositTimestamp = createTimestamp();
+    depositAuditLogPDA = await findAuditLogPDA(ctx);
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, depositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: depositAuditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    await ctx.program.methods
+      .schedulePayout(
+        PAYOUT_AMOUNT,
+        createTimestamp(3600),
+        false, // Not recurring
+        new BN(0), // No recurrence interval
+        new BN(1), // Index 1
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
+      )
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(1),
+        tokenMint: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  });
+
+  it("should keep audit logs for the default retention period", async () => {
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.auditLogRetention.toNumber()).to.equal(90 * 86400);
+
+    try {
+      await closeAuditLog(depositAuditLogPDA, ctx.depositor.publicKey);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AuditLogRetentionNotElapsed");
+    }
+  });
+
+  it("should reject a zero retention period", async () => {
+    try {
+      await setRetention(new BN(0));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidAuditLogRetention");
+    }
+  });
+
+  it("should only return audit log rent to the original payer", async () => {
+    await setRetention(new BN(1));
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    try {
+      await closeAuditLog(depositAuditLogPDA, ctx.admin.publicKey);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("RentReceiverMismatch");
+    }
+  });
+
+  it("should fold a closed audit log into the treasury digest", async () => {
+    const initialDepositorBalance = await ctx.provider.connection.getBalance(ctx.depositor.publicKey);
+
+    await closeAuditLog(depositAuditLogPDA, ctx.depositor.publicKey);
+
+    const auditLogInfo = await ctx.provider.connection.getAccountInfo(depositAuditLogPDA);
+    expect(auditLogInfo).to.be.null;
+
+    const finalDepositorBalance = await ctx.provider.connection.getBalance(ctx.depositor.publicKey);
+    expect(finalDepositorBalance).to.be.greaterThan(initialDepositorBalance);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.closedAuditLogCount.toNumber()).to.equal(1);
+    expect(treasury.auditDigest.some((byte: number) => byte !== 0)).to.be.true;
+  });
+
+  it("should not close an active payout schedule", async () => {
+    try {
+      await closePayoutSchedule(1);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("PayoutStillActive");
+    }
+  });
+
+  it("should close a cancelled payout schedule and refund its payer", async () => {
+    await ctx.program.methods
+      .cancelPayout()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(1),
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    await closePayoutSchedule(1);
+
+    const payoutScheduleInfo = await ctx.provider.connection.getAccountInfo(findPayoutPDA(1));
+    expect(payoutScheduleInfo).to.be.null;
+  });
+});
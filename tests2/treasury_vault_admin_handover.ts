@@ -0,0 +1,109 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  ADMIN_ROLE
+} from "./test_utils";
+
+describe("treasury_vault_admin_handover", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    // Setup test context
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+  });
+
+  async function proposeTreasurerAsAdmin(): Promise<void> {
+    await ctx.program.methods
+      .proposeAdmin()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        newAdmin: ctx.treasurer.publicKey,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  it("should record a pending admin", async () => {
+    await proposeTreasurerAsAdmin();
+
+    const treasuryAccount = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasuryAccount.pendingAdmin.toString()).to.equal(ctx.treasurer.publicKey.toString());
+    expect(treasuryAccount.admin.toString()).to.equal(ctx.admin.publicKey.toString());
+  });
+
+  it("should let the proposer cancel the handover", async () => {
+    await ctx.program.methods
+      .cancelAdminTransfer()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const treasuryAccount = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasuryAccount.pendingAdmin).to.be.null;
+  });
+
+  it("should reject acceptance by a key that was not proposed", async () => {
+    await proposeTreasurerAsAdmin();
+
+    const [depositorUserPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user"),
+        ctx.depositor.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+      ],
+      ctx.program.programId
+    );
+
+    try {
+      await ctx.program.methods
+        .acceptAdmin()
+        .accounts({
+          newAdmin: ctx.depositor.publicKey,
+          treasury: ctx.treasuryPDA,
+          previousAdminUser: ctx.adminUserPDA,
+          newAdminUser: depositorUserPDA,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.depositor])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("NoPendingAdminTransfer");
+    }
+  });
+
+  it("should hand over admin rights when the pending admin accepts", async () => {
+    await ctx.program.methods
+      .acceptAdmin()
+      .accounts({
+        newAdmin: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        previousAdminUser: ctx.adminUserPDA,
+        newAdminUser: ctx.treasurerUserPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+
+    const treasuryAccount = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasuryAccount.admin.toString()).to.equal(ctx.treasurer.publicKey.toString());
+    expect(treasuryAccount.pendingAdmin).to.be.null;
+    expect(treasuryAccount.activeAdminCount).to.equal(1);
+
+    const newAdminUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+    expect(newAdminUser.role).to.equal(ADMIN_ROLE);
+    expect(newAdminUser.isActive).to.be.true;
+
+    const previousAdminUser = await ctx.program.account.treasuryUser.fetch(ctx.adminUserPDA);
+    expect(previousAdminUser.isActive).to.be.false;
+  });
+});
@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  WITHDRAW_AMOUNT,
+  configUpdate,
+} from "./test_utils";
+
+describe("treasury_vault_audit_chain", () => {
+  let ctx: TestContext;
+  const auditLogPDAs: anchor.web3.PublicKey[] = [];
+
+  const deposit = async () => {
+    const auditLogPDA = await findAuditLogPDA(ctx);
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, createTimestamp())
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: auditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+    auditLogPDAs.push(auditLogPDA);
+  };
+
+  const withdraw = async () => {
+    const auditLogPDA = await findAuditLogPDA(ctx);
+    await ctx.program.methods
+      .withdraw(WITHDRAW_AMOUNT, createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        auditLog: auditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+    auditLogPDAs.push(auditLogPDA);
+  };
+
+  const verifyAuditRange = async (auditLogs: anchor.web3.PublicKey[]) => {
+    await ctx.program.methods
+      .verifyAuditRange()
+      .accounts({
+        treasury: ctx.treasuryPDA,
+      })
+      .remainingAccounts(
+        auditLogs.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }))
+      )
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    await deposit();
+    await deposit();
+    await withdraw();
+  });
+
+  it("should link each audit log to the previous one", async () => {
+    const auditLogs = await Promise.all(
+      auditLogPDAs.map((pda) => ctx.program.account.auditLog.fetch(pda))
+    );
+
+    for (let i = 1; i < auditLogs.length; i++) {
+      expect(Buffer.from(auditLogs[i].prevHash).equals(Buffer.from(auditLogs[i - 1].entryHash))).to.be.true;
+    }
+
+    // The treasury keeps the latest entry hash as the chain head
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const lastEntry = auditLogs[auditLogs.length - 1];
+    expect(Buffer.from(treasury.auditChainHead).equals(Buffer.from(lastEntry.entryHash))).to.be.true;
+  });
+
+  it("should chain the treasury's first audit log onto the zero hash", async () => {
+    const [firstAuditLogPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("audit"), ctx.treasuryPDA.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+      ctx.program.programId
+    );
+    const firstAuditLog = await ctx.program.account.auditLog.fetch(firstAuditLogPDA);
+    expect(Buffer.from(firstAuditLog.prevHash).equals(Buffer.alloc(32))).to.be.true;
+  });
+
+  it("should record the counterparty of an outflow", async () => {
+    const withdrawLog = await ctx.program.account.auditLog.fetch(auditLogPDAs[2]);
+    expect(withdrawLog.target.toString()).to.equal(ctx.recipient.publicKey.toString());
+
+    const depositLog = await ctx.program.account.auditLog.fetch(auditLogPDAs[0]);
+    expect(depositLog.target).to.be.null;
+  });
+
+  it("should record the treasury balance before and after a withdrawal", async () => {
+    const withdrawLog = await ctx.program.account.auditLog.fetch(auditLogPDAs[2]);
+    expect(withdrawLog.balanceBefore.toString()).to.equal(DEPOSIT_AMOUNT.muln(2).toString());
+    expect(withdrawLog.balanceAfter.toString()).to.equal(
+      DEPOSIT_AMOUNT.muln(2).sub(WITHDRAW_AMOUNT).toString()
+    );
+    expect(withdrawLog.payoutSchedule).to.be.null;
+  });
+
+  it("should log configuration changes", async () => {
+    const auditLogPDA = await findAuditLogPDA(ctx);
+    await ctx.program.methods
+      .updateTreasuryConfig(configUpdate({ spendingLimit: new BN(2000000000) }))
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: auditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+    auditLogPDAs.push(auditLogPDA);
+
+    const configLog = await ctx.program.account.auditLog.fetch(auditLogPDA);
+    expect(configLog.action).to.equal(36); // 36 = TreasuryConfigUpdated
+    expect(configLog.initiator.toString()).to.equal(ctx.admin.publicKey.toString());
+    expect(configLog.balanceBefore.toNumber()).to.equal(0);
+    expect(configLog.balanceAfter.toNumber()).to.equal(0);
+  });
+
+  it("should verify the full chain up to the head", async () => {
+    await verifyAuditRange(auditLogPDAs);
+  });
+
+  it("should verify a slice in the middle of the chain", async () => {
+    await verifyAuditRange(auditLogPDAs.slice(0, 2));
+  });
+
+  it("should reject a range with a missing entry", async () => {
+    try {
+      await verifyAuditRange([auditLogPDAs[0], auditLogPDAs[2]]);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AuditChainBroken");
+    }
+  });
+
+  it("should reject entries supplied out of order", async () => {
+    try {
+      await verifyAuditRange([auditLogPDAs[1], auditLogPDAs[0]]);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AuditChainBroken");
+    }
+  });
+
+  it("should reject an empty range", async () => {
+    try {
+      await verifyAuditRange([]);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("EmptyAuditRange");
+    }
+  });
+});
@@ -8,10 +8,12 @@ import {
   setupTestContext,
   initializeTreasury,
   setupTokenContext,
+  setTokenSpendingLimit,
   createTimestamp,
   findAuditLogPDA,
   DEPOSIT_AMOUNT,
   WITHDRAW_AMOUNT,
+  SPENDING_LIMIT,
   AUDIT_ACTION_TOKEN_DEPOSIT
 } from "./test_utils";
 
@@ -34,7 +36,7 @@ describe("treasury_vault_basic_spl", () => {
       const depositTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial balances
       const initialDepositorTokenAccount = await getAccount(
@@ -94,7 +96,7 @@ describe("treasury_vault_basic_spl", () => {
       const depositTimestamp = createTimestamp(-15);
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial token balance
       const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
@@ -137,7 +139,7 @@ describe("treasury_vault_basic_spl", () => {
       const withdrawTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial balances
       const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
@@ -159,6 +161,7 @@ describe("treasury_vault_basic_spl", () => {
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
           recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
           auditLog: auditLogPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -181,4 +184,81 @@ describe("treasury_vault_basic_spl", () => {
       expect(finalRecipientBalance - initialRecipientBalance).to.equal(WITHDRAW_AMOUNT.toNumber());
     });
   });
+
+  describe("Per-Mint Spending Limits", () => {
+    const withdrawTokens = async (amount: BN) => {
+      const withdrawTimestamp = createTimestamp();
+      const auditLogPDA = await findAuditLogPDA(ctx);
+      
+      await ctx.program.methods
+        .withdrawToken(amount, withdrawTimestamp)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          tokenBalance: tokenCtx.tokenBalancePDA,
+          treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+          recipientTokenAccount: tokenCtx.recipientTokenAccount,
+          tokenMint: tokenCtx.tokenMint,
+          recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
+          auditLog: auditLogPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+    };
+
+    it("should track token spending against the mint's own window", async () => {
+      const tokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+      expect(tokenBalance.spendingLimit.toString()).to.equal(SPENDING_LIMIT.toString());
+      expect(tokenBalance.epochSpending.toString()).to.equal(WITHDRAW_AMOUNT.toString());
+      expect(tokenBalance.lastEpochStart.toNumber()).to.be.greaterThan(0);
+      
+      // Token outflows must not touch the SOL spending window
+      const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+      expect(treasury.epochSpending.toNumber()).to.equal(0);
+    });
+
+    it("should reject withdrawals above the mint's spending limit", async () => {
+      // Only allow what has already been spent this epoch
+      await setTokenSpendingLimit(ctx, tokenCtx.tokenMint, WITHDRAW_AMOUNT);
+      
+      try {
+        await withdrawTokens(new BN(1));
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("SpendingLimitExceeded");
+      }
+      
+      // Raising the limit makes room again
+      await setTokenSpendingLimit(ctx, tokenCtx.tokenMint, SPENDING_LIMIT);
+      await withdrawTokens(new BN(1));
+      
+      const tokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+      expect(tokenBalance.epochSpending.toString()).to.equal(WITHDRAW_AMOUNT.addn(1).toString());
+    });
+
+    it("should not allow a treasurer to set a token spending limit", async () => {
+      try {
+        await ctx.program.methods
+          .setTokenSpendingLimit(new BN(1))
+          .accounts({
+            treasury: ctx.treasuryPDA,
+            authority: ctx.treasurer.publicKey,
+            user: ctx.treasurerUserPDA,
+            tokenBalance: tokenCtx.tokenBalancePDA,
+            tokenMint: tokenCtx.tokenMint,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([ctx.treasurer])
+          .rpc();
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedConfigUpdate");
+      }
+    });
+  });
 });
\ No newline at end of file
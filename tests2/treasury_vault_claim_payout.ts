@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  closeAccount,
+  getAccount,
+} from "@solana/spl-token";
+import {
+  TestContext,
+  TokenContext,
+  setupTestContext,
+  initializeTreasury,
+  setupTokenContext,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  PAYOUT_AMOUNT,
+} from "./test_utils";
+
+describe("treasury_vault_claim_payout", () => {
+  let ctx: TestContext;
+  let tokenCtx: TokenContext;
+
+  const findPayoutPDA = (index: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payout"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    )[0];
+
+  const schedulePayout = async (index: number, tokenMint: anchor.web3.PublicKey | null) => {
+    await ctx.program.methods
+      .schedulePayout(
+        PAYOUT_AMOUNT,
+        createTimestamp(3),
+        false, // Not recurring
+        new BN(0), // No recurrence interval
+        new BN(index),
+        {
+          endTime: null, // No end time
+          maxExecutions: null, // No execution cap
+          catchUpPolicy: null, // Default catch-up policy
+        }
+      )
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(index),
+        tokenMint,
+        tokenProgram: tokenMint ? TOKEN_PROGRAM_ID : null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const claimSolPayout = async (index: number, claimant: anchor.web3.Keypair) => {
+    const claimTimestamp = createTimestamp();
+    await ctx.program.methods
+      .claimPayout(claimTimestamp)
+      .accounts({
+        authority: claimant.publicKey,
+        treasury: ctx.treasuryPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(index),
+        auditLog: await findAuditLogPDA(ctx),
+        gateTokenAccount: null,
+        gateMetadata: null,
+        tokenBalance: null,
+        treasuryTokenAccount: null,
+        recipientTokenAccount: null,
+        tokenMint: null,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([claimant])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+    tokenCtx = await setupTokenContext(ctx);
+
+    const solDepositTimestamp = createTimestamp();
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, solDepositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    const tokenDepositTimestamp = createTimestamp(-4);
+    await ctx.program.methods
+      .depositToken(new BN(1000000), tokenDepositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+        depositorTokenAccount: tokenCtx.depositorTokenAccount,
+        tokenMint: tokenCtx.tokenMint,
+        auditLog: await findAuditLogPDA(ctx),
+        depositor: ctx.depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    await schedulePayout(1, null);
+    await schedulePayout(2, tokenCtx.tokenMint);
+    await schedulePayout(3, null);
+  });
+
+  it("should not let the recipient claim a payout before it is due", async () => {
+    try {
+      await claimSolPayout(1, ctx.recipient);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("PayoutNotDue");
+    }
+  });
+
+  it("should not let another wallet claim the recipient's payout", async () => {
+    await new Promise(resolve => setTimeout(resolve, 4000));
+
+    try {
+      await claimSolPayout(1, ctx.treasurer);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("should let the recipient claim a due SOL payout", async () => {
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+
+    await claimSolPayout(1, ctx.recipient);
+
+    const finalTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(initialTreasury.totalFunds.sub(finalTreasury.totalFunds).toString()).to.equal(
+      PAYOUT_AMOUNT.toString()
+    );
+    expect(finalTreasury.epochSpending.sub(initialTreasury.epochSpending).toString()).to.equal(
+      PAYOUT_AMOUNT.toString()
+    );
+
+    const payoutSchedule = await ctx.program.account.payoutSchedule.fetch(findPayoutPDA(1));
+    expect(payoutSchedule.isActive).to.be.false;
+  });
+
+  it("should charge claims against the recipient's spending limit", async () => {
+    const setRecipientSpendingLimit = async (limit: BN | null) => {
+      await ctx.program.methods
+        .setRecipientSpendingLimit(null, limit) // SOL
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: ctx.admin.publicKey,
+          user: ctx.adminUserPDA,
+          recipientAccount: ctx.recipientPDA,
+          proposal: null,
+        })
+        .signers([ctx.admin])
+        .rpc();
+    };
+
+    await setRecipientSpendingLimit(PAYOUT_AMOUNT.subn(1));
+
+    try {
+      await claimSolPayout(3, ctx.recipient);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("RecipientSpendingLimitExceeded");
+    } finally {
+      await setRecipientSpendingLimit(null);
+    }
+  });
+
+  it("should create the recipient's token account when claiming a token payout", async () => {
+    // Start without a recipient token account
+    await closeAccount(
+      ctx.provider.connection,
+      ctx.recipient,
+      tokenCtx.recipientTokenAccount,
+      ctx.recipient.publicKey,
+      ctx.recipient
+    );
+
+    const claimTimestamp = createTimestamp();
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    await ctx.program.methods
+      .claimPayout(claimTimestamp)
+      .accounts({
+        authority: ctx.recipient.publicKey,
+        treasury: ctx.treasuryPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(2),
+        auditLog: auditLogPDA,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+        recipientTokenAccount: tokenCtx.recipientTokenAccount,
+        tokenMint: tokenCtx.tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.recipient])
+      .rpc();
+
+    const recipientTokenAccount = await getAccount(
+      ctx.provider.connection,
+      tokenCtx.recipientTokenAccount
+    );
+    expect(recipientTokenAccount.amount.toString()).to.equal(PAYOUT_AMOUNT.toString());
+
+    const auditLog = await ctx.program.account.auditLog.fetch(auditLogPDA);
+    expect(auditLog.initiator.toString()).to.equal(ctx.recipient.publicKey.toString());
+    expect(auditLog.amount.toString()).to.equal(PAYOUT_AMOUNT.toString());
+  });
+});
@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { MockTokenMetadata } from "../target/types/mock_token_metadata";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  PAYOUT_AMOUNT,
+  DEFAULT_PAYOUT_OPTIONS,
+} from "./test_utils";
+
+describe("treasury_vault_collection_gate", () => {
+  let ctx: TestContext;
+  const metadataProgram = anchor.workspace.MockTokenMetadata as Program<MockTokenMetadata>;
+  const collection = anchor.web3.Keypair.generate().publicKey;
+  const COLLECTION_MODE = 1;
+
+  // NFTs held by the recipient, one verified in the collection and one not
+  let verifiedNft: { tokenAccount: anchor.web3.PublicKey; metadata: anchor.web3.PublicKey };
+  let unverifiedNft: { tokenAccount: anchor.web3.PublicKey; metadata: anchor.web3.PublicKey };
+
+  const findPayoutPDA = (index: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payout"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    )[0];
+
+  const createNft = async (verified: boolean) => {
+    const mint = await createMint(ctx.provider.connection, ctx.admin, ctx.admin.publicKey, null, 0);
+    const tokenAccount = (
+      await getOrCreateAssociatedTokenAccount(ctx.provider.connection, ctx.admin, mint, ctx.recipient.publicKey)
+    ).address;
+    await mintTo(ctx.provider.connection, ctx.admin, mint, tokenAccount, ctx.admin.publicKey, 1);
+
+    const [metadata] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), metadataProgram.programId.toBuffer(), mint.toBuffer()],
+      metadataProgram.programId
+    );
+    await metadataProgram.methods
+      .createMetadata(collection, verified)
+      .accounts({
+        metadata,
+        mint,
+        payer: ctx.admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    return { tokenAccount, metadata };
+  };
+
+  const setTokenGate = async (tokenMint: anchor.web3.PublicKey | null, mode: number) => {
+    await ctx.program.methods
+      .setTokenGate(new BN(0), mode)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        tokenMint,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const executePayout = async (
+    gateTokenAccount: anchor.web3.PublicKey | null,
+    gateMetadata: anchor.web3.PublicKey | null
+  ) => {
+    await ctx.program.methods
+      .executePayout(createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(1),
+        recipientWallet: ctx.recipient.publicKey,
+        gateTokenAccount,
+        gateMetadata,
+        auditLog: await findAuditLogPDA(ctx),
+        tipAuditLog: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  const expectGateFailure = async (
+    gateTokenAccount: anchor.web3.PublicKey | null,
+    gateMetadata: anchor.web3.PublicKey | null
+  ) => {
+    try {
+      await executePayout(gateTokenAccount, gateMetadata);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("TokenGateCheckFailed");
+    }
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, createTimestamp())
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    verifiedNft = await createNft(true);
+    unverifiedNft = await createNft(false);
+
+    await ctx.program.methods
+      .schedulePayout(PAYOUT_AMOUNT, createTimestamp(3), false, new BN(0), new BN(1), DEFAULT_PAYOUT_OPTIONS)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(1),
+        tokenMint: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    await new Promise(resolve => setTimeout(resolve, 4000));
+
+    await setTokenGate(collection, COLLECTION_MODE);
+  });
+
+  it("should store the collection gate", async () => {
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.gateTokenMint?.toString()).to.equal(collection.toString());
+    expect(treasury.gateMode).to.equal(COLLECTION_MODE);
+    expect(treasury.gateMinBalance.toNumber()).to.equal(1);
+  });
+
+  it("should reject an NFT whose collection is not verified", async () => {
+    await expectGateFailure(unverifiedNft.tokenAccount, unverifiedNft.metadata);
+  });
+
+  it("should reject a gated payout without the NFT's metadata", async () => {
+    await expectGateFailure(verifiedNft.tokenAccount, null);
+  });
+
+  it("should reject metadata that belongs to a different mint", async () => {
+    await expectGateFailure(unverifiedNft.tokenAccount, verifiedNft.metadata);
+  });
+
+  it("should pay a recipient holding a verified NFT of the collection", async () => {
+    const initialBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    await executePayout(verifiedNft.tokenAccount, verifiedNft.metadata);
+    const finalBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalBalance - initialBalance).to.equal(PAYOUT_AMOUNT.toNumber());
+  });
+
+  it("should reject an unknown gate mode", async () => {
+    try {
+      await setTokenGate(collection, 2);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidTokenGateMode");
+    }
+  });
+});
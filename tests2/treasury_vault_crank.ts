@@ -0,0 +1,276 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  TestContext,
+  TokenContext,
+  PayoutContext,
+  setupTestContext,
+  initializeTreasury,
+  setupTokenContext,
+  setupPayoutContext,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  PAYOUT_AMOUNT,
+  DEFAULT_PAYOUT_OPTIONS,
+  configUpdate,
+  AUDIT_ACTION_CRANK_TIP_PAID,
+} from "./test_utils";
+
+describe("treasury_vault_crank", () => {
+  let ctx: TestContext;
+  let tokenCtx: TokenContext;
+  let payoutCtx: PayoutContext;
+  const keeper = anchor.web3.Keypair.generate();
+  const CRANK_TIP = new BN(5000);
+  let solPayoutPDA: anchor.web3.PublicKey;
+
+  // The tip gets its own audit log right after the payout's entry
+  const findTipAuditLogPDA = () => findAuditLogPDA(ctx, ctx.treasuryPDA, 1);
+
+  const crankSolPayout = async (tipAuditLog: anchor.web3.PublicKey | null) => {
+    await ctx.program.methods
+      .executePayout(createTimestamp())
+      .accounts({
+        authority: keeper.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: null, // No treasurer role, run as a crank
+        recipient: ctx.recipientPDA,
+        payoutSchedule: solPayoutPDA,
+        recipientWallet: ctx.recipient.publicKey,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(ctx),
+        tipAuditLog,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([keeper])
+      .rpc();
+  };
+
+  const crankTokenPayout = async (payoutSchedulePDA: anchor.web3.PublicKey) => {
+    const executeTimestamp = createTimestamp();
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    await ctx.program.methods
+      .executeTokenPayout(executeTimestamp)
+      .accounts({
+        authority: keeper.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: null, // No treasurer role, run as a crank
+        recipient: ctx.recipientPDA,
+        payoutSchedule: payoutSchedulePDA,
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+        recipientTokenAccount: tokenCtx.recipientTokenAccount,
+        tokenMint: tokenCtx.tokenMint,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: auditLogPDA,
+        tipAuditLog: await findTipAuditLogPDA(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([keeper])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+    tokenCtx = await setupTokenContext(ctx);
+    payoutCtx = await setupPayoutContext(ctx, tokenCtx.tokenMint);
+
+    // Fund the keeper so it can pay for audit log accounts
+    const signature = await ctx.provider.connection.requestAirdrop(
+      keeper.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await ctx.provider.connection.confirmTransaction(signature);
+
+    // Deposit SOL to fund crank tips and tokens to fund the payout
+    const solDepositTimestamp = createTimestamp();
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, solDepositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    const tokenDepositTimestamp = createTimestamp(-4);
+    await ctx.program.methods
+      .depositToken(new BN(1000000), tokenDepositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+        depositorTokenAccount: tokenCtx.depositorTokenAccount,
+        tokenMint: tokenCtx.tokenMint,
+        auditLog: await findAuditLogPDA(ctx),
+        depositor: ctx.depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    // Configure the crank tip
+    await ctx.program.methods
+      .updateTreasuryConfig(configUpdate({ crankTip: CRANK_TIP }))
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    // Schedule a one-time token payout a few seconds out
+    await ctx.program.methods
+      .schedulePayout(PAYOUT_AMOUNT, createTimestamp(5), false, new BN(0), new BN(1), DEFAULT_PAYOUT_OPTIONS)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: payoutCtx.payoutSchedulePDA,
+        tokenMint: tokenCtx.tokenMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    // Schedule a one-time SOL payout alongside it
+    [solPayoutPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payout"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(3).toArrayLike(Buffer, "le", 8), // Index 3
+      ],
+      ctx.program.programId
+    );
+    await ctx.program.methods
+      .schedulePayout(PAYOUT_AMOUNT, createTimestamp(5), false, new BN(0), new BN(3), DEFAULT_PAYOUT_OPTIONS)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: solPayoutPDA,
+        tokenMint: null, // SOL payout
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  });
+
+  it("should store the configured crank tip", async () => {
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.crankTip.toString()).to.equal(CRANK_TIP.toString());
+  });
+
+  it("should not let a crank execute a payout before it is due", async () => {
+    try {
+      await crankTokenPayout(payoutCtx.payoutSchedulePDA);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("PayoutNotDue");
+    }
+  });
+
+  it("should let anyone crank a due payout and pay the tip", async () => {
+    await new Promise(resolve => setTimeout(resolve, 6000));
+
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const initialRecipientBalance = Number(
+      (await getAccount(ctx.provider.connection, tokenCtx.recipientTokenAccount)).amount
+    );
+
+    await crankTokenPayout(payoutCtx.payoutSchedulePDA);
+
+    // Funds went to the whitelisted recipient
+    const finalRecipientBalance = Number(
+      (await getAccount(ctx.provider.connection, tokenCtx.recipientTokenAccount)).amount
+    );
+    expect(finalRecipientBalance - initialRecipientBalance).to.equal(PAYOUT_AMOUNT.toNumber());
+
+    const payoutSchedule = await ctx.program.account.payoutSchedule.fetch(payoutCtx.payoutSchedulePDA);
+    expect(payoutSchedule.isActive).to.be.false;
+
+    // The tip came out of the treasury's SOL funds
+    const finalTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(initialTreasury.totalFunds.sub(finalTreasury.totalFunds).toString()).to.equal(
+      CRANK_TIP.toString()
+    );
+  });
+
+  it("should require the tip audit log when a crank tip is paid", async () => {
+    try {
+      await crankSolPayout(null);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidTipAuditLog");
+    }
+  });
+
+  it("should let anyone crank a due SOL payout and pay the tip", async () => {
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const initialRecipientBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    const tipAuditLogPDA = await findTipAuditLogPDA();
+
+    await crankSolPayout(tipAuditLogPDA);
+
+    // Lamports moved straight out of the treasury PDA
+    const finalRecipientBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalRecipientBalance - initialRecipientBalance).to.equal(PAYOUT_AMOUNT.toNumber());
+
+    const payoutSchedule = await ctx.program.account.payoutSchedule.fetch(solPayoutPDA);
+    expect(payoutSchedule.isActive).to.be.false;
+
+    // Both the payout and the tip count against the spending limit
+    const finalTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const outflow = PAYOUT_AMOUNT.add(CRANK_TIP);
+    expect(initialTreasury.totalFunds.sub(finalTreasury.totalFunds).toString()).to.equal(outflow.toString());
+    expect(finalTreasury.epochSpending.sub(initialTreasury.epochSpending).toString()).to.equal(outflow.toString());
+
+    // The tip is written to the audit log after the payout
+    const tipAuditLog = await ctx.program.account.auditLog.fetch(tipAuditLogPDA);
+    expect(tipAuditLog.action).to.equal(AUDIT_ACTION_CRANK_TIP_PAID);
+    expect(tipAuditLog.amount.toString()).to.equal(CRANK_TIP.toString());
+    expect(tipAuditLog.target.toString()).to.equal(keeper.publicKey.toString());
+    expect(tipAuditLog.payoutSchedule.toString()).to.equal(solPayoutPDA.toString());
+    expect(tipAuditLog.sequence.toString()).to.equal(finalTreasury.auditSequence.subn(1).toString());
+  });
+
+  it("should reject a crank tip above the maximum", async () => {
+    try {
+      await ctx.program.methods
+        .updateTreasuryConfig(configUpdate({ crankTip: new BN(10_000_001) }))
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: ctx.admin.publicKey,
+          user: ctx.adminUserPDA,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.admin])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("CrankTipTooLarge");
+    }
+  });
+});
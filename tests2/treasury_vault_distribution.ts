@@ -0,0 +1,275 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { createHash } from "crypto";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+} from "./test_utils";
+
+const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Must match Distribution::leaf_hash and Distribution::verify_proof
+const leafHash = (index: number, recipient: anchor.web3.PublicKey, amount: BN) => {
+  const indexBytes = Buffer.alloc(4);
+  indexBytes.writeUInt32LE(index);
+  return sha256(
+    Buffer.from([0]),
+    indexBytes,
+    recipient.toBuffer(),
+    anchor.web3.PublicKey.default.toBuffer(), // SOL distribution
+    amount.toArrayLike(Buffer, "le", 8)
+  );
+};
+
+const nodeHash = (a: Buffer, b: Buffer) =>
+  Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+
+// Builds every layer of the tree, carrying an unpaired node up unchanged
+const buildTree = (leaves: Buffer[]) => {
+  const layers = [leaves];
+  while (layers[layers.length - 1].length > 1) {
+    const layer = layers[layers.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < layer.length; i += 2) {
+      next.push(i + 1 < layer.length ? nodeHash(layer[i], layer[i + 1]) : layer[i]);
+    }
+    layers.push(next);
+  }
+  return layers;
+};
+
+const getProof = (layers: Buffer[][], index: number) => {
+  const proof: number[][] = [];
+  for (const layer of layers.slice(0, -1)) {
+    const sibling = index ^ 1;
+    if (sibling < layer.length) {
+      proof.push(Array.from(layer[sibling]));
+    }
+    index = Math.floor(index / 2);
+  }
+  return proof;
+};
+
+describe("treasury_vault_distribution", () => {
+  let ctx: TestContext;
+  let layers: Buffer[][];
+  let distributionPDA: anchor.web3.PublicKey;
+  const claimants = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+  const shares = [new BN(100000), new BN(50000), new BN(25000)];
+  const total = shares.reduce((sum, share) => sum.add(share), new BN(0));
+  const EXPIRY_DELAY = 12; // Seconds
+
+  const findRecipientPDA = (recipient: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient"), recipient.toBuffer(), ctx.treasuryPDA.toBuffer()],
+      ctx.program.programId
+    )[0];
+
+  const setWithdrawalWhitelist = async (required: boolean) => {
+    await ctx.program.methods
+      .setWithdrawalWhitelist(required)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const claim = async (claimant: anchor.web3.Keypair, index: number, amount: BN, proof: number[][]) => {
+    await ctx.program.methods
+      .claimDistribution(index, amount, proof, createTimestamp())
+      .accounts({
+        authority: claimant.publicKey,
+        treasury: ctx.treasuryPDA,
+        distribution: distributionPDA,
+        whitelistedRecipient: findRecipientPDA(claimant.publicKey),
+        auditLog: await findAuditLogPDA(ctx),
+        gateTokenAccount: null,
+        gateMetadata: null,
+        tokenBalance: null,
+        treasuryTokenAccount: null,
+        recipientTokenAccount: null,
+        tokenMint: null,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([claimant])
+      .rpc();
+  };
+
+  const reclaim = async () => {
+    await ctx.program.methods
+      .reclaimDistribution()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        distribution: distributionPDA,
+        tokenBalance: null,
+        rentReceiver: ctx.treasurer.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, createTimestamp())
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    // Claimants pay for the audit log of their claim
+    for (const claimant of claimants) {
+      const signature = await ctx.provider.connection.requestAirdrop(
+        claimant.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await ctx.provider.connection.confirmTransaction(signature);
+    }
+
+    layers = buildTree(claimants.map((claimant, i) => leafHash(i, claimant.publicKey, shares[i])));
+    [distributionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("distribution"), ctx.treasuryPDA.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+      ctx.program.programId
+    );
+  });
+
+  it("should reserve the distribution total out of the treasury", async () => {
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const expiresAt = new BN(Math.floor(Date.now() / 1000) + EXPIRY_DELAY);
+
+    await ctx.program.methods
+      .createDistribution(Array.from(layers[layers.length - 1][0]), total, claimants.length, expiresAt, new BN(0))
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        distribution: distributionPDA,
+        tokenBalance: null,
+        tokenMint: null,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+
+    const distribution = await ctx.program.account.distribution.fetch(distributionPDA);
+    expect(distribution.totalAmount.toString()).to.equal(total.toString());
+    expect(distribution.claimCount).to.equal(claimants.length);
+    expect(distribution.tokenMint).to.be.null;
+
+    // Reserved funds leave the spendable balance and count against the spending limit
+    const finalTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(initialTreasury.totalFunds.sub(finalTreasury.totalFunds).toString()).to.equal(total.toString());
+    expect(finalTreasury.epochSpending.sub(initialTreasury.epochSpending).toString()).to.equal(total.toString());
+  });
+
+  it("should pay a claimant who proves their leaf", async () => {
+    const initialLamports = await ctx.provider.connection.getBalance(ctx.treasuryPDA);
+    await claim(claimants[0], 0, shares[0], getProof(layers, 0));
+    const finalLamports = await ctx.provider.connection.getBalance(ctx.treasuryPDA);
+    expect(initialLamports - finalLamports).to.equal(shares[0].toNumber());
+
+    const distribution = await ctx.program.account.distribution.fetch(distributionPDA);
+    expect(distribution.claimedAmount.toString()).to.equal(shares[0].toString());
+    expect(distribution.claimedBitmap[0] & 1).to.equal(1);
+  });
+
+  it("should reject a second claim of the same leaf", async () => {
+    try {
+      await claim(claimants[0], 0, shares[0], getProof(layers, 0));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("DistributionAlreadyClaimed");
+    }
+  });
+
+  it("should reject a claim for a different amount", async () => {
+    try {
+      await claim(claimants[1], 1, shares[1].muln(2), getProof(layers, 1));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidMerkleProof");
+    }
+  });
+
+  it("should reject another wallet using a claimant's proof", async () => {
+    try {
+      await claim(claimants[2], 1, shares[1], getProof(layers, 1));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidMerkleProof");
+    }
+  });
+
+  it("should pay the leaf carried up without a sibling", async () => {
+    await claim(claimants[2], 2, shares[2], getProof(layers, 2));
+
+    const distribution = await ctx.program.account.distribution.fetch(distributionPDA);
+    expect(distribution.claimedAmount.toString()).to.equal(shares[0].add(shares[2]).toString());
+  });
+
+  it("should reject claimants outside the whitelist when withdrawals require it", async () => {
+    await setWithdrawalWhitelist(true);
+
+    try {
+      await claim(claimants[1], 1, shares[1], getProof(layers, 1));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("RecipientNotWhitelisted");
+    } finally {
+      await setWithdrawalWhitelist(false);
+    }
+  });
+
+  it("should not reclaim a distribution before it expires", async () => {
+    try {
+      await reclaim();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("DistributionNotExpired");
+    }
+  });
+
+  it("should close claims once the distribution expires", async () => {
+    await new Promise(resolve => setTimeout(resolve, (EXPIRY_DELAY + 1) * 1000));
+
+    try {
+      await claim(claimants[1], 1, shares[1], getProof(layers, 1));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("DistributionExpired");
+    }
+  });
+
+  it("should return the unclaimed rest to the treasury on reclaim", async () => {
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    await reclaim();
+    const finalTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(finalTreasury.totalFunds.sub(initialTreasury.totalFunds).toString()).to.equal(shares[1].toString());
+
+    const closed = await ctx.program.account.distribution.fetchNullable(distributionPDA);
+    expect(closed).to.be.null;
+  });
+});
@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  GUARDIAN_ROLE,
+  WITHDRAW_AMOUNT,
+  configUpdate,
+} from "./test_utils";
+
+describe("treasury_vault_guardian", () => {
+  let ctx: TestContext;
+  const guardian = anchor.web3.Keypair.generate();
+  let guardianUserPDA: anchor.web3.PublicKey;
+  const PAUSE_DURATION = new BN(3); // Seconds
+
+  const pauseAsGuardian = async (duration: BN | null) => {
+    await ctx.program.methods
+      .pauseTreasury(duration)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: guardian.publicKey,
+        user: guardianUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([guardian])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    const signature = await ctx.provider.connection.requestAirdrop(
+      guardian.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await ctx.provider.connection.confirmTransaction(signature);
+
+    [guardianUserPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), guardian.publicKey.toBuffer(), ctx.treasuryPDA.toBuffer()],
+      ctx.program.programId
+    );
+
+    await ctx.program.methods
+      .addTreasuryUser(GUARDIAN_ROLE)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        adminUser: ctx.adminUserPDA,
+        userAccount: guardianUserPDA,
+        user: guardian.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        proposal: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  });
+
+  it("should let a guardian pause the treasury with an expiry", async () => {
+    await pauseAsGuardian(PAUSE_DURATION);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.isPaused).to.be.true;
+    expect(treasury.pauseExpiresAt).to.not.be.null;
+  });
+
+  it("should not let a guardian unpause the treasury", async () => {
+    try {
+      await ctx.program.methods
+        .unpauseTreasury()
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: guardian.publicKey,
+          user: guardianUserPDA,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([guardian])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedPauseAction");
+    }
+  });
+
+  it("should not let a guardian configure the treasury", async () => {
+    try {
+      await ctx.program.methods
+        .updateTreasuryConfig(configUpdate({ spendingLimit: new BN(1) }))
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: guardian.publicKey,
+          user: guardianUserPDA,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([guardian])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedConfigUpdate");
+    }
+  });
+
+  it("should reject withdrawals while paused", async () => {
+    try {
+      await ctx.program.methods
+        .withdraw(WITHDRAW_AMOUNT, createTimestamp())
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("TreasuryPaused");
+    }
+  });
+
+  it("should lift a guardian pause once it expires", async () => {
+    // Still paused before the expiry
+    try {
+      await pauseAsGuardian(null);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("TreasuryAlreadyPaused");
+    }
+
+    await new Promise(resolve => setTimeout(resolve, (PAUSE_DURATION.toNumber() + 1) * 1000));
+
+    // The expired pause no longer counts, so it can be renewed
+    await pauseAsGuardian(null);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.isPaused).to.be.true;
+    expect(treasury.pauseExpiresAt).to.be.null;
+  });
+
+  it("should let an admin unpause a guardian pause", async () => {
+    await ctx.program.methods
+      .unpauseTreasury()
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.isPaused).to.be.false;
+    expect(treasury.pauseExpiresAt).to.be.null;
+  });
+
+  it("should not let a guardian move funds", async () => {
+    try {
+      await ctx.program.methods
+        .withdraw(WITHDRAW_AMOUNT, createTimestamp())
+        .accounts({
+          authority: guardian.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: guardianUserPDA,
+          recipient: guardian.publicKey,
+          whitelistedRecipient: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("recipient"), guardian.publicKey.toBuffer(), ctx.treasuryPDA.toBuffer()],
+            ctx.program.programId
+          )[0],
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([guardian])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedWithdrawal");
+    }
+  });
+});
@@ -0,0 +1,114 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  findTreasuryPDA,
+  createTimestamp,
+  EPOCH_DURATION,
+  SPENDING_LIMIT,
+  DEPOSIT_AMOUNT,
+  TREASURER_ROLE,
+  findAuditLogPDA
+} from "./test_utils";
+
+describe("treasury_vault_multi_treasury", () => {
+  let ctx: TestContext;
+  const SECOND_TREASURY_ID = new BN(1);
+  let secondTreasuryPDA: anchor.web3.PublicKey;
+  let secondAdminUserPDA: anchor.web3.PublicKey;
+  let secondTreasurerUserPDA: anchor.web3.PublicKey;
+
+  before(async () => {
+    // Setup test context with the default treasury
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    // Derive a second treasury owned by the same admin
+    [secondTreasuryPDA] = findTreasuryPDA(ctx.program, ctx.admin.publicKey, SECOND_TREASURY_ID);
+
+    [secondAdminUserPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user"),
+        ctx.admin.publicKey.toBuffer(),
+        secondTreasuryPDA.toBuffer(),
+      ],
+      ctx.program.programId
+    );
+
+    [secondTreasurerUserPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user"),
+        ctx.treasurer.publicKey.toBuffer(),
+        secondTreasuryPDA.toBuffer(),
+      ],
+      ctx.program.programId
+    );
+  });
+
+  it("should initialize a second treasury for the same admin", async () => {
+    await ctx.program.methods
+      .initializeTreasury(SECOND_TREASURY_ID, EPOCH_DURATION, SPENDING_LIMIT)
+      .accounts({
+        treasury: secondTreasuryPDA,
+        admin: ctx.admin.publicKey,
+        admin_user: secondAdminUserPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const secondTreasury = await ctx.program.account.treasury.fetch(secondTreasuryPDA);
+    expect(secondTreasury.creator.toString()).to.equal(ctx.admin.publicKey.toString());
+    expect(secondTreasury.treasuryId.toString()).to.equal(SECOND_TREASURY_ID.toString());
+    expect(secondTreasuryPDA.toString()).to.not.equal(ctx.treasuryPDA.toString());
+  });
+
+  it("should keep treasury users isolated between treasuries", async () => {
+    // The treasurer was only added to the first treasury
+    const info = await ctx.provider.connection.getAccountInfo(secondTreasurerUserPDA);
+    expect(info).to.be.null;
+
+    await ctx.program.methods
+      .addTreasuryUser(TREASURER_ROLE)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        treasury: secondTreasuryPDA,
+        adminUser: secondAdminUserPDA,
+        userAccount: secondTreasurerUserPDA,
+        user: ctx.treasurer.publicKey,
+        auditLog: await findAuditLogPDA(ctx, secondTreasuryPDA),
+        proposal: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const treasurerUser = await ctx.program.account.treasuryUser.fetch(secondTreasurerUserPDA);
+    expect(treasurerUser.treasury.toString()).to.equal(secondTreasuryPDA.toString());
+    expect(secondTreasurerUserPDA.toString()).to.not.equal(ctx.treasurerUserPDA.toString());
+  });
+
+  it("should track deposits separately per treasury", async () => {
+    const depositTimestamp = createTimestamp();
+    const auditLogPDA = await findAuditLogPDA(ctx, secondTreasuryPDA);
+
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, depositTimestamp)
+      .accounts({
+        treasury: secondTreasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: auditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    const firstTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const secondTreasury = await ctx.program.account.treasury.fetch(secondTreasuryPDA);
+    expect(secondTreasury.totalFunds.toString()).to.equal(DEPOSIT_AMOUNT.toString());
+    expect(firstTreasury.totalFunds.toString()).to.equal("0");
+  });
+});
@@ -0,0 +1,584 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  WITHDRAW_AMOUNT,
+  TREASURER_ROLE,
+  configUpdate,
+  TreasuryConfigUpdate,
+} from "./test_utils";
+
+// Proposal actions
+const PROPOSAL_ACTION_SOL_WITHDRAW = 0;
+const PROPOSAL_ACTION_CONFIG_CHANGE = 2;
+const PROPOSAL_ACTION_ADD_USER = 4;
+const PROPOSAL_ACTION_SET_USER_PERMISSIONS = 6;
+const PROPOSAL_ACTION_SET_USER_SPENDING_LIMIT = 9;
+
+// Permission bits
+const PERMISSION_DEPOSIT = 1 << 0;
+
+describe("treasury_vault_multisig", () => {
+  let ctx: TestContext;
+  let proposalPDA: anchor.web3.PublicKey;
+
+  function findProposalPDA(index: number): anchor.web3.PublicKey {
+    const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    );
+    return pda;
+  }
+
+  // Treasury user accounts of a proposal's approvers, in approval order
+  function approverAccounts(...userPDAs: anchor.web3.PublicKey[]) {
+    return userPDAs.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+  }
+
+  const createProposal = async (
+    proposer: anchor.web3.Keypair,
+    proposerUserPDA: anchor.web3.PublicKey,
+    index: number,
+    action: number,
+    params: {
+      amount?: BN;
+      target?: anchor.web3.PublicKey;
+      config?: TreasuryConfigUpdate;
+      role?: number;
+      permissions?: number;
+    }
+  ) => {
+    await ctx.program.methods
+      .createProposal(
+        action,
+        {
+          amount: params.amount ?? new BN(0),
+          target: params.target ?? null,
+          tokenMint: null,
+          config: params.config ?? configUpdate(),
+          role: params.role ?? null,
+          permissions: params.permissions ?? null,
+        },
+        createTimestamp(3600)
+      )
+      .accounts({
+        authority: proposer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: proposerUserPDA,
+        proposal: findProposalPDA(index),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+  };
+
+  const approve = async (approver: anchor.web3.Keypair, approverUserPDA: anchor.web3.PublicKey, index: number) => {
+    await ctx.program.methods
+      .approveProposal()
+      .accounts({
+        authority: approver.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: approverUserPDA,
+        proposal: findProposalPDA(index),
+      })
+      .signers([approver])
+      .rpc();
+  };
+
+  before(async () => {
+    // Setup test context
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    // Fund the treasury
+    const depositTimestamp = createTimestamp();
+    const auditLogPDA = await findAuditLogPDA(ctx);
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, depositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: auditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    proposalPDA = findProposalPDA(0);
+  });
+
+  const setApprovalThreshold = async (threshold: number) => {
+    await ctx.program.methods
+      .updateTreasuryConfig(configUpdate({ approvalThreshold: threshold }))
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  it("should only accept a threshold the active approvers can meet", async () => {
+    // The admin and the treasurer are the only users holding Withdraw
+    try {
+      await setApprovalThreshold(3);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("ApprovalThresholdUnreachable");
+    }
+
+    // Require two approvals for sensitive actions
+    await setApprovalThreshold(2);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.approvalThreshold).to.equal(2);
+    expect(treasury.activeApproverCount).to.equal(2);
+  });
+
+  it("should block direct withdrawals once a threshold is set", async () => {
+    const withdrawTimestamp = createTimestamp(-20);
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    try {
+      await ctx.program.methods
+        .withdraw(WITHDRAW_AMOUNT, withdrawTimestamp)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
+          auditLog: auditLogPDA,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("MultisigApprovalRequired");
+    }
+  });
+
+  it("should block vesting streams once a threshold is set", async () => {
+    const [vestingStreamPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vesting"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    );
+
+    // A stream that has already fully vested would pay out without any approvals
+    try {
+      await ctx.program.methods
+        .createVestingStream(WITHDRAW_AMOUNT, createTimestamp(-120), null, createTimestamp(-60), new BN(0))
+        .accounts({
+          authority: ctx.admin.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.adminUserPDA,
+          recipient: ctx.recipientPDA,
+          vestingStream: vestingStreamPDA,
+          tokenBalance: null,
+          tokenMint: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.admin])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("MultisigApprovalRequired");
+    }
+  });
+
+  it("should create a withdrawal proposal with the proposer's approval", async () => {
+    const expiresAt = createTimestamp(3600);
+
+    await ctx.program.methods
+      .createProposal(
+        PROPOSAL_ACTION_SOL_WITHDRAW,
+        {
+          amount: WITHDRAW_AMOUNT,
+          target: ctx.recipient.publicKey,
+          tokenMint: null,
+          config: configUpdate(),
+          role: null,
+          permissions: null,
+        },
+        expiresAt
+      )
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        proposal: proposalPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+
+    const proposal = await ctx.program.account.proposal.fetch(proposalPDA);
+    expect(proposal.action).to.equal(PROPOSAL_ACTION_SOL_WITHDRAW);
+    expect(proposal.approvals.length).to.equal(1);
+    expect(proposal.approvals[0].toString()).to.equal(ctx.treasurer.publicKey.toString());
+    expect(proposal.executed).to.be.false;
+  });
+
+  it("should not execute without enough approvals", async () => {
+    const executeTimestamp = createTimestamp(-10);
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    try {
+      await ctx.program.methods
+        .executeProposal(executeTimestamp)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          proposal: proposalPDA,
+          auditLog: auditLogPDA,
+          recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
+          tokenBalance: null,
+          treasuryTokenAccount: null,
+          recipientTokenAccount: null,
+          tokenMint: null,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(approverAccounts(ctx.treasurerUserPDA))
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InsufficientApprovals");
+    }
+  });
+
+  it("should allow an approver to revoke their approval", async () => {
+    await ctx.program.methods
+      .approveProposal()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        proposal: proposalPDA,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    await ctx.program.methods
+      .revokeApproval()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        proposal: proposalPDA,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const proposal = await ctx.program.account.proposal.fetch(proposalPDA);
+    expect(proposal.approvals.length).to.equal(1);
+  });
+
+  it("should execute once the threshold is met", async () => {
+    await ctx.program.methods
+      .approveProposal()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        proposal: proposalPDA,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const initialRecipientBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+
+    const executeTimestamp = createTimestamp(-12);
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    await ctx.program.methods
+      .executeProposal(executeTimestamp)
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        proposal: proposalPDA,
+        auditLog: auditLogPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        tokenBalance: null,
+        treasuryTokenAccount: null,
+        recipientTokenAccount: null,
+        tokenMint: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(approverAccounts(ctx.treasurerUserPDA, ctx.adminUserPDA))
+      .signers([ctx.treasurer])
+      .rpc();
+
+    const proposal = await ctx.program.account.proposal.fetch(proposalPDA);
+    expect(proposal.executed).to.be.true;
+
+    const updatedTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(updatedTreasury.totalFunds.toString()).to.equal(
+      initialTreasury.totalFunds.sub(WITHDRAW_AMOUNT).toString()
+    );
+
+    const updatedRecipientBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(updatedRecipientBalance - initialRecipientBalance).to.equal(WITHDRAW_AMOUNT.toNumber());
+  });
+
+  describe("user management", () => {
+    const newUser = anchor.web3.Keypair.generate();
+    let newUserPDA: anchor.web3.PublicKey;
+
+    const addUser = async (proposal: anchor.web3.PublicKey | null, approvers: anchor.web3.PublicKey[]) => {
+      await ctx.program.methods
+        .addTreasuryUser(TREASURER_ROLE)
+        .accounts({
+          admin: ctx.admin.publicKey,
+          treasury: ctx.treasuryPDA,
+          adminUser: ctx.adminUserPDA,
+          userAccount: newUserPDA,
+          user: newUser.publicKey,
+          auditLog: await findAuditLogPDA(ctx),
+          proposal,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(approverAccounts(...approvers))
+        .signers([ctx.admin])
+        .rpc();
+    };
+
+    before(async () => {
+      [newUserPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), newUser.publicKey.toBuffer(), ctx.treasuryPDA.toBuffer()],
+        ctx.program.programId
+      );
+
+      await ctx.provider.connection.requestAirdrop(newUser.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    });
+
+    it("should not add users without a proposal", async () => {
+      try {
+        await addUser(null, []);
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("MultisigApprovalRequired");
+      }
+    });
+
+    it("should add a user through an approved proposal", async () => {
+      await createProposal(ctx.treasurer, ctx.treasurerUserPDA, 1, PROPOSAL_ACTION_ADD_USER, {
+        target: newUser.publicKey,
+        role: TREASURER_ROLE,
+      });
+      await approve(ctx.admin, ctx.adminUserPDA, 1);
+
+      await addUser(findProposalPDA(1), [ctx.treasurerUserPDA, ctx.adminUserPDA]);
+
+      const user = await ctx.program.account.treasuryUser.fetch(newUserPDA);
+      expect(user.isActive).to.be.true;
+      expect(user.role).to.equal(TREASURER_ROLE);
+
+      const proposal = await ctx.program.account.proposal.fetch(findProposalPDA(1));
+      expect(proposal.executed).to.be.true;
+
+      const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+      expect(treasury.activeApproverCount).to.equal(3);
+    });
+
+    it("should not count approvals from users who lost the Withdraw permission", async () => {
+      // The new user proposes a withdrawal and the treasurer approves it
+      await createProposal(newUser, newUserPDA, 2, PROPOSAL_ACTION_SOL_WITHDRAW, {
+        amount: WITHDRAW_AMOUNT,
+        target: ctx.recipient.publicKey,
+      });
+      await approve(ctx.treasurer, ctx.treasurerUserPDA, 2);
+
+      // The new user's Withdraw permission is then revoked through a proposal
+      await createProposal(ctx.admin, ctx.adminUserPDA, 3, PROPOSAL_ACTION_SET_USER_PERMISSIONS, {
+        target: newUser.publicKey,
+        permissions: PERMISSION_DEPOSIT,
+      });
+      await approve(ctx.treasurer, ctx.treasurerUserPDA, 3);
+
+      await ctx.program.methods
+        .setUserPermissions(PERMISSION_DEPOSIT)
+        .accounts({
+          authority: ctx.admin.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.adminUserPDA,
+          userAccount: newUserPDA,
+          proposal: findProposalPDA(3),
+        })
+        .remainingAccounts(approverAccounts(ctx.adminUserPDA, ctx.treasurerUserPDA))
+        .signers([ctx.admin])
+        .rpc();
+
+      // Only the treasurer's approval still counts
+      try {
+        await ctx.program.methods
+          .executeProposal(createTimestamp(-10))
+          .accounts({
+            authority: ctx.treasurer.publicKey,
+            treasury: ctx.treasuryPDA,
+            user: ctx.treasurerUserPDA,
+            proposal: findProposalPDA(2),
+            auditLog: await findAuditLogPDA(ctx),
+            recipient: ctx.recipient.publicKey,
+            whitelistedRecipient: ctx.recipientPDA,
+            tokenBalance: null,
+            treasuryTokenAccount: null,
+            recipientTokenAccount: null,
+            tokenMint: null,
+            tokenProgram: null,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts(approverAccounts(newUserPDA, ctx.treasurerUserPDA))
+          .signers([ctx.treasurer])
+          .rpc();
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("InsufficientApprovals");
+      }
+    });
+  });
+
+  describe("config changes", () => {
+    const executeConfigChange = async (index: number) => {
+      await ctx.program.methods
+        .executeProposal(createTimestamp(-10))
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          proposal: findProposalPDA(index),
+          auditLog: await findAuditLogPDA(ctx),
+          recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: null,
+          tokenBalance: null,
+          treasuryTokenAccount: null,
+          recipientTokenAccount: null,
+          tokenMint: null,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(approverAccounts(ctx.adminUserPDA, ctx.treasurerUserPDA))
+        .signers([ctx.treasurer])
+        .rpc();
+    };
+
+    it("should not set the withdrawal timelock directly", async () => {
+      try {
+        await ctx.program.methods
+          .setWithdrawalTimelock(new BN(1000), new BN(60))
+          .accounts({
+            treasury: ctx.treasuryPDA,
+            authority: ctx.admin.publicKey,
+            user: ctx.adminUserPDA,
+            auditLog: await findAuditLogPDA(ctx),
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([ctx.admin])
+          .rpc();
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("MultisigApprovalRequired");
+      }
+    });
+
+    it("should set the withdrawal timelock through an approved proposal", async () => {
+      await createProposal(ctx.admin, ctx.adminUserPDA, 4, PROPOSAL_ACTION_CONFIG_CHANGE, {
+        config: configUpdate({
+          withdrawalTimelockThreshold: new BN(1000),
+          withdrawalTimelockDelay: new BN(60),
+        }),
+      });
+      await approve(ctx.treasurer, ctx.treasurerUserPDA, 4);
+
+      await executeConfigChange(4);
+
+      const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+      expect(treasury.withdrawalTimelockThreshold.toString()).to.equal("1000");
+      expect(treasury.withdrawalTimelockDelay.toString()).to.equal("60");
+    });
+
+    it("should switch the withdrawal whitelist policy through an approved proposal", async () => {
+      await createProposal(ctx.admin, ctx.adminUserPDA, 5, PROPOSAL_ACTION_CONFIG_CHANGE, {
+        config: configUpdate({ withdrawalsRequireWhitelist: true }),
+      });
+      await approve(ctx.treasurer, ctx.treasurerUserPDA, 5);
+
+      await executeConfigChange(5);
+
+      const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+      expect(treasury.withdrawalsRequireWhitelist).to.be.true;
+    });
+  });
+
+  describe("spending limits", () => {
+    const USER_LIMIT = new BN(500000);
+
+    const setUserSpendingLimit = async (proposal: anchor.web3.PublicKey | null, approvers: anchor.web3.PublicKey[]) => {
+      await ctx.program.methods
+        .setUserSpendingLimit(null, USER_LIMIT) // SOL
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: ctx.admin.publicKey,
+          user: ctx.adminUserPDA,
+          userAccount: ctx.treasurerUserPDA,
+          proposal,
+        })
+        .remainingAccounts(approverAccounts(...approvers))
+        .signers([ctx.admin])
+        .rpc();
+    };
+
+    it("should not set a per-user limit without a proposal", async () => {
+      try {
+        await setUserSpendingLimit(null, []);
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("MultisigApprovalRequired");
+      }
+    });
+
+    it("should set a per-user limit through an approved proposal", async () => {
+      await createProposal(ctx.admin, ctx.adminUserPDA, 6, PROPOSAL_ACTION_SET_USER_SPENDING_LIMIT, {
+        amount: USER_LIMIT,
+        target: ctx.treasurer.publicKey,
+      });
+      await approve(ctx.treasurer, ctx.treasurerUserPDA, 6);
+
+      await setUserSpendingLimit(findProposalPDA(6), [ctx.adminUserPDA, ctx.treasurerUserPDA]);
+
+      const treasurerUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+      expect(treasurerUser.epochLimits.length).to.equal(1);
+      expect(treasurerUser.epochLimits[0].tokenMint).to.be.null;
+      expect(treasurerUser.epochLimits[0].limit.toString()).to.equal(USER_LIMIT.toString());
+
+      const proposal = await ctx.program.account.proposal.fetch(findProposalPDA(6));
+      expect(proposal.executed).to.be.true;
+    });
+  });
+});
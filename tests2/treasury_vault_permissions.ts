@@ -0,0 +1,197 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  TREASURER_ROLE,
+  ADMIN_ROLE,
+  DEPOSIT_AMOUNT,
+  WITHDRAW_AMOUNT,
+} from "./test_utils";
+
+describe("treasury_vault_permissions", () => {
+  let ctx: TestContext;
+
+  // Mirrors the program's Permission bits
+  const PERMISSION_WITHDRAW = 1 << 1;
+  const PERMISSION_SCHEDULE = 1 << 2;
+  const PERMISSION_MANAGE_USERS = 1 << 6;
+  const TREASURER_PERMISSIONS = 0b11111; // Deposit | Withdraw | Schedule | Execute | Cancel
+  const ALL_PERMISSIONS = (1 << 9) - 1;
+
+  const setUserPermissions = async (userAccount: anchor.web3.PublicKey, permissions: number) => {
+    await ctx.program.methods
+      .setUserPermissions(permissions)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        userAccount,
+        proposal: null,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const withdraw = async () => {
+    await ctx.program.methods
+      .withdraw(WITHDRAW_AMOUNT, createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, createTimestamp())
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+  });
+
+  it("should seed users with their role's default permissions", async () => {
+    const adminUser = await ctx.program.account.treasuryUser.fetch(ctx.adminUserPDA);
+    expect(adminUser.permissions).to.equal(ALL_PERMISSIONS);
+
+    const treasurerUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+    expect(treasurerUser.permissions).to.equal(TREASURER_PERMISSIONS);
+  });
+
+  it("should enforce a narrowed permission set", async () => {
+    await setUserPermissions(ctx.treasurerUserPDA, PERMISSION_SCHEDULE);
+
+    const treasurerUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+    expect(treasurerUser.permissions).to.equal(PERMISSION_SCHEDULE);
+
+    try {
+      await withdraw();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedWithdrawal");
+    }
+  });
+
+  it("should allow an action once its permission is granted", async () => {
+    await setUserPermissions(ctx.treasurerUserPDA, PERMISSION_SCHEDULE | PERMISSION_WITHDRAW);
+
+    const initialBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    await withdraw();
+    const finalBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalBalance - initialBalance).to.equal(WITHDRAW_AMOUNT.toNumber());
+  });
+
+  it("should reset custom permissions on a role change", async () => {
+    await ctx.program.methods
+      .updateUserRole(TREASURER_ROLE)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        userAccount: ctx.treasurerUserPDA,
+        proposal: null,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const treasurerUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+    expect(treasurerUser.permissions).to.equal(TREASURER_PERMISSIONS);
+  });
+
+  it("should reject unknown permission bits", async () => {
+    try {
+      await setUserPermissions(ctx.treasurerUserPDA, 1 << 9);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidPermissions");
+    }
+  });
+
+  it("should not let admin permissions be edited", async () => {
+    try {
+      await setUserPermissions(ctx.adminUserPDA, PERMISSION_SCHEDULE);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AdminPermissionsFixed");
+    }
+  });
+
+  it("should not let a user without ManageUsers change permissions", async () => {
+    try {
+      await ctx.program.methods
+        .setUserPermissions(ALL_PERMISSIONS)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          userAccount: ctx.treasurerUserPDA,
+          proposal: null,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedUser");
+    }
+  });
+
+  it("should not let a non-admin user manager change an admin", async () => {
+    await setUserPermissions(ctx.treasurerUserPDA, TREASURER_PERMISSIONS | PERMISSION_MANAGE_USERS);
+
+    try {
+      await ctx.program.methods
+        .updateUserRole(TREASURER_ROLE)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          userAccount: ctx.adminUserPDA,
+          proposal: null,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AdminTargetRequiresAdmin");
+    }
+
+    try {
+      await ctx.program.methods
+        .deactivateTreasuryUser()
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          userAccount: ctx.adminUserPDA,
+          proposal: null,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("AdminTargetRequiresAdmin");
+    }
+
+    const adminUser = await ctx.program.account.treasuryUser.fetch(ctx.adminUserPDA);
+    expect(adminUser.role).to.equal(ADMIN_ROLE);
+    expect(adminUser.isActive).to.be.true;
+  });
+});
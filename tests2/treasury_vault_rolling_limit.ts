@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  WITHDRAW_AMOUNT,
+  EPOCH_DURATION,
+  configUpdate,
+} from "./test_utils";
+
+describe("treasury_vault_rolling_limit", () => {
+  let ctx: TestContext;
+  const FIXED_EPOCH_MODE = 0;
+  const ROLLING_MODE = 1;
+  const ROLLING_LIMIT = new BN(300000);
+  const BUCKETS = 24;
+
+  const updateConfig = async (spendingLimit: BN | null, spendingLimitMode: number | null) => {
+    await ctx.program.methods
+      .updateTreasuryConfig(configUpdate({ spendingLimit: spendingLimit, spendingLimitMode: spendingLimitMode }))
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const withdraw = async (amount: BN) => {
+    await ctx.program.methods
+      .withdraw(amount, createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    for (let i = 0; i < 2; i++) {
+      await ctx.program.methods
+        .deposit(DEPOSIT_AMOUNT, createTimestamp())
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          depositor: ctx.depositor.publicKey,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.depositor])
+        .rpc();
+    }
+  });
+
+  it("should default to fixed epochs", async () => {
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.spendingLimitMode).to.equal(FIXED_EPOCH_MODE);
+  });
+
+  it("should switch to a rolling window", async () => {
+    await updateConfig(ROLLING_LIMIT, ROLLING_MODE);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.spendingLimitMode).to.equal(ROLLING_MODE);
+    expect(treasury.spendingLimit.toString()).to.equal(ROLLING_LIMIT.toString());
+  });
+
+  it("should cap spending over the trailing window", async () => {
+    await withdraw(WITHDRAW_AMOUNT);
+
+    // The buckets other than the current one span a full epoch duration
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.rollingSpending.bucketWidth.toNumber()).to.equal(
+      Math.ceil(EPOCH_DURATION.toNumber() / (BUCKETS - 1))
+    );
+    const spent = treasury.rollingSpending.buckets.reduce(
+      (total: BN, bucket: any) => total.add(bucket.amount),
+      new BN(0)
+    );
+    expect(spent.toString()).to.equal(WITHDRAW_AMOUNT.toString());
+
+    try {
+      await withdraw(WITHDRAW_AMOUNT);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("SpendingLimitExceeded");
+    }
+  });
+
+  it("should keep counting spending after switching back to fixed epochs", async () => {
+    await updateConfig(null, FIXED_EPOCH_MODE);
+
+    // Spending was tracked per epoch while the rolling window was enforced
+    try {
+      await withdraw(WITHDRAW_AMOUNT);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("SpendingLimitExceeded");
+    }
+  });
+
+  it("should reject an unknown mode", async () => {
+    try {
+      await updateConfig(null, 2);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidSpendingLimitMode");
+    }
+  });
+});
@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  WITHDRAW_AMOUNT,
+} from "./test_utils";
+
+describe("treasury_vault_spending_caps", () => {
+  let ctx: TestContext;
+  const USER_LIMIT = new BN(300000);
+  const RECIPIENT_LIMIT = new BN(150000);
+
+  const setUserSpendingLimit = async (limit: BN | null) => {
+    await ctx.program.methods
+      .setUserSpendingLimit(null, limit) // SOL
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        userAccount: ctx.treasurerUserPDA,
+        proposal: null,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const setRecipientSpendingLimit = async (limit: BN | null) => {
+    await ctx.program.methods
+      .setRecipientSpendingLimit(null, limit) // SOL
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        recipientAccount: ctx.recipientPDA,
+        proposal: null,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const withdraw = async (amount: BN) => {
+    await ctx.program.methods
+      .withdraw(amount, createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    for (let i = 0; i < 2; i++) {
+      await ctx.program.methods
+        .deposit(DEPOSIT_AMOUNT, createTimestamp())
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          depositor: ctx.depositor.publicKey,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.depositor])
+        .rpc();
+    }
+  });
+
+  it("should store a per-user limit for SOL", async () => {
+    await setUserSpendingLimit(USER_LIMIT);
+
+    const treasurerUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+    expect(treasurerUser.epochLimits.length).to.equal(1);
+    expect(treasurerUser.epochLimits[0].tokenMint).to.be.null;
+    expect(treasurerUser.epochLimits[0].limit.toString()).to.equal(USER_LIMIT.toString());
+  });
+
+  it("should stop a user at their own limit", async () => {
+    await withdraw(WITHDRAW_AMOUNT);
+
+    try {
+      await withdraw(WITHDRAW_AMOUNT);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UserSpendingLimitExceeded");
+    }
+
+    const treasurerUser = await ctx.program.account.treasuryUser.fetch(ctx.treasurerUserPDA);
+    expect(treasurerUser.epochLimits[0].epochSpending.toString()).to.equal(WITHDRAW_AMOUNT.toString());
+  });
+
+  it("should stop payments to a recipient at its cap", async () => {
+    await setUserSpendingLimit(null);
+    await setRecipientSpendingLimit(RECIPIENT_LIMIT);
+
+    try {
+      await withdraw(WITHDRAW_AMOUNT);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("RecipientSpendingLimitExceeded");
+    }
+
+    // Smaller withdrawals still fit under the cap
+    await withdraw(RECIPIENT_LIMIT);
+
+    const recipient = await ctx.program.account.whitelistedRecipient.fetch(ctx.recipientPDA);
+    expect(recipient.epochLimits[0].epochSpending.toString()).to.equal(RECIPIENT_LIMIT.toString());
+  });
+
+  it("should remove a limit", async () => {
+    await setRecipientSpendingLimit(null);
+
+    const recipient = await ctx.program.account.whitelistedRecipient.fetch(ctx.recipientPDA);
+    expect(recipient.epochLimits.length).to.equal(0);
+  });
+
+  it("should reject a zero limit", async () => {
+    try {
+      await setUserSpendingLimit(new BN(0));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidSpendingLimit");
+    }
+  });
+
+  it("should not let a treasurer change limits", async () => {
+    try {
+      await ctx.program.methods
+        .setUserSpendingLimit(null, new BN(1))
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: ctx.treasurer.publicKey,
+          user: ctx.treasurerUserPDA,
+          userAccount: ctx.treasurerUserPDA,
+          proposal: null,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedConfigUpdate");
+    }
+  });
+});
@@ -41,7 +41,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       const depositTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Deposit exact amount (100,000 with 6 decimals = 0.1 tokens)
       const exactAmount = new BN(100000);
@@ -71,7 +71,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       const withdrawTimestamp = createTimestamp();
       
       // Find audit log PDA for withdrawal
-      const withdrawAuditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+      const withdrawAuditLogPDA = await findAuditLogPDA(ctx);
       
       // Withdraw the exact same amount
       await ctx.program.methods
@@ -85,6 +85,7 @@ describe("treasury_vault_spl_edge_cases", () => {
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
           recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
           auditLog: withdrawAuditLogPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -114,7 +115,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       
       // Deposit first token type (100,000 with 6 decimals = 0.1 tokens)
       const firstDepositTimestamp = createTimestamp(-50);
-      const firstAuditLogPDA = await findAuditLogPDA(ctx, firstDepositTimestamp, ctx.depositor.publicKey);
+      const firstAuditLogPDA = await findAuditLogPDA(ctx);
       const firstAmount = new BN(100000);
       
       try {
@@ -127,7 +128,7 @@ describe("treasury_vault_spl_edge_cases", () => {
           
           if (existingBalance.gt(new BN(0))) {
             const withdrawTimestamp = createTimestamp(-60);
-            const auditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+            const auditLogPDA = await findAuditLogPDA(ctx);
             
             await ctx.program.methods
               .withdrawToken(existingBalance, withdrawTimestamp)
@@ -140,6 +141,7 @@ describe("treasury_vault_spl_edge_cases", () => {
                 recipientTokenAccount: tokenCtx.recipientTokenAccount,
                 tokenMint: tokenCtx.tokenMint,
                 recipient: ctx.recipient.publicKey,
+                whitelistedRecipient: ctx.recipientPDA,
                 auditLog: auditLogPDA,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
@@ -177,7 +179,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       
       // Deposit second token type (100,000,000 with 9 decimals = 0.1 tokens)
       const secondDepositTimestamp = createTimestamp(-45);
-      const secondAuditLogPDA = await findAuditLogPDA(ctx, secondDepositTimestamp, ctx.depositor.publicKey);
+      const secondAuditLogPDA = await findAuditLogPDA(ctx);
       const secondAmount = new BN(100000000);
       
       try {
@@ -190,7 +192,7 @@ describe("treasury_vault_spl_edge_cases", () => {
           
           if (existingBalance.gt(new BN(0))) {
             const withdrawTimestamp = createTimestamp(-55);
-            const auditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+            const auditLogPDA = await findAuditLogPDA(ctx);
             
             await ctx.program.methods
               .withdrawToken(existingBalance, withdrawTimestamp)
@@ -203,6 +205,7 @@ describe("treasury_vault_spl_edge_cases", () => {
                 recipientTokenAccount: secondTokenCtx.recipientTokenAccount,
                 tokenMint: secondTokenCtx.tokenMint,
                 recipient: ctx.recipient.publicKey,
+                whitelistedRecipient: ctx.recipientPDA,
                 auditLog: auditLogPDA,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
@@ -252,8 +255,8 @@ describe("treasury_vault_spl_edge_cases", () => {
       const secondDepositTimestamp = createTimestamp(-15);
       
       // Find audit log PDAs
-      const firstAuditLogPDA = await findAuditLogPDA(ctx, firstDepositTimestamp, ctx.depositor.publicKey);
-      const secondAuditLogPDA = await findAuditLogPDA(ctx, secondDepositTimestamp, ctx.depositor.publicKey);
+      const firstAuditLogPDA = await findAuditLogPDA(ctx);
+      const secondAuditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial balances
       const initialFirstBalance = (await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA)).balance;
@@ -330,8 +333,8 @@ describe("treasury_vault_spl_edge_cases", () => {
       const secondWithdrawTimestamp = createTimestamp(-20);
       
       // Find audit log PDAs
-      const firstAuditLogPDA = await findAuditLogPDA(ctx, firstWithdrawTimestamp, ctx.treasurer.publicKey);
-      const secondAuditLogPDA = await findAuditLogPDA(ctx, secondWithdrawTimestamp, ctx.treasurer.publicKey);
+      const firstAuditLogPDA = await findAuditLogPDA(ctx);
+      const secondAuditLogPDA = await findAuditLogPDA(ctx);
       
       // Withdraw half of first token type (50,000 with 6 decimals = 0.05 tokens)
       const firstWithdrawAmount = new BN(50000);
@@ -347,6 +350,7 @@ describe("treasury_vault_spl_edge_cases", () => {
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
           recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
           auditLog: firstAuditLogPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -369,6 +373,7 @@ describe("treasury_vault_spl_edge_cases", () => {
           recipientTokenAccount: secondTokenCtx.recipientTokenAccount,
           tokenMint: secondTokenCtx.tokenMint,
           recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
           auditLog: secondAuditLogPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -416,7 +421,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       
       for (let i = 0; i < 5; i++) {
         const depositTimestamp = createTimestamp(-20 - i); // Different timestamps
-        const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+        const auditLogPDA = await findAuditLogPDA(ctx);
         
         depositPromises.push(
           ctx.program.methods
@@ -454,7 +459,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       const depositTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Deposit minimum amount (1 with 6 decimals = 0.000001 tokens)
       const minAmount = new BN(1);
@@ -484,7 +489,7 @@ describe("treasury_vault_spl_edge_cases", () => {
       const withdrawTimestamp = createTimestamp();
       
       // Find audit log PDA for withdrawal
-      const withdrawAuditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+      const withdrawAuditLogPDA = await findAuditLogPDA(ctx);
       
       // Withdraw the minimum amount
       await ctx.program.methods
@@ -498,6 +503,7 @@ describe("treasury_vault_spl_edge_cases", () => {
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
           recipient: ctx.recipient.publicKey,
+          whitelistedRecipient: ctx.recipientPDA,
           auditLog: withdrawAuditLogPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -52,7 +52,7 @@ describe("treasury_vault_spl_failures", () => {
     
     // Make an initial deposit to have some tokens in the treasury
     const depositTimestamp = createTimestamp();
-    const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+    const auditLogPDA = await findAuditLogPDA(ctx);
     
     await ctx.program.methods
       .depositToken(DEPOSIT_AMOUNT, depositTimestamp)
@@ -78,7 +78,7 @@ describe("treasury_vault_spl_failures", () => {
   const depositTimestamp = createTimestamp();
   
   // Find audit log PDA
-  const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+  const auditLogPDA = await findAuditLogPDA(ctx);
   
   try {
     // Try to deposit 0 tokens
@@ -127,7 +127,7 @@ describe("treasury_vault_spl_failures", () => {
       const futureTimestamp = createTimestamp(3600); // 1 hour in the future
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, futureTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       try {
         // Try to deposit with future timestamp
@@ -160,7 +160,7 @@ describe("treasury_vault_spl_failures", () => {
       const depositTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Find token balance PDA for the invalid mint
       const [invalidTokenBalancePDA] = await anchor.web3.PublicKey.findProgramAddressSync(
@@ -206,7 +206,7 @@ describe("treasury_vault_spl_failures", () => {
       const withdrawTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.depositor.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       try {
         // Try to withdraw as non-treasurer
@@ -221,6 +221,7 @@ describe("treasury_vault_spl_failures", () => {
             recipientTokenAccount: tokenCtx.recipientTokenAccount,
             tokenMint: tokenCtx.tokenMint,
             recipient: ctx.recipient.publicKey,
+            whitelistedRecipient: ctx.recipientPDA,
             auditLog: auditLogPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
@@ -242,7 +243,7 @@ describe("treasury_vault_spl_failures", () => {
       const withdrawTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get current token balance
       const tokenBalanceAccount = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
@@ -262,6 +263,7 @@ describe("treasury_vault_spl_failures", () => {
             recipientTokenAccount: tokenCtx.recipientTokenAccount,
             tokenMint: tokenCtx.tokenMint,
             recipient: ctx.recipient.publicKey,
+            whitelistedRecipient: ctx.recipientPDA,
             auditLog: auditLogPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
@@ -282,7 +284,7 @@ describe("treasury_vault_spl_failures", () => {
       const withdrawTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, withdrawTimestamp, ctx.treasurer.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Create an associated token account for the invalid mint
       const invalidRecipientTokenAccount = await getOrCreateAssociatedTokenAccount(
@@ -305,6 +307,7 @@ describe("treasury_vault_spl_failures", () => {
             recipientTokenAccount: invalidRecipientTokenAccount.address, // This is for the invalid mint
             tokenMint: tokenCtx.tokenMint,
             recipient: ctx.recipient.publicKey,
+            whitelistedRecipient: ctx.recipientPDA,
             auditLog: auditLogPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
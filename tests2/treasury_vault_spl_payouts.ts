@@ -34,7 +34,7 @@ describe("treasury_vault_spl_payouts", () => {
     
     // Make an initial deposit to have tokens in the treasury
     const depositTimestamp = createTimestamp();
-    const auditLogPDA = await findAuditLogPDA(ctx, depositTimestamp, ctx.depositor.publicKey);
+    const auditLogPDA = await findAuditLogPDA(ctx);
     
     // Deposit 1 token (1,000,000 with 6 decimals)
     await ctx.program.methods
@@ -67,7 +67,12 @@ describe("treasury_vault_spl_payouts", () => {
           scheduleTime,
           false, // Not recurring
           new BN(0), // No recurrence interval
-          new BN(1) // Index 1
+          new BN(1), // Index 1
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: ctx.admin.publicKey,
@@ -76,6 +81,7 @@ describe("treasury_vault_spl_payouts", () => {
           recipient: ctx.recipientPDA,
           payoutSchedule: payoutCtx.payoutSchedulePDA,
           tokenMint: tokenCtx.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([ctx.admin])
@@ -99,7 +105,7 @@ describe("treasury_vault_spl_payouts", () => {
       const executeTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, executeTimestamp, ctx.treasurer.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial balances
       const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
@@ -122,7 +128,10 @@ describe("treasury_vault_spl_payouts", () => {
           treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
+          gateTokenAccount: null,
+          gateMetadata: null,
           auditLog: auditLogPDA,
+          tipAuditLog: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -161,7 +170,7 @@ describe("treasury_vault_spl_payouts", () => {
   const executeTimestamp = createTimestamp();
   
   // Find audit log PDA
-  const auditLogPDA = await findAuditLogPDA(ctx, executeTimestamp, ctx.treasurer.publicKey);
+  const auditLogPDA = await findAuditLogPDA(ctx);
   
   try {
     // Try to execute the same payout again
@@ -177,7 +186,10 @@ describe("treasury_vault_spl_payouts", () => {
         treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
         recipientTokenAccount: tokenCtx.recipientTokenAccount,
         tokenMint: tokenCtx.tokenMint,
+        gateTokenAccount: null,
+        gateMetadata: null,
         auditLog: auditLogPDA,
+        tipAuditLog: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -224,7 +236,12 @@ describe("treasury_vault_spl_payouts", () => {
           scheduleTime,
           true, // Recurring
           recurrenceInterval,
-          new BN(2) // Index 2
+          new BN(2), // Index 2
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: null, // Default catch-up policy
+          }
         )
         .accounts({
           authority: ctx.admin.publicKey,
@@ -233,6 +250,7 @@ describe("treasury_vault_spl_payouts", () => {
           recipient: ctx.recipientPDA,
           payoutSchedule: payoutCtx.recurringPayoutPDA,
           tokenMint: tokenCtx.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([ctx.admin])
@@ -254,7 +272,7 @@ describe("treasury_vault_spl_payouts", () => {
       const executeTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, executeTimestamp, ctx.treasurer.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial balances
       const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
@@ -277,7 +295,10 @@ describe("treasury_vault_spl_payouts", () => {
           treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
+          gateTokenAccount: null,
+          gateMetadata: null,
           auditLog: auditLogPDA,
+          tipAuditLog: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -309,7 +330,7 @@ describe("treasury_vault_spl_payouts", () => {
   const executeTimestamp = createTimestamp();
   
   // Find audit log PDA
-  const auditLogPDA = await findAuditLogPDA(ctx, executeTimestamp, ctx.treasurer.publicKey);
+  const auditLogPDA = await findAuditLogPDA(ctx);
   
   try {
     // Try to execute the recurring payout again immediately
@@ -325,7 +346,10 @@ describe("treasury_vault_spl_payouts", () => {
         treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
         recipientTokenAccount: tokenCtx.recipientTokenAccount,
         tokenMint: tokenCtx.tokenMint,
+        gateTokenAccount: null,
+        gateMetadata: null,
         auditLog: auditLogPDA,
+        tipAuditLog: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -367,7 +391,7 @@ describe("treasury_vault_spl_payouts", () => {
       const executeTimestamp = createTimestamp();
       
       // Find audit log PDA
-      const auditLogPDA = await findAuditLogPDA(ctx, executeTimestamp, ctx.treasurer.publicKey);
+      const auditLogPDA = await findAuditLogPDA(ctx);
       
       // Get initial balances
       const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
@@ -390,7 +414,10 @@ describe("treasury_vault_spl_payouts", () => {
           treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
           recipientTokenAccount: tokenCtx.recipientTokenAccount,
           tokenMint: tokenCtx.tokenMint,
+          gateTokenAccount: null,
+          gateMetadata: null,
           auditLog: auditLogPDA,
+          tipAuditLog: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -427,6 +454,7 @@ describe("treasury_vault_spl_payouts", () => {
       user: ctx.adminUserPDA,
       recipient: ctx.recipientPDA, // Add the recipient account
       payoutSchedule: payoutCtx.recurringPayoutPDA,
+      auditLog: await findAuditLogPDA(ctx),
       systemProgram: anchor.web3.SystemProgram.programId, // Add the system program
     })
     .signers([ctx.admin])
@@ -441,7 +469,7 @@ describe("treasury_vault_spl_payouts", () => {
   const executeTimestamp = createTimestamp();
   
   // Find audit log PDA
-  const auditLogPDA = await findAuditLogPDA(ctx, executeTimestamp, ctx.treasurer.publicKey);
+  const auditLogPDA = await findAuditLogPDA(ctx);
   
   try {
     // Try to execute the cancelled payout
@@ -457,7 +485,10 @@ describe("treasury_vault_spl_payouts", () => {
         treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
         recipientTokenAccount: tokenCtx.recipientTokenAccount,
         tokenMint: tokenCtx.tokenMint,
+        gateTokenAccount: null,
+        gateMetadata: null,
         auditLog: auditLogPDA,
+        tipAuditLog: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -488,4 +519,241 @@ describe("treasury_vault_spl_payouts", () => {
   }
 });
   });
+
+  describe("Bounded Recurring Token Payouts", () => {
+    const findPayoutPDA = (index: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("payout"),
+          ctx.recipient.publicKey.toBuffer(),
+          ctx.treasuryPDA.toBuffer(),
+          new BN(index).toArrayLike(Buffer, "le", 8),
+        ],
+        ctx.program.programId
+      )[0];
+
+    it("should complete a recurring payout after its final execution", async () => {
+      const boundedPayoutPDA = findPayoutPDA(3);
+
+      await ctx.program.methods
+        .schedulePayout(
+          PAYOUT_AMOUNT,
+          createTimestamp(3),
+          true, // Recurring
+          new BN(60), // Every minute
+          new BN(3), // Index 3
+          {
+            endTime: null, // No end time
+            maxExecutions: 1, // Single execution
+            catchUpPolicy: null, // Default catch-up policy
+          }
+        )
+        .accounts({
+          authority: ctx.admin.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.adminUserPDA,
+          recipient: ctx.recipientPDA,
+          payoutSchedule: boundedPayoutPDA,
+          tokenMint: tokenCtx.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.admin])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      const executeTimestamp = createTimestamp();
+      const auditLogPDA = await findAuditLogPDA(ctx);
+
+      await ctx.program.methods
+        .executeTokenPayout(executeTimestamp)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          recipient: ctx.recipientPDA,
+          payoutSchedule: boundedPayoutPDA,
+          tokenBalance: tokenCtx.tokenBalancePDA,
+          treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+          recipientTokenAccount: tokenCtx.recipientTokenAccount,
+          tokenMint: tokenCtx.tokenMint,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          auditLog: auditLogPDA,
+          tipAuditLog: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+
+      const payoutSchedule = await ctx.program.account.payoutSchedule.fetch(boundedPayoutPDA);
+      expect(payoutSchedule.executionCount).to.equal(1);
+      expect(payoutSchedule.maxExecutions).to.equal(1);
+      expect(payoutSchedule.isActive).to.be.false;
+    });
+
+    it("should reject an end time on a one-time payout", async () => {
+      try {
+        await ctx.program.methods
+          .schedulePayout(
+            PAYOUT_AMOUNT,
+            createTimestamp(60),
+            false, // Not recurring
+            new BN(0), // No recurrence interval
+            new BN(4), // Index 4
+            {
+              endTime: createTimestamp(120), // End time
+              maxExecutions: null, // No execution cap
+              catchUpPolicy: null, // Default catch-up policy
+            }
+          )
+          .accounts({
+            authority: ctx.admin.publicKey,
+            treasury: ctx.treasuryPDA,
+            user: ctx.adminUserPDA,
+            recipient: ctx.recipientPDA,
+            payoutSchedule: findPayoutPDA(4),
+            tokenMint: tokenCtx.tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([ctx.admin])
+          .rpc();
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidPayoutBounds");
+      }
+    });
+  });
+
+  describe("Catch-up of Missed Recurring Periods", () => {
+    const findPayoutPDA = (index: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("payout"),
+          ctx.recipient.publicKey.toBuffer(),
+          ctx.treasuryPDA.toBuffer(),
+          new BN(index).toArrayLike(Buffer, "le", 8),
+        ],
+        ctx.program.programId
+      )[0];
+
+    const scheduleCatchUpPayout = async (index: number, scheduleTime: BN, catchUpPolicy: number | null) => {
+      await ctx.program.methods
+        .schedulePayout(
+          PAYOUT_AMOUNT,
+          scheduleTime,
+          true, // Recurring
+          new BN(2), // Every two seconds
+          new BN(index),
+          {
+            endTime: null, // No end time
+            maxExecutions: null, // No execution cap
+            catchUpPolicy: catchUpPolicy,
+          }
+        )
+        .accounts({
+          authority: ctx.admin.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.adminUserPDA,
+          recipient: ctx.recipientPDA,
+          payoutSchedule: findPayoutPDA(index),
+          tokenMint: tokenCtx.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.admin])
+        .rpc();
+    };
+
+    const executePayout = async (payoutSchedulePDA: anchor.web3.PublicKey) => {
+      const executeTimestamp = createTimestamp();
+      const auditLogPDA = await findAuditLogPDA(ctx);
+
+      await ctx.program.methods
+        .executeTokenPayout(executeTimestamp)
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          recipient: ctx.recipientPDA,
+          payoutSchedule: payoutSchedulePDA,
+          tokenBalance: tokenCtx.tokenBalancePDA,
+          treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+          recipientTokenAccount: tokenCtx.recipientTokenAccount,
+          tokenMint: tokenCtx.tokenMint,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          auditLog: auditLogPDA,
+          tipAuditLog: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+    };
+
+    it("should pay every missed period by default", async () => {
+      const payoutPDA = findPayoutPDA(5);
+      const scheduleTime = createTimestamp(2);
+      await scheduleCatchUpPayout(5, scheduleTime, null);
+
+      // Miss a few periods
+      await new Promise(resolve => setTimeout(resolve, 7000));
+
+      const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+      await executePayout(payoutPDA);
+
+      const payoutSchedule = await ctx.program.account.payoutSchedule.fetch(payoutPDA);
+      const periods = payoutSchedule.executionCount;
+      expect(periods).to.be.greaterThan(1);
+
+      // Recurrence stays anchored to the schedule time
+      expect(payoutSchedule.nextDueTime.toString()).to.equal(
+        scheduleTime.add(new BN(2 * periods)).toString()
+      );
+
+      const updatedTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+      expect(initialTokenBalance.balance.sub(updatedTokenBalance.balance).toString()).to.equal(
+        PAYOUT_AMOUNT.muln(periods).toString()
+      );
+    });
+
+    it("should pay once and skip missed periods when requested", async () => {
+      const payoutPDA = findPayoutPDA(6);
+      const scheduleTime = createTimestamp(2);
+      await scheduleCatchUpPayout(6, scheduleTime, 1); // Pay once and skip
+
+      await new Promise(resolve => setTimeout(resolve, 7000));
+
+      const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+      await executePayout(payoutPDA);
+
+      const payoutSchedule = await ctx.program.account.payoutSchedule.fetch(payoutPDA);
+      expect(payoutSchedule.executionCount).to.equal(1);
+
+      // The next period is still on the original grid and in the future
+      const nextDueOffset = payoutSchedule.nextDueTime.sub(scheduleTime).toNumber();
+      expect(nextDueOffset % 2).to.equal(0);
+      expect(payoutSchedule.nextDueTime.toNumber()).to.be.greaterThan(payoutSchedule.lastExecuted.toNumber());
+
+      const updatedTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+      expect(initialTokenBalance.balance.sub(updatedTokenBalance.balance).toString()).to.equal(
+        PAYOUT_AMOUNT.toString()
+      );
+    });
+
+    it("should reject an unknown catch-up policy", async () => {
+      try {
+        await scheduleCatchUpPayout(7, createTimestamp(60), 3);
+        expect.fail("Expected error was not thrown");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidCatchUpPolicy");
+      }
+    });
+  });
 });
\ No newline at end of file
@@ -0,0 +1,235 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  AUDIT_ACTION_WITHDRAWAL_VETOED,
+  DEPOSIT_AMOUNT,
+} from "./test_utils";
+
+describe("treasury_vault_timelock", () => {
+  let ctx: TestContext;
+  const TIMELOCK_THRESHOLD = new BN(200000);
+  const TIMELOCK_DELAY = new BN(3); // Seconds
+  const LARGE_WITHDRAW_AMOUNT = new BN(300000);
+
+  const findPendingWithdrawalPDA = async () => {
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("pending_withdrawal"),
+        ctx.treasuryPDA.toBuffer(),
+        treasury.nextPendingWithdrawalIndex.toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    )[0];
+  };
+
+  const setWithdrawalTimelock = async (threshold: BN, delay: BN) => {
+    await ctx.program.methods
+      .setWithdrawalTimelock(threshold, delay)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const withdraw = async (amount: BN) => {
+    await ctx.program.methods
+      .withdraw(amount, createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  const queueWithdrawal = async (amount: BN) => {
+    const pendingWithdrawalPDA = await findPendingWithdrawalPDA();
+    await ctx.program.methods
+      .queueWithdrawal(amount)
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        pendingWithdrawal: pendingWithdrawalPDA,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+    return pendingWithdrawalPDA;
+  };
+
+  const executeWithdrawal = async (pendingWithdrawal: anchor.web3.PublicKey) => {
+    await ctx.program.methods
+      .executeWithdrawal(createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        pendingWithdrawal,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        rentReceiver: ctx.treasurer.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    for (let i = 0; i < 2; i++) {
+      await ctx.program.methods
+        .deposit(DEPOSIT_AMOUNT, createTimestamp())
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          depositor: ctx.depositor.publicKey,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.depositor])
+        .rpc();
+    }
+
+    await setWithdrawalTimelock(TIMELOCK_THRESHOLD, TIMELOCK_DELAY);
+  });
+
+  it("should store the timelock policy", async () => {
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.withdrawalTimelockThreshold.toString()).to.equal(TIMELOCK_THRESHOLD.toString());
+    expect(treasury.withdrawalTimelockDelay.toString()).to.equal(TIMELOCK_DELAY.toString());
+  });
+
+  it("should keep small withdrawals immediate", async () => {
+    const initialBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    await withdraw(TIMELOCK_THRESHOLD);
+    const finalBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalBalance - initialBalance).to.equal(TIMELOCK_THRESHOLD.toNumber());
+  });
+
+  it("should reject an immediate withdrawal above the threshold", async () => {
+    try {
+      await withdraw(LARGE_WITHDRAW_AMOUNT);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("WithdrawalTimelockRequired");
+    }
+  });
+
+  it("should queue a large withdrawal and execute it after the delay", async () => {
+    const pendingWithdrawalPDA = await queueWithdrawal(LARGE_WITHDRAW_AMOUNT);
+
+    const pendingWithdrawal = await ctx.program.account.pendingWithdrawal.fetch(pendingWithdrawalPDA);
+    expect(pendingWithdrawal.amount.toString()).to.equal(LARGE_WITHDRAW_AMOUNT.toString());
+    expect(pendingWithdrawal.recipient.toString()).to.equal(ctx.recipient.publicKey.toString());
+    expect(pendingWithdrawal.executableAt.sub(pendingWithdrawal.queuedAt).toString()).to.equal(
+      TIMELOCK_DELAY.toString()
+    );
+
+    // The delay has not elapsed yet
+    try {
+      await executeWithdrawal(pendingWithdrawalPDA);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("WithdrawalTimelockActive");
+    }
+
+    await new Promise(resolve => setTimeout(resolve, (TIMELOCK_DELAY.toNumber() + 1) * 1000));
+
+    const initialBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    await executeWithdrawal(pendingWithdrawalPDA);
+    const finalBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalBalance - initialBalance).to.equal(LARGE_WITHDRAW_AMOUNT.toNumber());
+
+    // The pending withdrawal is closed once executed
+    const closed = await ctx.program.account.pendingWithdrawal.fetchNullable(pendingWithdrawalPDA);
+    expect(closed).to.be.null;
+  });
+
+  it("should let an admin veto a queued withdrawal", async () => {
+    const pendingWithdrawalPDA = await queueWithdrawal(LARGE_WITHDRAW_AMOUNT);
+    const initialTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    await ctx.program.methods
+      .vetoWithdrawal()
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        pendingWithdrawal: pendingWithdrawalPDA,
+        rentReceiver: ctx.treasurer.publicKey,
+        auditLog: auditLogPDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const closed = await ctx.program.account.pendingWithdrawal.fetchNullable(pendingWithdrawalPDA);
+    expect(closed).to.be.null;
+
+    // No funds left the treasury
+    const finalTreasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(finalTreasury.totalFunds.toString()).to.equal(initialTreasury.totalFunds.toString());
+
+    // The veto is logged with the withdrawal it cancelled
+    const auditLog = await ctx.program.account.auditLog.fetch(auditLogPDA);
+    expect(auditLog.action).to.equal(AUDIT_ACTION_WITHDRAWAL_VETOED);
+    expect(auditLog.initiator.toString()).to.equal(ctx.admin.publicKey.toString());
+    expect(auditLog.target.toString()).to.equal(ctx.recipient.publicKey.toString());
+    expect(auditLog.amount.toString()).to.equal(LARGE_WITHDRAW_AMOUNT.toString());
+  });
+
+  it("should not let a treasurer veto a queued withdrawal", async () => {
+    const pendingWithdrawalPDA = await queueWithdrawal(LARGE_WITHDRAW_AMOUNT);
+
+    try {
+      await ctx.program.methods
+        .vetoWithdrawal()
+        .accounts({
+          authority: ctx.treasurer.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.treasurerUserPDA,
+          pendingWithdrawal: pendingWithdrawalPDA,
+          rentReceiver: ctx.treasurer.publicKey,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedUser");
+    }
+  });
+
+  it("should reject a delay above the maximum", async () => {
+    try {
+      await setWithdrawalTimelock(TIMELOCK_THRESHOLD, new BN(30 * 86400 + 1));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidWithdrawalDelay");
+    }
+  });
+});
@@ -0,0 +1,217 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  setTokenSpendingLimit,
+  createTimestamp,
+  findAuditLogPDA,
+  SPENDING_LIMIT,
+} from "./test_utils";
+
+describe("treasury_vault_token_2022", () => {
+  let ctx: TestContext;
+  let tokenMint: anchor.web3.PublicKey;
+  let treasuryTokenAccount: anchor.web3.PublicKey;
+  let depositorTokenAccount: anchor.web3.PublicKey;
+  let recipientTokenAccount: anchor.web3.PublicKey;
+  let tokenBalancePDA: anchor.web3.PublicKey;
+
+  const DECIMALS = 6;
+  const FEE_BASIS_POINTS = 100; // 1% transfer fee
+  const MAX_FEE = BigInt(1_000_000);
+  const TOKEN_2022_DEPOSIT = new BN(100_000);
+  const TOKEN_2022_WITHDRAW = new BN(50_000);
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    // Create a Token-2022 mint with a transfer fee extension
+    const mintKeypair = anchor.web3.Keypair.generate();
+    tokenMint = mintKeypair.publicKey;
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await ctx.provider.connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const transaction = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: ctx.admin.publicKey,
+        newAccountPubkey: tokenMint,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        tokenMint,
+        ctx.admin.publicKey,
+        ctx.admin.publicKey,
+        FEE_BASIS_POINTS,
+        MAX_FEE,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        tokenMint,
+        DECIMALS,
+        ctx.admin.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await anchor.web3.sendAndConfirmTransaction(
+      ctx.provider.connection,
+      transaction,
+      [ctx.admin, mintKeypair]
+    );
+
+    depositorTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      ctx.provider.connection,
+      ctx.admin,
+      tokenMint,
+      ctx.depositor.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+
+    recipientTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      ctx.provider.connection,
+      ctx.admin,
+      tokenMint,
+      ctx.recipient.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+
+    treasuryTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      ctx.provider.connection,
+      ctx.admin,
+      tokenMint,
+      ctx.treasuryPDA,
+      true,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+
+    await mintTo(
+      ctx.provider.connection,
+      ctx.admin,
+      tokenMint,
+      depositorTokenAccount,
+      ctx.admin.publicKey,
+      10 ** DECIMALS,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    [tokenBalancePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("token_balance"),
+        ctx.treasuryPDA.toBuffer(),
+        tokenMint.toBuffer(),
+      ],
+      ctx.program.programId
+    );
+
+    await setTokenSpendingLimit(ctx, tokenMint, SPENDING_LIMIT);
+  });
+
+  it("should credit only the amount received for transfer-fee mints", async () => {
+    const depositTimestamp = createTimestamp();
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    await ctx.program.methods
+      .depositToken(TOKEN_2022_DEPOSIT, depositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        tokenBalance: tokenBalancePDA,
+        treasuryTokenAccount,
+        depositorTokenAccount,
+        tokenMint,
+        auditLog: auditLogPDA,
+        depositor: ctx.depositor.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    const fee = TOKEN_2022_DEPOSIT.muln(FEE_BASIS_POINTS).divn(10_000);
+    const expectedReceived = TOKEN_2022_DEPOSIT.sub(fee);
+
+    const tokenBalance = await ctx.program.account.tokenBalance.fetch(tokenBalancePDA);
+    expect(tokenBalance.balance.toString()).to.equal(expectedReceived.toString());
+
+    const treasuryAccount = await getAccount(
+      ctx.provider.connection,
+      treasuryTokenAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(treasuryAccount.amount.toString()).to.equal(expectedReceived.toString());
+
+    const auditLog = await ctx.program.account.auditLog.fetch(auditLogPDA);
+    expect(auditLog.amount.toString()).to.equal(expectedReceived.toString());
+  });
+
+  it("should withdraw from a Token-2022 treasury account", async () => {
+    const withdrawTimestamp = createTimestamp();
+    const auditLogPDA = await findAuditLogPDA(ctx);
+
+    const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenBalancePDA);
+
+    await ctx.program.methods
+      .withdrawToken(TOKEN_2022_WITHDRAW, withdrawTimestamp)
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        tokenBalance: tokenBalancePDA,
+        treasuryTokenAccount,
+        recipientTokenAccount,
+        tokenMint,
+        recipient: ctx.recipient.publicKey,
+        whitelistedRecipient: ctx.recipientPDA,
+        auditLog: auditLogPDA,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+
+    const tokenBalance = await ctx.program.account.tokenBalance.fetch(tokenBalancePDA);
+    expect(tokenBalance.balance.toString()).to.equal(
+      initialTokenBalance.balance.sub(TOKEN_2022_WITHDRAW).toString()
+    );
+
+    // The recipient receives the withdrawal minus the mint's transfer fee
+    const fee = TOKEN_2022_WITHDRAW.muln(FEE_BASIS_POINTS).divn(10_000);
+    const recipientAccount = await getAccount(
+      ctx.provider.connection,
+      recipientTokenAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(recipientAccount.amount.toString()).to.equal(
+      TOKEN_2022_WITHDRAW.sub(fee).toString()
+    );
+  });
+});
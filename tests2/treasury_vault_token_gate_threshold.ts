@@ -0,0 +1,164 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  PAYOUT_AMOUNT,
+  DEFAULT_PAYOUT_OPTIONS,
+} from "./test_utils";
+
+describe("treasury_vault_token_gate_threshold", () => {
+  let ctx: TestContext;
+  let gateMint: anchor.web3.PublicKey;
+  let gateTokenAccount: anchor.web3.PublicKey;
+  const GATE_HOLDING = 5;
+
+  const findPayoutPDA = (index: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payout"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    )[0];
+
+  const setTokenGate = async (tokenMint: anchor.web3.PublicKey | null, minBalance: BN) => {
+    await ctx.program.methods
+      .setTokenGate(minBalance, 0)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        tokenMint,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const executePayout = async (gateAccount: anchor.web3.PublicKey | null) => {
+    await ctx.program.methods
+      .executePayout(createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(1),
+        recipientWallet: ctx.recipient.publicKey,
+        gateTokenAccount: gateAccount,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(ctx),
+        tipAuditLog: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, createTimestamp())
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    // The recipient holds a few gate tokens in their own account
+    gateMint = await createMint(ctx.provider.connection, ctx.admin, ctx.admin.publicKey, null, 0);
+    gateTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.provider.connection,
+        ctx.admin,
+        gateMint,
+        ctx.recipient.publicKey
+      )
+    ).address;
+    await mintTo(ctx.provider.connection, ctx.admin, gateMint, gateTokenAccount, ctx.admin.publicKey, GATE_HOLDING);
+
+    await ctx.program.methods
+      .schedulePayout(PAYOUT_AMOUNT, createTimestamp(3), false, new BN(0), new BN(1), DEFAULT_PAYOUT_OPTIONS)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        payoutSchedule: findPayoutPDA(1),
+        tokenMint: null,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    await new Promise(resolve => setTimeout(resolve, 4000));
+  });
+
+  it("should store the gate threshold", async () => {
+    await setTokenGate(gateMint, new BN(GATE_HOLDING + 1));
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.gateTokenMint?.toString()).to.equal(gateMint.toString());
+    expect(treasury.gateMinBalance.toNumber()).to.equal(GATE_HOLDING + 1);
+  });
+
+  it("should reject a SOL payout when the recipient holds less than the threshold", async () => {
+    try {
+      await executePayout(gateTokenAccount);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("TokenGateCheckFailed");
+    }
+  });
+
+  it("should reject a gated SOL payout without a gate token account", async () => {
+    try {
+      await executePayout(null);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("TokenGateCheckFailed");
+    }
+  });
+
+  it("should pay out once the recipient meets the threshold", async () => {
+    await setTokenGate(gateMint, new BN(GATE_HOLDING));
+
+    const initialBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    await executePayout(gateTokenAccount);
+    const finalBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalBalance - initialBalance).to.equal(PAYOUT_AMOUNT.toNumber());
+  });
+
+  it("should reject a zero threshold for an enabled gate", async () => {
+    try {
+      await setTokenGate(gateMint, new BN(0));
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidGateMinBalance");
+    }
+  });
+
+  it("should clear the threshold when the gate is disabled", async () => {
+    await setTokenGate(null, new BN(0));
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.gateTokenMint).to.be.null;
+    expect(treasury.gateMinBalance.toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,252 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "bn.js";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  TestContext,
+  TokenContext,
+  setupTestContext,
+  initializeTreasury,
+  setupTokenContext,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+} from "./test_utils";
+
+describe("treasury_vault_vesting", () => {
+  let ctx: TestContext;
+  let tokenCtx: TokenContext;
+  const STREAM_AMOUNT = new BN(200000);
+
+  const findVestingPDA = (index: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vesting"),
+        ctx.recipient.publicKey.toBuffer(),
+        ctx.treasuryPDA.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      ctx.program.programId
+    )[0];
+
+  const createStream = async (
+    index: number,
+    startTime: BN,
+    cliffTime: BN | null,
+    endTime: BN,
+    tokenMint: anchor.web3.PublicKey | null
+  ) => {
+    await ctx.program.methods
+      .createVestingStream(STREAM_AMOUNT, startTime, cliffTime, endTime, new BN(index))
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipient: ctx.recipientPDA,
+        vestingStream: findVestingPDA(index),
+        tokenBalance: tokenMint ? tokenCtx.tokenBalancePDA : null,
+        tokenMint,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const claimTokens = async (index: number) => {
+    const claimTimestamp = createTimestamp();
+    await ctx.program.methods
+      .claimTokenVesting(claimTimestamp)
+      .accounts({
+        authority: ctx.recipient.publicKey,
+        treasury: ctx.treasuryPDA,
+        recipient: ctx.recipientPDA,
+        vestingStream: findVestingPDA(index),
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+        recipientTokenAccount: tokenCtx.recipientTokenAccount,
+        tokenMint: tokenCtx.tokenMint,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        auditLog: await findAuditLogPDA(ctx),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.recipient])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+    tokenCtx = await setupTokenContext(ctx);
+
+    const solDepositTimestamp = createTimestamp();
+    await ctx.program.methods
+      .deposit(DEPOSIT_AMOUNT, solDepositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        depositor: ctx.depositor.publicKey,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+
+    const tokenDepositTimestamp = createTimestamp(-4);
+    await ctx.program.methods
+      .depositToken(new BN(1000000), tokenDepositTimestamp)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        treasuryTokenAccount: tokenCtx.treasuryTokenAccount,
+        depositorTokenAccount: tokenCtx.depositorTokenAccount,
+        tokenMint: tokenCtx.tokenMint,
+        auditLog: await findAuditLogPDA(ctx),
+        depositor: ctx.depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ctx.depositor])
+      .rpc();
+  });
+
+  it("should reserve the stream amount from the token balance", async () => {
+    const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+
+    await createStream(0, createTimestamp(-1), null, createTimestamp(10), tokenCtx.tokenMint);
+
+    const stream = await ctx.program.account.vestingStream.fetch(findVestingPDA(0));
+    expect(stream.totalAmount.toString()).to.equal(STREAM_AMOUNT.toString());
+    expect(stream.claimedAmount.toNumber()).to.equal(0);
+    expect(stream.isActive).to.be.true;
+
+    const tokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+    expect(initialTokenBalance.balance.sub(tokenBalance.balance).toString()).to.equal(
+      STREAM_AMOUNT.toString()
+    );
+  });
+
+  it("should let the recipient claim the vested portion", async () => {
+    await new Promise(resolve => setTimeout(resolve, 3000));
+
+    const initialRecipientBalance = Number(
+      (await getAccount(ctx.provider.connection, tokenCtx.recipientTokenAccount)).amount
+    );
+
+    await claimTokens(0);
+
+    const stream = await ctx.program.account.vestingStream.fetch(findVestingPDA(0));
+    const claimed = stream.claimedAmount.toNumber();
+    expect(claimed).to.be.greaterThan(0);
+    expect(claimed).to.be.lessThan(STREAM_AMOUNT.toNumber());
+
+    const finalRecipientBalance = Number(
+      (await getAccount(ctx.provider.connection, tokenCtx.recipientTokenAccount)).amount
+    );
+    expect(finalRecipientBalance - initialRecipientBalance).to.equal(claimed);
+  });
+
+  it("should not allow claims before the cliff", async () => {
+    await createStream(1, createTimestamp(-1), createTimestamp(60), createTimestamp(120), tokenCtx.tokenMint);
+
+    try {
+      await claimTokens(1);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("NothingToClaim");
+    }
+  });
+
+  it("should return the unvested remainder when cancelled", async () => {
+    const initialTokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+
+    await ctx.program.methods
+      .cancelVestingStream()
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient: ctx.recipientPDA,
+        vestingStream: findVestingPDA(1),
+        tokenBalance: tokenCtx.tokenBalancePDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+
+    // Cancelled before the cliff, so the whole amount goes back to the treasury
+    const tokenBalance = await ctx.program.account.tokenBalance.fetch(tokenCtx.tokenBalancePDA);
+    expect(tokenBalance.balance.sub(initialTokenBalance.balance).toString()).to.equal(
+      STREAM_AMOUNT.toString()
+    );
+
+    const stream = await ctx.program.account.vestingStream.fetch(findVestingPDA(1));
+    expect(stream.totalAmount.toNumber()).to.equal(0);
+    expect(stream.isActive).to.be.false;
+  });
+
+  it("should stream SOL from the treasury", async () => {
+    await createStream(2, createTimestamp(-2), null, createTimestamp(1), null);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.totalFunds.toString()).to.equal(DEPOSIT_AMOUNT.sub(STREAM_AMOUNT).toString());
+
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    const claimTimestamp = createTimestamp();
+    await ctx.program.methods
+      .claimVesting(claimTimestamp)
+      .accounts({
+        authority: ctx.recipient.publicKey,
+        treasury: ctx.treasuryPDA,
+        recipient: ctx.recipientPDA,
+        vestingStream: findVestingPDA(2),
+        auditLog: await findAuditLogPDA(ctx),
+        gateTokenAccount: null,
+        gateMetadata: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.recipient])
+      .rpc();
+
+    // Fully vested and claimed, so the stream is closed
+    const stream = await ctx.program.account.vestingStream.fetch(findVestingPDA(2));
+    expect(stream.claimedAmount.toString()).to.equal(STREAM_AMOUNT.toString());
+    expect(stream.isActive).to.be.false;
+  });
+
+  it("should reject a cliff after the end time", async () => {
+    try {
+      await createStream(3, createTimestamp(), createTimestamp(120), createTimestamp(60), null);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidVestingSchedule");
+    }
+  });
+
+  it("should keep the recipient while a vesting stream is active", async () => {
+    await createStream(4, createTimestamp(), null, createTimestamp(3600), null);
+
+    // Streams are counted apart from payout schedules
+    const recipient = await ctx.program.account.whitelistedRecipient.fetch(ctx.recipientPDA);
+    expect(recipient.activeVestingCount).to.equal(1);
+    expect(recipient.activePayoutCount).to.equal(0);
+
+    try {
+      await ctx.program.methods
+        .removeWhitelistedRecipient()
+        .accounts({
+          authority: ctx.admin.publicKey,
+          treasury: ctx.treasuryPDA,
+          user: ctx.adminUserPDA,
+          recipientAccount: ctx.recipientPDA,
+        })
+        .signers([ctx.admin])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("ActiveVestingStreamsRemaining");
+    }
+  });
+});
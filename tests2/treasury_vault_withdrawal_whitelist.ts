@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TestContext,
+  setupTestContext,
+  initializeTreasury,
+  createTimestamp,
+  findAuditLogPDA,
+  DEPOSIT_AMOUNT,
+  WITHDRAW_AMOUNT,
+} from "./test_utils";
+
+describe("treasury_vault_withdrawal_whitelist", () => {
+  let ctx: TestContext;
+  const stranger = anchor.web3.Keypair.generate();
+
+  const findRecipientPDA = (wallet: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("recipient"), wallet.toBuffer(), ctx.treasuryPDA.toBuffer()],
+      ctx.program.programId
+    )[0];
+
+  const setWithdrawalWhitelist = async (required: boolean) => {
+    await ctx.program.methods
+      .setWithdrawalWhitelist(required)
+      .accounts({
+        treasury: ctx.treasuryPDA,
+        authority: ctx.admin.publicKey,
+        user: ctx.adminUserPDA,
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const setRecipientActive = async (isActive: boolean) => {
+    await ctx.program.methods
+      .updateWhitelistedRecipient(null, isActive)
+      .accounts({
+        authority: ctx.admin.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.adminUserPDA,
+        recipientAccount: ctx.recipientPDA,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  };
+
+  const withdrawTo = async (recipient: anchor.web3.PublicKey) => {
+    await ctx.program.methods
+      .withdraw(WITHDRAW_AMOUNT, createTimestamp())
+      .accounts({
+        authority: ctx.treasurer.publicKey,
+        treasury: ctx.treasuryPDA,
+        user: ctx.treasurerUserPDA,
+        recipient,
+        whitelistedRecipient: findRecipientPDA(recipient),
+        auditLog: await findAuditLogPDA(ctx),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([ctx.treasurer])
+      .rpc();
+  };
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeTreasury(ctx);
+
+    for (let i = 0; i < 2; i++) {
+      await ctx.program.methods
+        .deposit(DEPOSIT_AMOUNT, createTimestamp())
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          depositor: ctx.depositor.publicKey,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.depositor])
+        .rpc();
+    }
+  });
+
+  it("should allow withdrawals to any account by default", async () => {
+    const initialBalance = await ctx.provider.connection.getBalance(stranger.publicKey);
+    await withdrawTo(stranger.publicKey);
+    const finalBalance = await ctx.provider.connection.getBalance(stranger.publicKey);
+    expect(finalBalance - initialBalance).to.equal(WITHDRAW_AMOUNT.toNumber());
+  });
+
+  it("should reject withdrawals to non-whitelisted accounts once required", async () => {
+    await setWithdrawalWhitelist(true);
+
+    const treasury = await ctx.program.account.treasury.fetch(ctx.treasuryPDA);
+    expect(treasury.withdrawalsRequireWhitelist).to.be.true;
+
+    try {
+      await withdrawTo(stranger.publicKey);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("RecipientNotWhitelisted");
+    }
+  });
+
+  it("should still allow withdrawals to whitelisted recipients", async () => {
+    const initialBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    await withdrawTo(ctx.recipient.publicKey);
+    const finalBalance = await ctx.provider.connection.getBalance(ctx.recipient.publicKey);
+    expect(finalBalance - initialBalance).to.equal(WITHDRAW_AMOUNT.toNumber());
+  });
+
+  it("should reject withdrawals to deactivated recipients", async () => {
+    await setRecipientActive(false);
+
+    try {
+      await withdrawTo(ctx.recipient.publicKey);
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("RecipientNotActive");
+    }
+
+    await setRecipientActive(true);
+  });
+
+  it("should not let a treasurer lift the policy", async () => {
+    try {
+      await ctx.program.methods
+        .setWithdrawalWhitelist(false)
+        .accounts({
+          treasury: ctx.treasuryPDA,
+          authority: ctx.treasurer.publicKey,
+          user: ctx.treasurerUserPDA,
+          auditLog: await findAuditLogPDA(ctx),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([ctx.treasurer])
+        .rpc();
+      expect.fail("Expected error was not thrown");
+    } catch (error: any) {
+      expect(error.message).to.include("UnauthorizedConfigUpdate");
+    }
+  });
+});